@@ -0,0 +1,357 @@
+//! Derives for parsing puzzle input. They are re-exported from
+//! `advent_lib::helpers`, which has the traits and errors they use, so year
+//! crates don't depend on this crate directly.
+
+#![deny(clippy::all, clippy::pedantic)]
+#![warn(clippy::nursery)]
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input,
+    spanned::Spanned,
+    Attribute,
+    Data,
+    DeriveInput,
+    Error,
+    Fields,
+    GenericArgument,
+    Lit,
+    Meta,
+    NestedMeta,
+    PathArguments,
+    Type,
+};
+
+/// Implement `advent_lib::helpers::Record` for a struct with named fields.
+///
+/// Each field is read from the value with the same key, parsed with
+/// `FromStr`. Fields of type `Option<T>` may be left out of a record. The
+/// `#[record(...)]` attribute changes how a field is read:
+///
+/// - `key = "byr"` reads the field from a different key.
+/// - `range(1920, 2002)` requires the value to be in an inclusive range.
+/// - `len = 9` requires the text of the value to be that many characters.
+/// - `check = "path::to::fn"` requires a `fn(&T) -> bool` to accept the value.
+#[proc_macro_derive(Record, attributes(record))]
+pub fn derive_record(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    record(&input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+/// Implement `FromStr` for an enum of unit variants, so it can be a field of a
+/// record. Each variant is written as its name in lower case, unless it has a
+/// `#[record(value = "...")]` attribute.
+#[proc_macro_derive(FieldValue, attributes(record))]
+pub fn derive_field_value(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    field_value(&input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+/// How to read one field of a record.
+struct RecordField {
+    ident: syn::Ident,
+    key: String,
+    /// The type that is parsed, without any `Option` around it.
+    ty: Type,
+    required: bool,
+    checks: Vec<Check>,
+}
+
+/// Code that validates a field, by returning an error message from a closure.
+struct Check {
+    /// Whether the check looks at the text of the value (`raw`), rather than
+    /// the parsed value (`value`).
+    uses_raw: bool,
+    tokens: TokenStream2,
+}
+
+impl RecordField {
+    fn new(field: &syn::Field) -> Result<Self, Error> {
+        let ident = field
+            .ident
+            .clone()
+            .ok_or_else(|| Error::new(field.span(), "Record fields must be named"))?;
+        let (ty, required) = option_inner(&field.ty)
+            .map_or_else(|| (field.ty.clone(), true), |inner| (inner.clone(), false));
+        let mut rv = Self {
+            key: ident.to_string(),
+            ident,
+            ty,
+            required,
+            checks: vec![],
+        };
+
+        for meta in record_attributes(&field.attrs)? {
+            match &meta {
+                NestedMeta::Meta(Meta::NameValue(pair)) if pair.path.is_ident("key") => {
+                    rv.key = lit_str(&pair.lit)?.value();
+                }
+                NestedMeta::Meta(Meta::NameValue(pair)) if pair.path.is_ident("len") => {
+                    let len = match &pair.lit {
+                        Lit::Int(len) => len.base10_parse::<usize>()?,
+                        lit => return Err(Error::new_spanned(lit, "Expected a length")),
+                    };
+                    rv.checks.push(Check {
+                        uses_raw: true,
+                        tokens: quote! {
+                            if raw.chars().count() != #len {
+                                return Err(format!("expected {} characters", #len));
+                            }
+                        },
+                    });
+                }
+                NestedMeta::Meta(Meta::NameValue(pair)) if pair.path.is_ident("check") => {
+                    let check: syn::Path = lit_str(&pair.lit)?.parse()?;
+                    let name = quote!(#check).to_string().replace(' ', "");
+                    rv.checks.push(Check {
+                        uses_raw: false,
+                        tokens: quote! {
+                            if !#check(value) {
+                                return Err(format!("rejected by `{}`", #name));
+                            }
+                        },
+                    });
+                }
+                NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("range") => {
+                    let bounds: Vec<_> = list.nested.iter().collect();
+                    if let [NestedMeta::Lit(low), NestedMeta::Lit(high)] = bounds.as_slice() {
+                        rv.checks.push(Check {
+                            uses_raw: false,
+                            tokens: quote! {
+                                if !(#low..=#high).contains(value) {
+                                    return Err(format!("not between {} and {}", #low, #high));
+                                }
+                            },
+                        });
+                    } else {
+                        return Err(Error::new_spanned(list, "Expected `range(low, high)`"));
+                    }
+                }
+                meta => return Err(Error::new_spanned(meta, "Unknown record attribute")),
+            }
+        }
+
+        Ok(rv)
+    }
+}
+
+fn record(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    &data.fields,
+                    "Record can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                input,
+                "Record can only be derived for structs",
+            ))
+        }
+    };
+    let fields = fields
+        .iter()
+        .map(RecordField::new)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let helpers = quote!(::advent_lib::helpers);
+
+    // The generated locals are prefixed, so that they can't clash with the
+    // names of the fields
+    let slot = |ident: &syn::Ident| format_ident!("__field_{}", ident);
+    let slots = fields.iter().map(|RecordField { ident, ty, .. }| {
+        let slot = slot(ident);
+        quote! { let mut #slot: ::std::option::Option<#ty> = None; }
+    });
+    let arms = fields.iter().map(
+        |RecordField {
+             ident,
+             key,
+             ty,
+             checks,
+             ..
+         }| {
+            // Only name the arguments that the checks use, so that the
+            // closure doesn't have unused variables
+            let raw = if checks.iter().any(|check| check.uses_raw) {
+                quote!(raw)
+            } else {
+                quote!(_)
+            };
+            let value = if checks.iter().any(|check| !check.uses_raw) {
+                quote!(value)
+            } else {
+                quote!(_)
+            };
+            let checks = checks.iter().map(|check| &check.tokens);
+            let slot = slot(ident);
+            let check = quote! {
+                |#raw: &str, #value: &#ty| -> ::std::result::Result<(), ::std::string::String> {
+                    #(#checks)*
+                    Ok(())
+                }
+            };
+            quote! {
+                #key => #helpers::parse_record_field(
+                    #key,
+                    __record_raw,
+                    &mut #slot,
+                    &mut __record_errors,
+                    #check,
+                ),
+            }
+        },
+    );
+    let missing =
+        fields
+            .iter()
+            .filter(|field| field.required)
+            .map(|RecordField { key, .. }| {
+                quote! {
+                    if !__record_fields.iter().any(|&(key, _)| key == #key) {
+                        __record_errors.push(#helpers::FieldError::Missing(#key));
+                    }
+                }
+            });
+    let values = fields.iter().map(
+        |RecordField {
+             ident, required, ..
+         }| {
+            let slot = slot(ident);
+            if *required {
+                // Anything missing or invalid has already been reported
+                quote! { #ident: #slot.unwrap() }
+            } else {
+                quote! { #ident: #slot }
+            }
+        },
+    );
+
+    Ok(quote! {
+        impl #impl_generics #helpers::Record for #name #ty_generics #where_clause {
+            fn from_fields(
+                __record_fields: &[(&str, &str)],
+            ) -> ::std::result::Result<Self, #helpers::RecordError> {
+                let mut __record_errors = ::std::vec::Vec::new();
+                #(#slots)*
+                for &(__record_key, __record_raw) in __record_fields {
+                    match __record_key {
+                        #(#arms)*
+                        _ => __record_errors.push(
+                            #helpers::FieldError::Unknown(__record_key.to_owned()),
+                        ),
+                    }
+                }
+                #(#missing)*
+                if !__record_errors.is_empty() {
+                    return Err(#helpers::RecordError {
+                        errors: __record_errors,
+                    });
+                }
+                Ok(Self { #(#values),* })
+            }
+        }
+    })
+}
+
+fn field_value(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let variants = match &input.data {
+        Data::Enum(data) => &data.variants,
+        _ => {
+            return Err(Error::new_spanned(
+                input,
+                "FieldValue can only be derived for enums",
+            ))
+        }
+    };
+
+    let mut arms = vec![];
+    let mut values = vec![];
+    for variant in variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new_spanned(
+                variant,
+                "FieldValue variants can't have fields",
+            ));
+        }
+        let mut value = variant.ident.to_string().to_lowercase();
+        for meta in record_attributes(&variant.attrs)? {
+            match &meta {
+                NestedMeta::Meta(Meta::NameValue(pair)) if pair.path.is_ident("value") => {
+                    value = lit_str(&pair.lit)?.value();
+                }
+                meta => return Err(Error::new_spanned(meta, "Unknown record attribute")),
+            }
+        }
+        let ident = &variant.ident;
+        arms.push(quote! { #value => Ok(Self::#ident), });
+        values.push(value);
+    }
+
+    let name = &input.ident;
+    let expected = format!("expected one of {}", values.join(", "));
+    Ok(quote! {
+        impl ::std::str::FromStr for #name {
+            type Err = ::std::string::String;
+
+            fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+                match s {
+                    #(#arms)*
+                    _ => Err(#expected.to_owned()),
+                }
+            }
+        }
+    })
+}
+
+/// The items of every `#[record(...)]` attribute.
+fn record_attributes(attrs: &[Attribute]) -> Result<Vec<NestedMeta>, Error> {
+    let mut rv = vec![];
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("record")) {
+        match attr.parse_meta()? {
+            Meta::List(list) => rv.extend(list.nested),
+            meta => return Err(Error::new_spanned(meta, "Expected `#[record(...)]`")),
+        }
+    }
+    Ok(rv)
+}
+
+fn lit_str(lit: &Lit) -> Result<&syn::LitStr, Error> {
+    match lit {
+        Lit::Str(s) => Ok(s),
+        lit => Err(Error::new_spanned(lit, "Expected a string")),
+    }
+}
+
+/// The `T` of an `Option<T>`.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let path = match ty {
+        Type::Path(path) if path.qself.is_none() => &path.path,
+        _ => return None,
+    };
+    let segment = path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) => match args.args.first()? {
+            GenericArgument::Type(inner) => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
@@ -0,0 +1,65 @@
+//! Work out the build id that plugins are checked against. Puzzles cross the
+//! plugin boundary as Rust trait objects, whose layout is only the same when
+//! both sides were built by the same compiler for the same target, from the
+//! same source of this crate, with the same features and profile.
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    env,
+    fs,
+    hash::{Hash, Hasher},
+    io,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+fn main() {
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_owned());
+    let rustc_version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .expect("Could not run rustc");
+    let rustc_version = String::from_utf8_lossy(&rustc_version.stdout);
+
+    let mut sources = vec![];
+    find_sources(Path::new("src"), &mut sources).expect("Could not list sources");
+    sources.sort();
+    let mut hasher = DefaultHasher::new();
+    for path in sources {
+        path.hash(&mut hasher);
+        fs::read(&path)
+            .expect("Could not read source")
+            .hash(&mut hasher);
+    }
+
+    let mut features: Vec<String> = env::vars()
+        .filter_map(|(name, _)| Some(name.strip_prefix("CARGO_FEATURE_")?.to_lowercase()))
+        .collect();
+    features.sort();
+
+    println!(
+        "cargo:rustc-env=ADVENT_LIB_BUILD_ID={}, {}, {} (opt-level {}, debug {}), advent-lib {} \
+         ({:016x}) with features [{}]",
+        rustc_version.trim(),
+        env::var("TARGET").unwrap(),
+        env::var("PROFILE").unwrap(),
+        env::var("OPT_LEVEL").unwrap(),
+        env::var("DEBUG").unwrap(),
+        env::var("CARGO_PKG_VERSION").unwrap(),
+        hasher.finish(),
+        features.join(", ")
+    );
+    println!("cargo:rerun-if-changed=src");
+}
+
+fn find_sources(dir: &Path, sources: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            find_sources(&path, sources)?;
+        } else {
+            sources.push(path);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,32 @@
+/// The output of a puzzle, in the form it would be submitted.
+pub trait Answer {
+    /// The canonical text to submit for this answer.
+    fn submission(&self) -> String;
+}
+
+macro_rules! answer_to_string {
+    ($($t: ty),*) => {
+        $(
+            impl Answer for $t {
+                fn submission(&self) -> String {
+                    self.to_string()
+                }
+            }
+        )*
+    };
+}
+
+answer_to_string!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+answer_to_string!(String, &str);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_submission() {
+        assert_eq!(1_006_176_u32.submission(), "1006176");
+        assert_eq!((-42_isize).submission(), "-42");
+        assert_eq!("fgij".to_string().submission(), "fgij");
+    }
+}
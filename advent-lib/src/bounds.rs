@@ -0,0 +1,367 @@
+//! Boxes of points, with any number of dimensions.
+//!
+//! A [`Bounds`] works like a `Range` on every axis: its minimum corner is
+//! included, and its maximum corner is excluded. `extremes` is the equivalent
+//! for a single axis.
+
+use crate::twodee::{Point, PointAxe};
+use num_traits::{One, Saturating, Zero};
+use std::iter::FromIterator;
+
+/// A point that bounds can be found for.
+pub trait Coordinates: Copy {
+    type Axis: PointAxe;
+
+    /// The number of axes.
+    const DIMENSIONS: usize;
+
+    /// The value of one axis, numbered from 0 up to `DIMENSIONS`.
+    fn axis(&self, dimension: usize) -> Self::Axis;
+
+    /// Build a point from the value of each axis. `f` is called once for each
+    /// dimension, in order.
+    fn from_axes<F: FnMut(usize) -> Self::Axis>(f: F) -> Self;
+}
+
+impl<I: PointAxe> Coordinates for Point<I> {
+    type Axis = I;
+
+    const DIMENSIONS: usize = 2;
+
+    fn axis(&self, dimension: usize) -> I {
+        match dimension {
+            0 => self.x,
+            1 => self.y,
+            _ => panic!("2D points have no axis {}", dimension),
+        }
+    }
+
+    fn from_axes<F: FnMut(usize) -> I>(mut f: F) -> Self {
+        let x = f(0);
+        let y = f(1);
+        Self::new(x, y)
+    }
+}
+
+impl<I: PointAxe, const N: usize> Coordinates for [I; N] {
+    type Axis = I;
+
+    const DIMENSIONS: usize = N;
+
+    fn axis(&self, dimension: usize) -> I {
+        self[dimension]
+    }
+
+    fn from_axes<F: FnMut(usize) -> I>(mut f: F) -> Self {
+        let mut rv = [I::zero(); N];
+        for (dimension, axis) in rv.iter_mut().enumerate() {
+            *axis = f(dimension);
+        }
+        rv
+    }
+}
+
+/// The points in a box with a side along each axis. `min` is the corner with
+/// the lowest value on every axis and is inside the box. `max` is the
+/// opposite corner, and is just outside of it.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Bounds<P> {
+    min: P,
+    max: P,
+}
+
+impl<P: Coordinates> Bounds<P> {
+    /// The points from `min` up to but not including `max` on every axis.
+    ///
+    /// # Panics
+    ///
+    /// If `max` is lower than `min` on any axis.
+    pub fn exclusive(min: P, max: P) -> Self {
+        for dimension in 0..P::DIMENSIONS {
+            assert!(
+                min.axis(dimension) <= max.axis(dimension),
+                "The maximum corner is below the minimum on axis {}",
+                dimension
+            );
+        }
+        Self { min, max }
+    }
+
+    /// The points from `min` to `max` on every axis, including both.
+    ///
+    /// # Panics
+    ///
+    /// If `max` is lower than `min` on any axis.
+    pub fn inclusive(min: P, max: P) -> Self {
+        Self::exclusive(min, P::from_axes(|d| max.axis(d) + P::Axis::one()))
+    }
+
+    /// The corner inside the bounds with the lowest value on every axis.
+    pub const fn min(&self) -> P {
+        self.min
+    }
+
+    /// The corner just outside of the bounds, with one more than the highest
+    /// value on every axis.
+    pub const fn max_exclusive(&self) -> P {
+        self.max
+    }
+
+    /// The corner inside the bounds with the highest value on every axis, if
+    /// there are any points in the bounds.
+    pub fn max_inclusive(&self) -> Option<P> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(P::from_axes(|d| self.max.axis(d) - P::Axis::one()))
+        }
+    }
+
+    /// The number of values the bounds cover on one axis.
+    pub fn size(&self, dimension: usize) -> P::Axis {
+        self.max.axis(dimension) - self.min.axis(dimension)
+    }
+
+    /// The number of points in the bounds. For 3D bounds, this is the volume.
+    pub fn area(&self) -> P::Axis {
+        (0..P::DIMENSIONS).fold(P::Axis::one(), |area, d| area * self.size(d))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        (0..P::DIMENSIONS).any(|d| self.size(d) == P::Axis::zero())
+    }
+
+    pub fn contains(&self, p: P) -> bool {
+        (0..P::DIMENSIONS).all(|d| {
+            let axis = p.axis(d);
+            self.min.axis(d) <= axis && axis < self.max.axis(d)
+        })
+    }
+
+    /// Whether every point of `other` is in these bounds. Empty bounds are
+    /// in any others.
+    pub fn contains_bounds(&self, other: &Self) -> bool {
+        other.is_empty()
+            || (0..P::DIMENSIONS).all(|d| {
+                self.min.axis(d) <= other.min.axis(d) && other.max.axis(d) <= self.max.axis(d)
+            })
+    }
+
+    /// The smallest bounds that contain both these and `other`.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        if self.is_empty() {
+            *other
+        } else if other.is_empty() {
+            *self
+        } else {
+            Self {
+                min: P::from_axes(|d| min(self.min.axis(d), other.min.axis(d))),
+                max: P::from_axes(|d| max(self.max.axis(d), other.max.axis(d))),
+            }
+        }
+    }
+
+    /// The points in both these bounds and `other`, if there are any.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let rv = Self {
+            min: P::from_axes(|d| max(self.min.axis(d), other.min.axis(d))),
+            max: P::from_axes(|d| min(self.max.axis(d), other.max.axis(d))),
+        };
+        if (0..P::DIMENSIONS).all(|d| rv.min.axis(d) < rv.max.axis(d)) {
+            Some(rv)
+        } else {
+            None
+        }
+    }
+
+    /// Grow the bounds by `n` in every direction, such as to include the
+    /// neighbors of every point. Sides that would go past the lowest or
+    /// highest value of the axis type stop there instead, so bounds starting
+    /// at 0 on an unsigned axis still start at 0.
+    ///
+    /// # Panics
+    ///
+    /// If `n` is negative and shrinks the bounds to less than nothing on any
+    /// axis.
+    #[must_use]
+    pub fn expand(&self, n: P::Axis) -> Self
+    where
+        P::Axis: Saturating,
+    {
+        Self::exclusive(
+            P::from_axes(|d| self.min.axis(d).saturating_sub(n)),
+            P::from_axes(|d| self.max.axis(d).saturating_add(n)),
+        )
+    }
+
+    /// Every point in the bounds. The first axis changes fastest, so 2D
+    /// points are visited a row at a time.
+    pub fn iter_points(&self) -> PointIterator<P> {
+        PointIterator {
+            next: if self.is_empty() {
+                None
+            } else {
+                Some(self.min)
+            },
+            bounds: *self,
+        }
+    }
+}
+
+impl<P: Coordinates> FromIterator<P> for Bounds<P> {
+    /// The smallest bounds that contain every point. If there are no points,
+    /// the bounds are empty.
+    fn from_iter<T: IntoIterator<Item = P>>(iter: T) -> Self {
+        let mut iter = iter.into_iter();
+        if let Some(first) = iter.next() {
+            let (min_corner, max_corner) = iter.fold((first, first), |(low, high), p| {
+                (
+                    P::from_axes(|d| min(low.axis(d), p.axis(d))),
+                    P::from_axes(|d| max(high.axis(d), p.axis(d))),
+                )
+            });
+            Self::inclusive(min_corner, max_corner)
+        } else {
+            let origin = P::from_axes(|_| P::Axis::zero());
+            Self::exclusive(origin, origin)
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PointIterator<P> {
+    next: Option<P>,
+    bounds: Bounds<P>,
+}
+
+impl<P: Coordinates> Iterator for PointIterator<P> {
+    type Item = P;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+        let bounds = &self.bounds;
+
+        // Count up like an odometer, wrapping each axis back to the minimum
+        // until one doesn't overflow
+        let mut carry = true;
+        let next = P::from_axes(|d| {
+            let axis = current.axis(d);
+            if !carry {
+                axis
+            } else if axis + P::Axis::one() < bounds.max.axis(d) {
+                carry = false;
+                axis + P::Axis::one()
+            } else {
+                bounds.min.axis(d)
+            }
+        });
+        self.next = if carry { None } else { Some(next) };
+
+        Some(current)
+    }
+}
+
+// `PointAxe` is only `PartialOrd`, so `cmp::min` and `cmp::max` don't apply
+fn min<T: PartialOrd>(a: T, b: T) -> T {
+    if b < a {
+        b
+    } else {
+        a
+    }
+}
+
+fn max<T: PartialOrd>(a: T, b: T) -> T {
+    if b > a {
+        b
+    } else {
+        a
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inclusive_and_exclusive() {
+        let bounds = Bounds::inclusive(Point::new(1, 2), Point::new(3, 3));
+        assert_eq!(
+            bounds,
+            Bounds::exclusive(Point::new(1, 2), Point::new(4, 4))
+        );
+        assert_eq!(bounds.max_inclusive(), Some(Point::new(3, 3)));
+        assert_eq!(bounds.area(), 6);
+        assert!(bounds.contains(Point::new(3, 3)));
+        assert!(!bounds.contains(Point::new(4, 3)));
+
+        let empty = Bounds::exclusive(Point::new(1, 2), Point::new(1, 5));
+        assert!(empty.is_empty());
+        assert_eq!(empty.max_inclusive(), None);
+        assert_eq!(empty.iter_points().count(), 0);
+    }
+
+    #[test]
+    fn test_from_points() {
+        let bounds: Bounds<Point<i32>> = vec![Point::new(2, -1), Point::new(-3, 4)]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            bounds,
+            Bounds::inclusive(Point::new(-3, -1), Point::new(2, 4))
+        );
+
+        let empty: Bounds<[i32; 3]> = vec![].into_iter().collect();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_combining() {
+        let a = Bounds::exclusive([0, 0, 0], [2, 2, 2]);
+        let b = Bounds::exclusive([1, 1, 1], [3, 4, 5]);
+        assert_eq!(a.union(&b), Bounds::exclusive([0, 0, 0], [3, 4, 5]));
+        assert_eq!(
+            a.intersection(&b),
+            Some(Bounds::exclusive([1, 1, 1], [2, 2, 2]))
+        );
+        assert_eq!(
+            a.intersection(&Bounds::exclusive([2, 0, 0], [3, 1, 1])),
+            None
+        );
+
+        assert!(a.union(&b).contains_bounds(&a));
+        assert!(!a.contains_bounds(&b));
+        assert_eq!(a.expand(1), Bounds::exclusive([-1, -1, -1], [3, 3, 3]));
+        assert_eq!(a.expand(1).area(), 64);
+    }
+
+    #[test]
+    fn test_expand_saturates() {
+        let a = Bounds::exclusive(Point::new(0_u32, 5), Point::new(2, 7));
+        assert_eq!(
+            a.expand(1),
+            Bounds::exclusive(Point::new(0, 4), Point::new(3, 8))
+        );
+
+        let b = Bounds::exclusive([-128_i8, 0], [100, 127]);
+        assert_eq!(b.expand(2), Bounds::exclusive([-128, -2], [102, 127]));
+        assert_eq!(b.expand(-1), Bounds::exclusive([-127, 1], [99, 126]));
+    }
+
+    #[test]
+    fn test_iter_points() {
+        let points: Vec<_> = Bounds::exclusive(Point::new(0, 5), Point::new(2, 7))
+            .iter_points()
+            .collect();
+        assert_eq!(
+            points,
+            vec![
+                Point::new(0, 5),
+                Point::new(1, 5),
+                Point::new(0, 6),
+                Point::new(1, 6)
+            ]
+        );
+        assert_eq!(Bounds::exclusive([0; 4], [3; 4]).iter_points().count(), 81);
+    }
+}
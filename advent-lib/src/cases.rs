@@ -1,6 +1,16 @@
+use crate::{
+    answer::Answer,
+    context::RunContext,
+    diff::Mismatch,
+    error::{AdventError, ErrorLocation},
+    id::PuzzleId,
+    property::PropertyCase,
+};
+use once_cell::sync::OnceCell;
 use std::{
     fmt::Display,
     marker::PhantomData,
+    sync::{Arc, Mutex, PoisonError},
     time::{Duration, Instant},
     unimplemented,
 };
@@ -10,6 +20,17 @@ use std::{
 pub trait PuzzleCase: std::fmt::Debug + Sync + Send {
     fn name(&self) -> String;
     fn run(&self) -> PuzzleResult;
+
+    /// Run the case, letting it report progress and be cancelled through
+    /// `ctx`. Cases that don't support this ignore the context.
+    fn run_with_context(&self, _ctx: &RunContext) -> PuzzleResult {
+        self.run()
+    }
+
+    /// Forget anything kept from earlier runs, such as the state a day
+    /// prepares for both of its parts, so that the next run does all of its
+    /// work again.
+    fn reset(&self) {}
 }
 
 /// A set of puzzle cases and associated metadata
@@ -18,8 +39,24 @@ pub trait PuzzleCase: std::fmt::Debug + Sync + Send {
 pub trait Puzzle: std::fmt::Debug + Sync + Send {
     fn name(&self) -> String;
     fn cases(&self) -> Result<Vec<Box<dyn PuzzleCase>>, Box<dyn std::error::Error>>;
+
+    /// The name of this implementation, for puzzles that have more than one.
+    /// Implementations of the same puzzle share a name and their cases.
+    fn implementation(&self) -> Option<String> {
+        None
+    }
+
+    /// The puzzle this solves, if its name is in the usual form.
+    fn id(&self) -> Option<PuzzleId> {
+        self.name().parse().ok()
+    }
 }
 
+/// The error type that most puzzles use, for [`PuzzleRunner::Error`] and
+/// [`DayRunner::Error`]. Those were defaults of the traits, but associated
+/// type defaults aren't stable Rust, so each puzzle now names its error type.
+pub type DefaultError = Box<dyn std::error::Error>;
+
 /// A function to run a specific puzzle's code
 ///
 /// In contrast to `Puzzle`, this trait contains the specific types for the
@@ -27,18 +64,27 @@ pub trait Puzzle: std::fmt::Debug + Sync + Send {
 pub trait PuzzleRunner: std::fmt::Debug + Sync + Send {
     type Input;
     type Output;
-    type Error = Box<dyn std::error::Error>;
+    /// Usually [`DefaultError`].
+    type Error;
 
     /// The name of this puzzle
     fn name(&self) -> String;
 
+    /// The name of this implementation, for puzzles that have more than one
+    fn implementation(&self) -> Option<String> {
+        None
+    }
+
     /// The cases this puzzle has, including examples and solutions
     fn cases(&self) -> Result<Vec<Box<dyn PuzzleCase>>, Box<dyn std::error::Error>>;
 
     /// Run the puzzle infallibly. This might panic if the puzzle is not
     /// infallible
     fn run_puzzle(_input: Self::Input) -> Self::Output {
-        unimplemented!();
+        unimplemented!(
+            "Puzzles must implement `run_puzzle` or `try_run_puzzle`, or be run with \
+             `try_run_puzzle_with_context`"
+        );
     }
 
     /// Run the puzzle.
@@ -48,6 +94,20 @@ pub trait PuzzleRunner: std::fmt::Debug + Sync + Send {
     fn try_run_puzzle(input: Self::Input) -> Result<Self::Output, Self::Error> {
         Ok(Self::run_puzzle(input))
     }
+
+    /// Run the puzzle, reporting progress to `ctx` and stopping early if it is
+    /// cancelled. This is what the runner calls. Puzzles that take long
+    /// enough to need a context implement it instead of `try_run_puzzle`, and
+    /// are run directly by calling it with `RunContext::new()`.
+    ///
+    /// # Errors
+    /// Returns an error if the puzzle could not be solved, or was cancelled
+    fn try_run_puzzle_with_context(
+        input: Self::Input,
+        _ctx: &RunContext,
+    ) -> Result<Self::Output, Self::Error> {
+        Self::try_run_puzzle(input)
+    }
 }
 
 impl<T: PuzzleRunner> Puzzle for T {
@@ -55,6 +115,10 @@ impl<T: PuzzleRunner> Puzzle for T {
         PuzzleRunner::name(self)
     }
 
+    fn implementation(&self) -> Option<String> {
+        PuzzleRunner::implementation(self)
+    }
+
     fn cases(&self) -> Result<Vec<Box<dyn PuzzleCase>>, Box<dyn std::error::Error>> {
         PuzzleRunner::cases(self)
     }
@@ -98,90 +162,159 @@ pub struct GenericPuzzleCase<'a, T, I, O> {
     pub phantom: PhantomData<&'a T>,
 }
 
+#[derive(Clone, Debug)]
 pub struct PuzzleResult {
     pub status: PuzzleResultStatus,
     pub description: String,
     pub duration: Duration,
+
+    /// The submission text of the puzzle's output, if it produced one.
+    pub answer: Option<String>,
+
+    /// For wrong answers that span several lines, the expected and actual
+    /// values, so that they can be shown as a diff.
+    pub mismatch: Option<Mismatch>,
+
+    /// For errors parsing the input, where in the input the problem is.
+    pub location: Option<ErrorLocation>,
+
+    /// How long it took to prepare the state that this part of a day shares
+    /// with the other part. This isn't included in `duration`, as it is only
+    /// done once for both parts.
+    pub prepare: Option<Duration>,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum PuzzleResultStatus {
     Match,
     Fail,
     Unknown,
     Error,
+    Todo,
+    Cancelled,
 }
 
 impl<'a, T, I, O, E> PuzzleCase for GenericPuzzleCase<'a, T, I, O>
 where
     T: PuzzleRunner<Input = I, Output = O, Error = E>,
-    O: PartialEq + std::fmt::Debug + Sync + Send,
+    O: Answer + PartialEq + std::fmt::Debug + Sync + Send,
     I: Clone + std::fmt::Debug + Sync + Send,
-    E: Display,
+    E: Into<Box<dyn std::error::Error>>,
 {
     fn name(&self) -> String {
         self.name.clone()
     }
 
     fn run(&self) -> PuzzleResult {
+        self.run_with_context(&RunContext::new())
+    }
+
+    fn run_with_context(&self, ctx: &RunContext) -> PuzzleResult {
         let start = Instant::now();
-        let actual_result = T::try_run_puzzle(self.input.clone());
+        let actual_result = T::try_run_puzzle_with_context(self.input.clone(), ctx);
         let duration = start.elapsed();
+        check_output(actual_result, &self.expected, duration)
+    }
+}
 
-        if let Err(err) = actual_result {
-            return PuzzleResult {
-                status: PuzzleResultStatus::Error,
-                duration,
-                description: format!("Error: {}", err),
-            };
-        }
-        let actual = actual_result.ok().unwrap();
-
-        match self.expected {
-            ExpectedValue::Exact(ref expected) => {
-                if actual == *expected {
-                    PuzzleResult {
-                        status: PuzzleResultStatus::Match,
-                        duration,
-                        description: format!("{:?} is correct", actual),
-                    }
+/// Compare the output of a puzzle to the expected value, and describe the
+/// result.
+fn check_output<O, E>(
+    actual_result: Result<O, E>,
+    expected: &ExpectedValue<O>,
+    duration: Duration,
+) -> PuzzleResult
+where
+    O: Answer + PartialEq + std::fmt::Debug,
+    E: Into<Box<dyn std::error::Error>>,
+{
+    let actual = match actual_result {
+        Ok(actual) => actual,
+        Err(err) => return check_error(err.into(), duration),
+    };
+
+    let answer = actual.submission();
+    let mut mismatch = None;
+    let (status, description) = match expected {
+        ExpectedValue::Exact(ref expected) => {
+            if actual == *expected {
+                (PuzzleResultStatus::Match, format!("{} is correct", answer))
+            } else {
+                mismatch = Mismatch::new(expected, &actual);
+                let description = if mismatch.is_some() {
+                    "output differs from the expected value".to_owned()
                 } else {
-                    PuzzleResult {
-                        status: PuzzleResultStatus::Fail,
-                        duration,
-                        description: format!("expected {:?} got {:?}", expected, actual),
-                    }
-                }
+                    format!("expected {} got {}", expected.submission(), answer)
+                };
+                (PuzzleResultStatus::Fail, description)
             }
-            ExpectedValue::Predicate(predicate) => {
-                if predicate(&actual) {
-                    PuzzleResult {
-                        status: PuzzleResultStatus::Unknown,
-                        duration,
-                        description: format!("{:?} matches predicate", actual),
-                    }
-                } else {
-                    PuzzleResult {
-                        status: PuzzleResultStatus::Fail,
-                        duration,
-                        description: format!("{:?} does not match predicate", actual),
-                    }
-                }
+        }
+        ExpectedValue::Predicate(predicate) => {
+            if predicate(&actual) {
+                (
+                    PuzzleResultStatus::Unknown,
+                    format!("{} matches predicate", answer),
+                )
+            } else {
+                (
+                    PuzzleResultStatus::Fail,
+                    format!("{} does not match predicate", answer),
+                )
             }
-            ExpectedValue::None => PuzzleResult {
-                status: PuzzleResultStatus::Unknown,
-                duration,
-                description: format!("{:?}", actual),
-            },
         }
+        ExpectedValue::None => (PuzzleResultStatus::Unknown, answer.clone()),
+    };
+
+    PuzzleResult {
+        status,
+        description,
+        duration,
+        answer: Some(answer),
+        mismatch,
+        location: None,
+        prepare: None,
+    }
+}
+
+/// Describe a puzzle that failed to produce an output.
+fn check_error(err: Box<dyn std::error::Error>, duration: Duration) -> PuzzleResult {
+    match err.downcast_ref::<AdventError>() {
+        Some(AdventError::NotImplemented) => PuzzleResult {
+            status: PuzzleResultStatus::Todo,
+            duration,
+            description: err.to_string(),
+            answer: None,
+            mismatch: None,
+            location: None,
+            prepare: None,
+        },
+        Some(AdventError::Cancelled) => PuzzleResult {
+            status: PuzzleResultStatus::Cancelled,
+            duration,
+            description: err.to_string(),
+            answer: None,
+            mismatch: None,
+            location: None,
+            prepare: None,
+        },
+        _ => PuzzleResult {
+            status: PuzzleResultStatus::Error,
+            duration,
+            description: format!("Error: {}", err),
+            answer: None,
+            mismatch: None,
+            location: ErrorLocation::of(err.as_ref()),
+            prepare: None,
+        },
     }
 }
 
 impl<'a, T, I, O, E> GenericPuzzleCase<'a, T, I, O>
 where
     T: PuzzleRunner<Input = I, Output = O, Error = E>,
-    O: PartialEq + 'a + std::fmt::Debug + Sync + Send,
+    O: Answer + PartialEq + 'a + std::fmt::Debug + Sync + Send,
     I: Clone + 'a + std::fmt::Debug + Sync + Send,
-    E: Display,
+    E: Into<Box<dyn std::error::Error>>,
 {
     #[must_use]
     pub fn build_set() -> CaseSetBuilder<'a, T, I, O> {
@@ -191,6 +324,7 @@ where
 
 pub struct CaseSetBuilder<'a, T, I, O, E = Box<dyn std::error::Error>> {
     cases: Vec<GenericPuzzleCase<'a, T, I, O>>,
+    property_cases: Vec<Box<dyn PuzzleCase + 'a>>,
     try_transform: Option<Box<dyn Fn(&str) -> Result<I, E>>>,
     phantom: PhantomData<&'a T>,
 }
@@ -198,13 +332,14 @@ pub struct CaseSetBuilder<'a, T, I, O, E = Box<dyn std::error::Error>> {
 impl<'a, T, I, O, ERunner, ETransform> CaseSetBuilder<'a, T, I, O, ETransform>
 where
     T: PuzzleRunner<Input = I, Output = O, Error = ERunner>,
-    O: PartialEq + 'a + std::fmt::Debug + Sync + Send,
+    O: Answer + PartialEq + 'a + std::fmt::Debug + Sync + Send,
     I: Clone + 'a + std::fmt::Debug + Sync + Send,
-    ERunner: Display,
+    ERunner: Into<Box<dyn std::error::Error>>,
 {
     fn new() -> Self {
         Self {
             cases: vec![],
+            property_cases: vec![],
             try_transform: None,
             phantom: PhantomData::<&T>,
         }
@@ -260,11 +395,519 @@ where
         }
     }
 
+    /// Add a case that checks the puzzle against an oracle on generated
+    /// inputs. These run after all the other cases.
+    #[must_use]
+    pub fn property_case(mut self, case: PropertyCase<T>) -> Self
+    where
+        T: 'a,
+    {
+        self.property_cases.push(Box::new(case));
+        self
+    }
+
     #[must_use]
     pub fn collect(self) -> Vec<Box<dyn PuzzleCase + 'a>> {
         self.cases
             .into_iter()
             .map(|case| Box::new(case) as Box<dyn PuzzleCase + 'a>)
+            .chain(self.property_cases)
+            .collect()
+    }
+}
+
+/// Both parts of a day's puzzle, sharing their input
+///
+/// In contrast to `PuzzleRunner`, which describes a single part, the input
+/// for each case is parsed once and shared between the two parts. Any work
+/// that both parts need, such as exploring a maze, can be done once in
+/// `prepare`. Each part is still reported as its own `Puzzle`, with its own
+/// timings. The time spent in `prepare` is measured separately, and reported
+/// with the results of both parts as [`PuzzleResult::prepare`].
+pub trait DayRunner: std::fmt::Debug + Sync + Send + Sized + 'static {
+    type Input: std::fmt::Debug + Sync + Send;
+    type Shared: std::fmt::Debug + Sync + Send;
+    type Output1: Answer + PartialEq + std::fmt::Debug + Sync + Send;
+    type Output2: Answer + PartialEq + std::fmt::Debug + Sync + Send;
+    /// Usually [`DefaultError`].
+    type Error: Into<Box<dyn std::error::Error>>;
+
+    /// The name of this day, such as `2019-D15`. The parts are named by
+    /// adding `-P1` and `-P2` to this.
+    fn name(&self) -> String;
+
+    /// The name of this implementation, for days that have more than one
+    fn implementation(&self) -> Option<String> {
+        None
+    }
+
+    /// The cases this day has, including examples and solutions
+    ///
+    /// # Errors
+    /// Returns an error if the cases could not be built, such as when an
+    /// input could not be parsed
+    fn cases(&self) -> Result<Vec<DayCase<Self>>, Box<dyn std::error::Error>>;
+
+    /// Compute the state that both parts share.
+    ///
+    /// # Errors
+    /// Returns an error if the shared state could not be computed
+    fn prepare(input: &Self::Input) -> Result<Self::Shared, Self::Error>;
+
+    /// Run the first part of the puzzle.
+    ///
+    /// # Errors
+    /// Returns an error if the puzzle could not be solved
+    fn part1(input: &Self::Input, shared: &Self::Shared) -> Result<Self::Output1, Self::Error>;
+
+    /// Run the second part of the puzzle.
+    ///
+    /// # Errors
+    /// Returns an error if the puzzle could not be solved
+    fn part2(input: &Self::Input, shared: &Self::Shared) -> Result<Self::Output2, Self::Error>;
+
+    /// Run the first part, reporting progress to `ctx` and stopping early if
+    /// it is cancelled.
+    ///
+    /// # Errors
+    /// Returns an error if the puzzle could not be solved, or was cancelled
+    fn part1_with_context(
+        input: &Self::Input,
+        shared: &Self::Shared,
+        _ctx: &RunContext,
+    ) -> Result<Self::Output1, Self::Error> {
+        Self::part1(input, shared)
+    }
+
+    /// Run the second part, reporting progress to `ctx` and stopping early if
+    /// it is cancelled.
+    ///
+    /// # Errors
+    /// Returns an error if the puzzle could not be solved, or was cancelled
+    fn part2_with_context(
+        input: &Self::Input,
+        shared: &Self::Shared,
+        _ctx: &RunContext,
+    ) -> Result<Self::Output2, Self::Error> {
+        Self::part2(input, shared)
+    }
+
+    /// Split this day into a `Puzzle` for each part.
+    #[must_use]
+    fn into_puzzles(self) -> Vec<Box<dyn Puzzle>> {
+        let day = Arc::new(DayCaseSet {
+            day: self,
+            cases: OnceCell::new(),
+        });
+        vec![
+            Box::new(DayPart {
+                day: day.clone(),
+                part: Part::One,
+            }),
+            Box::new(DayPart {
+                day,
+                part: Part::Two,
+            }),
+        ]
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Display for Part {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::One => write!(fmt, "P1"),
+            Self::Two => write!(fmt, "P2"),
+        }
+    }
+}
+
+/// A case for both parts of a day, sharing the input and prepared state.
+#[derive(Debug)]
+pub struct DayCase<T: DayRunner> {
+    pub name: String,
+    pub input: T::Input,
+    pub expected1: Option<ExpectedValue<T::Output1>>,
+    pub expected2: Option<ExpectedValue<T::Output2>>,
+    /// The prepared state, and how long it took to prepare.
+    prepared: Mutex<Option<(Arc<T::Shared>, Duration)>>,
+}
+
+impl<T: DayRunner> DayCase<T> {
+    #[must_use]
+    pub fn build_set() -> DayCaseSetBuilder<T> {
+        DayCaseSetBuilder::new()
+    }
+
+    /// The prepared state for this case, and how long it took to prepare,
+    /// computing it if needed. A failed `prepare` isn't cached, so each part
+    /// will report the error.
+    ///
+    /// # Errors
+    /// Returns the error from `DayRunner::prepare`, if it failed
+    pub fn shared(&self) -> Result<(Arc<T::Shared>, Duration), T::Error> {
+        // Holding the lock while preparing keeps the parts from both doing it
+        // if they run at the same time
+        let mut prepared = self.prepared.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some((shared, duration)) = prepared.as_ref() {
+            return Ok((shared.clone(), *duration));
+        }
+        let start = Instant::now();
+        let shared = Arc::new(T::prepare(&self.input)?);
+        let duration = start.elapsed();
+        *prepared = Some((shared.clone(), duration));
+        Ok((shared, duration))
+    }
+
+    /// Forget the prepared state, so that it is prepared again the next time
+    /// it is needed.
+    pub fn reset(&self) {
+        *self.prepared.lock().unwrap_or_else(PoisonError::into_inner) = None;
+    }
+}
+
+#[derive(Debug)]
+struct DayCaseSet<T: DayRunner> {
+    day: T,
+    cases: OnceCell<Result<Vec<Arc<DayCase<T>>>, String>>,
+}
+
+impl<T: DayRunner> DayCaseSet<T> {
+    fn cases(&self) -> Result<&[Arc<DayCase<T>>], Box<dyn std::error::Error>> {
+        self.cases
+            .get_or_init(|| {
+                self.day
+                    .cases()
+                    .map(|cases| cases.into_iter().map(Arc::new).collect())
+                    .map_err(|err| err.to_string())
+            })
+            .as_ref()
+            .map(Vec::as_slice)
+            .map_err(|err| err.clone().into())
+    }
+}
+
+#[derive(Debug)]
+struct DayPart<T: DayRunner> {
+    day: Arc<DayCaseSet<T>>,
+    part: Part,
+}
+
+impl<T: DayRunner> Puzzle for DayPart<T> {
+    fn name(&self) -> String {
+        format!("{}-{}", self.day.day.name(), self.part)
+    }
+
+    fn implementation(&self) -> Option<String> {
+        self.day.day.implementation()
+    }
+
+    fn cases(&self) -> Result<Vec<Box<dyn PuzzleCase>>, Box<dyn std::error::Error>> {
+        Ok(self
+            .day
+            .cases()?
+            .iter()
+            .filter(|case| match self.part {
+                Part::One => case.expected1.is_some(),
+                Part::Two => case.expected2.is_some(),
+            })
+            .map(|case| {
+                Box::new(DayPartCase {
+                    case: case.clone(),
+                    part: self.part,
+                }) as Box<dyn PuzzleCase>
+            })
+            .collect())
+    }
+}
+
+#[derive(Debug)]
+struct DayPartCase<T: DayRunner> {
+    case: Arc<DayCase<T>>,
+    part: Part,
+}
+
+impl<T: DayRunner> PuzzleCase for DayPartCase<T> {
+    fn name(&self) -> String {
+        self.case.name.clone()
+    }
+
+    fn run(&self) -> PuzzleResult {
+        self.run_with_context(&RunContext::new())
+    }
+
+    fn run_with_context(&self, ctx: &RunContext) -> PuzzleResult {
+        let case = &self.case;
+        let start = Instant::now();
+        let (shared, prepare) = match case.shared() {
+            Ok(prepared) => prepared,
+            Err(err) => return check_error(err.into(), start.elapsed()),
+        };
+
+        let start = Instant::now();
+        let result = match (self.part, &case.expected1, &case.expected2) {
+            (Part::One, Some(expected), _) => {
+                let actual_result = T::part1_with_context(&case.input, &shared, ctx);
+                check_output(actual_result, expected, start.elapsed())
+            }
+            (Part::Two, _, Some(expected)) => {
+                let actual_result = T::part2_with_context(&case.input, &shared, ctx);
+                check_output(actual_result, expected, start.elapsed())
+            }
+            _ => unreachable!("Cases are only made for parts with an expected value"),
+        };
+        PuzzleResult {
+            prepare: Some(prepare),
+            ..result
+        }
+    }
+
+    fn reset(&self) {
+        self.case.reset();
+    }
+}
+
+/// Parses the raw input of a case, for
+/// [`DayCaseSetBuilder::add_try_transform`].
+type Transform<I, E> = Box<dyn Fn(&str) -> Result<I, E>>;
+
+pub struct DayCaseSetBuilder<T: DayRunner, E = Box<dyn std::error::Error>> {
+    cases: Vec<DayCase<T>>,
+    try_transform: Option<Transform<T::Input, E>>,
+}
+
+impl<T: DayRunner, E> DayCaseSetBuilder<T, E> {
+    fn new() -> Self {
+        Self {
+            cases: vec![],
+            try_transform: None,
+        }
+    }
+
+    #[must_use]
+    pub fn add_transform<F>(mut self, transform: F) -> Self
+    where
+        F: Fn(&str) -> T::Input + 'static,
+    {
+        self.try_transform = Some(Box::new(move |input| Ok(transform(input))));
+        self
+    }
+
+    #[must_use]
+    pub fn add_try_transform<F>(mut self, transform: F) -> Self
+    where
+        F: Fn(&str) -> Result<T::Input, E> + 'static,
+    {
+        self.try_transform = Some(Box::new(transform));
+        self
+    }
+
+    fn push(
+        mut self,
+        name: String,
+        input: T::Input,
+        expected1: Option<ExpectedValue<T::Output1>>,
+        expected2: Option<ExpectedValue<T::Output2>>,
+    ) -> Self {
+        self.cases.push(DayCase {
+            name,
+            input,
+            expected1,
+            expected2,
+            prepared: Mutex::new(None),
+        });
+        self
+    }
+
+    fn transform(&self, raw_input: &str) -> Result<T::Input, E> {
+        match self.try_transform {
+            Some(ref try_transform) => try_transform(raw_input),
+            None => panic!("Must call `add_transform` before transformed_case"),
+        }
+    }
+
+    /// Add a case that applies to both parts
+    #[must_use]
+    pub fn case<S, I_, O1, O2>(self, name: S, input: I_, expected1: O1, expected2: O2) -> Self
+    where
+        S: Into<String>,
+        I_: Into<T::Input>,
+        O1: Into<ExpectedValue<T::Output1>>,
+        O2: Into<ExpectedValue<T::Output2>>,
+    {
+        self.push(
+            name.into(),
+            input.into(),
+            Some(expected1.into()),
+            Some(expected2.into()),
+        )
+    }
+
+    /// Add a case that only applies to the first part
+    #[must_use]
+    pub fn part1_case<S, I_, O1>(self, name: S, input: I_, expected1: O1) -> Self
+    where
+        S: Into<String>,
+        I_: Into<T::Input>,
+        O1: Into<ExpectedValue<T::Output1>>,
+    {
+        self.push(name.into(), input.into(), Some(expected1.into()), None)
+    }
+
+    /// Add a case that only applies to the second part
+    #[must_use]
+    pub fn part2_case<S, I_, O2>(self, name: S, input: I_, expected2: O2) -> Self
+    where
+        S: Into<String>,
+        I_: Into<T::Input>,
+        O2: Into<ExpectedValue<T::Output2>>,
+    {
+        self.push(name.into(), input.into(), None, Some(expected2.into()))
+    }
+
+    /// Add a case that applies to both parts, using the transform to parse
+    /// the input.
+    ///
+    /// # Errors
+    /// Returns an error if the input could not be transformed
+    pub fn transformed_case<S, O1, O2>(
+        self,
+        name: S,
+        raw_input: &str,
+        expected1: O1,
+        expected2: O2,
+    ) -> Result<Self, E>
+    where
+        S: Into<String>,
+        O1: Into<ExpectedValue<T::Output1>>,
+        O2: Into<ExpectedValue<T::Output2>>,
+    {
+        let input = self.transform(raw_input)?;
+        Ok(self.push(
+            name.into(),
+            input,
+            Some(expected1.into()),
+            Some(expected2.into()),
+        ))
+    }
+
+    /// Add a case that only applies to the first part, using the transform
+    /// to parse the input.
+    ///
+    /// # Errors
+    /// Returns an error if the input could not be transformed
+    pub fn transformed_part1_case<S, O1>(
+        self,
+        name: S,
+        raw_input: &str,
+        expected1: O1,
+    ) -> Result<Self, E>
+    where
+        S: Into<String>,
+        O1: Into<ExpectedValue<T::Output1>>,
+    {
+        let input = self.transform(raw_input)?;
+        Ok(self.push(name.into(), input, Some(expected1.into()), None))
+    }
+
+    /// Add a case that only applies to the second part, using the transform
+    /// to parse the input.
+    ///
+    /// # Errors
+    /// Returns an error if the input could not be transformed
+    pub fn transformed_part2_case<S, O2>(
+        self,
+        name: S,
+        raw_input: &str,
+        expected2: O2,
+    ) -> Result<Self, E>
+    where
+        S: Into<String>,
+        O2: Into<ExpectedValue<T::Output2>>,
+    {
+        let input = self.transform(raw_input)?;
+        Ok(self.push(name.into(), input, None, Some(expected2.into())))
+    }
+
+    #[must_use]
+    pub fn collect(self) -> Vec<DayCase<T>> {
+        self.cases
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static PREPARED: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Debug)]
+    struct Sum;
+
+    impl DayRunner for Sum {
+        type Input = Vec<u32>;
+        type Shared = u32;
+        type Output1 = u32;
+        type Output2 = u32;
+        type Error = DefaultError;
+
+        fn name(&self) -> String {
+            "2015-D01".to_owned()
+        }
+
+        fn cases(&self) -> Result<Vec<DayCase<Self>>, Box<dyn std::error::Error>> {
+            Ok(DayCase::<Self>::build_set()
+                .case("Example", vec![1, 2, 3], 6, 12)
+                .collect())
+        }
+
+        fn prepare(input: &Self::Input) -> Result<Self::Shared, Self::Error> {
+            PREPARED.fetch_add(1, Ordering::SeqCst);
+            Ok(input.iter().sum())
+        }
+
+        fn part1(_input: &Self::Input, shared: &Self::Shared) -> Result<u32, Self::Error> {
+            Ok(*shared)
+        }
+
+        fn part2(_input: &Self::Input, shared: &Self::Shared) -> Result<u32, Self::Error> {
+            Ok(shared * 2)
+        }
+    }
+
+    #[test]
+    fn test_day_prepare() {
+        let results: Vec<PuzzleResult> = Sum
+            .into_puzzles()
+            .iter()
+            .map(|puzzle| puzzle.cases().unwrap()[0].run())
+            .collect();
+        assert!(results
+            .iter()
+            .all(|result| result.status == PuzzleResultStatus::Match));
+        assert_eq!(PREPARED.load(Ordering::SeqCst), 1);
+
+        // Both parts report the same preparation, whichever ran first
+        assert!(results[0].prepare.is_some());
+        assert_eq!(results[0].prepare, results[1].prepare);
+    }
+
+    #[test]
+    fn test_day_reset() {
+        let case = DayCase::<Sum>::build_set()
+            .case("Example", vec![4, 5], 9, 18)
             .collect()
+            .remove(0);
+        let (first, _) = case.shared().unwrap();
+        case.reset();
+        let (second, _) = case.shared().unwrap();
+        assert_eq!(first, second);
+        assert!(!Arc::ptr_eq(&first, &second));
     }
 }
@@ -0,0 +1,143 @@
+use crate::error::AdventError;
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+type ProgressSink = Arc<dyn Fn(usize, usize) + Send + Sync>;
+
+/// Lets a running puzzle report how far along it is, and find out if it
+/// should stop early.
+///
+/// Long running solutions should call [`RunContext::progress`] and
+/// [`RunContext::check`] every so often. Both are cheap, but not free, so
+/// calling them on every iteration of a tight loop is best avoided.
+#[derive(Clone, Default)]
+pub struct RunContext {
+    cancelled: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+    progress: Option<ProgressSink>,
+    seed: Option<u64>,
+}
+
+impl RunContext {
+    /// A context that is never cancelled and ignores progress.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel the run once `timeout` has passed, starting now.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.deadline = Some(Instant::now() + timeout);
+        self
+    }
+
+    /// Send progress reports to `sink`, as `(done, total)`.
+    #[must_use]
+    pub fn with_progress<F>(mut self, sink: F) -> Self
+    where
+        F: Fn(usize, usize) + Send + Sync + 'static,
+    {
+        self.progress = Some(Arc::new(sink));
+        self
+    }
+
+    /// Seed the random inputs of property cases that don't have a seed of
+    /// their own with `seed`.
+    #[must_use]
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// The seed for property cases, if one was given.
+    #[must_use]
+    pub const fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Ask the run to stop. This can be called from any thread holding a clone
+    /// of the context.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether the run has been cancelled, or has run out of time.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+            || self
+                .deadline
+                .map_or(false, |deadline| Instant::now() >= deadline)
+    }
+
+    /// Bail out of the run if it has been cancelled.
+    ///
+    /// # Errors
+    /// Returns `AdventError::Cancelled` if the run should stop.
+    pub fn check(&self) -> Result<(), AdventError> {
+        if self.is_cancelled() {
+            Err(AdventError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Report that `done` out of `total` units of work are complete.
+    pub fn progress(&self, done: usize, total: usize) {
+        if let Some(sink) = &self.progress {
+            sink(done, total);
+        }
+    }
+}
+
+impl fmt::Debug for RunContext {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("RunContext")
+            .field("cancelled", &self.cancelled)
+            .field("deadline", &self.deadline)
+            .field("progress", &self.progress.is_some())
+            .field("seed", &self.seed)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    #[test]
+    fn test_cancel() {
+        let ctx = RunContext::new();
+        let handle = ctx.clone();
+        assert_eq!(ctx.check(), Ok(()));
+        handle.cancel();
+        assert_eq!(ctx.check(), Err(AdventError::Cancelled));
+    }
+
+    #[test]
+    fn test_timeout() {
+        let ctx = RunContext::new().with_timeout(Duration::from_secs(0));
+        assert!(ctx.is_cancelled());
+        let ctx = RunContext::new().with_timeout(Duration::from_secs(3600));
+        assert!(!ctx.is_cancelled());
+    }
+
+    #[test]
+    fn test_progress() {
+        let reported = Arc::new(AtomicUsize::new(0));
+        let sink = reported.clone();
+        let ctx = RunContext::new().with_progress(move |done, _total| {
+            sink.store(done, Ordering::Relaxed);
+        });
+        ctx.progress(7, 10);
+        assert_eq!(reported.load(Ordering::Relaxed), 7);
+    }
+}
@@ -0,0 +1,134 @@
+use crate::answer::Answer;
+use std::fmt::Debug;
+
+/// An expected and actual value that are worth showing as a diff, instead of
+/// on a single line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mismatch {
+    pub expected: String,
+    pub actual: String,
+}
+
+impl Mismatch {
+    /// Describe how `actual` differs from `expected`, if the difference is
+    /// best shown line by line. Multi-line answers, such as drawings, are
+    /// compared as-is. Collections and other structured values are compared
+    /// using their pretty-printed debug form, which has one element per line.
+    /// Returns `None` for simple values that fit on one line.
+    pub fn new<O: Answer + Debug>(expected: &O, actual: &O) -> Option<Self> {
+        let (expected_text, actual_text) = (expected.submission(), actual.submission());
+        if expected_text.contains('\n') || actual_text.contains('\n') {
+            return Some(Self {
+                expected: expected_text,
+                actual: actual_text,
+            });
+        }
+
+        let (expected_debug, actual_debug) = (format!("{:#?}", expected), format!("{:#?}", actual));
+        if expected_debug.contains('\n') || actual_debug.contains('\n') {
+            Some(Self {
+                expected: expected_debug,
+                actual: actual_debug,
+            })
+        } else {
+            None
+        }
+    }
+
+    #[must_use]
+    pub fn diff(&self) -> Vec<DiffLine> {
+        diff_lines(&self.expected, &self.actual)
+    }
+}
+
+/// One line of a line-by-line diff.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiffLine<'a> {
+    /// A line that is in both texts.
+    Same(&'a str),
+    /// A line that is only in the expected text.
+    Expected(&'a str),
+    /// A line that is only in the actual text.
+    Actual(&'a str),
+}
+
+/// Compare two texts line by line, using their longest common subsequence of
+/// lines. Lines only in `expected` are listed before lines only in `actual`.
+#[must_use]
+pub fn diff_lines<'a>(expected: &'a str, actual: &'a str) -> Vec<DiffLine<'a>> {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+
+    // lcs[i][j] is the length of the longest common subsequence of
+    // `expected[i..]` and `actual[j..]`
+    let mut lcs = vec![vec![0_usize; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            lcs[i][j] = if expected[i] == actual[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = Vec::with_capacity(expected.len().max(actual.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() && j < actual.len() {
+        if expected[i] == actual[j] {
+            diff.push(DiffLine::Same(expected[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            diff.push(DiffLine::Expected(expected[i]));
+            i += 1;
+        } else {
+            diff.push(DiffLine::Actual(actual[j]));
+            j += 1;
+        }
+    }
+    diff.extend(expected[i..].iter().map(|line| DiffLine::Expected(line)));
+    diff.extend(actual[j..].iter().map(|line| DiffLine::Actual(line)));
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mismatch_single_line() {
+        assert_eq!(Mismatch::new(&1_u32, &2_u32), None);
+        assert_eq!(Mismatch::new(&"ab".to_string(), &"ac".to_string()), None);
+    }
+
+    #[test]
+    fn test_mismatch_multi_line() {
+        let mismatch = Mismatch::new(&"a\nb".to_string(), &"a\nc".to_string());
+        assert_eq!(mismatch.map(|m| m.diff().len()), Some(3));
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        let expected = "#..#\n.##.\n#..#";
+        let actual = "#..#\n.#..\n#..#\n....";
+        assert_eq!(
+            diff_lines(expected, actual),
+            vec![
+                DiffLine::Same("#..#"),
+                DiffLine::Expected(".##."),
+                DiffLine::Actual(".#.."),
+                DiffLine::Same("#..#"),
+                DiffLine::Actual("...."),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_identical() {
+        assert_eq!(
+            diff_lines("a\nb", "a\nb"),
+            vec![DiffLine::Same("a"), DiffLine::Same("b")]
+        );
+    }
+}
@@ -0,0 +1,277 @@
+use std::{error::Error, fmt};
+
+/// The ways a puzzle solution can fail, in a form the runner can report on.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AdventError {
+    /// The input could not be parsed. `line` and `column` are 1-based, and
+    /// `text` is the part of the input that was rejected.
+    Parse {
+        line: Option<usize>,
+        column: Option<usize>,
+        text: String,
+        message: String,
+    },
+
+    /// The solution ran to completion, but didn't find an answer.
+    NoSolution,
+
+    /// The input broke an assumption that the solution relies on.
+    InvalidInput(String),
+
+    /// The solution hasn't been written yet.
+    NotImplemented,
+
+    /// The run was stopped before it finished, for example by a timeout.
+    Cancelled,
+}
+
+impl AdventError {
+    /// A parse error with no known location.
+    pub fn parse<T, M>(text: T, message: M) -> Self
+    where
+        T: Into<String>,
+        M: Into<String>,
+    {
+        Self::Parse {
+            line: None,
+            column: None,
+            text: text.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Build an `InvalidInput` error.
+    pub fn invalid_input<M: Into<String>>(message: M) -> Self {
+        Self::InvalidInput(message.into())
+    }
+}
+
+impl fmt::Display for AdventError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Parse {
+                line,
+                column,
+                text,
+                message,
+            } => {
+                write!(fmt, "Parse error")?;
+                match (line, column) {
+                    (Some(line), Some(column)) => {
+                        write!(fmt, " at line {}, column {}", line, column)?
+                    }
+                    (Some(line), None) => write!(fmt, " at line {}", line)?,
+                    (None, Some(column)) => write!(fmt, " at column {}", column)?,
+                    (None, None) => (),
+                }
+                write!(fmt, ": {} in `{}`", message, text)
+            }
+            Self::NoSolution => write!(fmt, "No solution found"),
+            Self::InvalidInput(message) => write!(fmt, "Invalid input: {}", message),
+            Self::NotImplemented => write!(fmt, "Not implemented"),
+            Self::Cancelled => write!(fmt, "Cancelled"),
+        }
+    }
+}
+
+impl Error for AdventError {}
+
+/// An error from parsing one line of a puzzle's input, with the line it
+/// happened on. Any error from a `FromStr` impl can be wrapped in one.
+#[derive(Debug)]
+pub struct ParseError {
+    /// The 1-based line number.
+    pub line: usize,
+    /// The 1-based column, if the wrapped error knows it.
+    pub column: Option<usize>,
+    /// The line that couldn't be parsed.
+    pub text: String,
+    source: Box<dyn Error + Send + Sync>,
+}
+
+impl ParseError {
+    /// Wrap `err`, which happened while parsing `text`, the line numbered
+    /// `line`. If `err` is an [`AdventError::Parse`] with a column, the column
+    /// is kept.
+    pub fn new<T, E>(line: usize, text: T, err: E) -> Self
+    where
+        T: Into<String>,
+        E: Into<Box<dyn Error + Send + Sync>>,
+    {
+        let source = err.into();
+        let column = match source.downcast_ref::<AdventError>() {
+            Some(AdventError::Parse { column, .. }) => *column,
+            _ => None,
+        };
+        Self {
+            line,
+            column,
+            text: text.into(),
+            source,
+        }
+    }
+
+    /// Move the column right by `offset`, for lines that were trimmed before
+    /// being parsed.
+    #[must_use]
+    pub fn shifted(mut self, offset: usize) -> Self {
+        self.column = self.column.map(|column| column + offset);
+        self
+    }
+
+    /// What went wrong, without the location.
+    #[must_use]
+    pub fn message(&self) -> String {
+        match self.source.downcast_ref::<AdventError>() {
+            Some(AdventError::Parse { message, .. }) => message.clone(),
+            _ => self.source.to_string(),
+        }
+    }
+}
+
+impl From<ParseError> for AdventError {
+    fn from(err: ParseError) -> Self {
+        Self::Parse {
+            line: Some(err.line),
+            column: err.column,
+            message: err.message(),
+            text: err.text,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let err = AdventError::Parse {
+            line: Some(self.line),
+            column: self.column,
+            text: self.text.clone(),
+            message: self.message(),
+        };
+        write!(fmt, "{}", err)
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
+
+/// Where in a puzzle's input an error happened, so the runner can show the
+/// line and mark the column.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ErrorLocation {
+    pub line: usize,
+    pub column: Option<usize>,
+    pub text: String,
+}
+
+impl ErrorLocation {
+    /// The location of `err`, or of the error that caused it, if it is a
+    /// parse error that knows its line.
+    #[must_use]
+    pub fn of(err: &(dyn Error + 'static)) -> Option<Self> {
+        let mut next = Some(err);
+        while let Some(err) = next {
+            if let Some(err) = err.downcast_ref::<ParseError>() {
+                return Some(Self {
+                    line: err.line,
+                    column: err.column,
+                    text: err.text.clone(),
+                });
+            }
+            if let Some(AdventError::Parse {
+                line: Some(line),
+                column,
+                text,
+                ..
+            }) = err.downcast_ref::<AdventError>()
+            {
+                return Some(Self {
+                    line: *line,
+                    column: *column,
+                    text: text.clone(),
+                });
+            }
+            next = err.source();
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_parse() {
+        let err = AdventError::Parse {
+            line: Some(3),
+            column: Some(5),
+            text: "jmp +x".to_string(),
+            message: "invalid digit".to_string(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "Parse error at line 3, column 5: invalid digit in `jmp +x`"
+        );
+        assert_eq!(
+            AdventError::parse("xyz", "Invalid operation").to_string(),
+            "Parse error: Invalid operation in `xyz`"
+        );
+    }
+
+    #[test]
+    fn test_boxed_downcast() {
+        let boxed: Box<dyn Error> = AdventError::NotImplemented.into();
+        assert_eq!(
+            boxed.downcast_ref::<AdventError>(),
+            Some(&AdventError::NotImplemented)
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = ParseError::new(3, "jmp +x", "invalid digit".to_string());
+        assert_eq!(
+            err.to_string(),
+            "Parse error at line 3: invalid digit in `jmp +x`"
+        );
+
+        let err = ParseError::new(
+            2,
+            "  #1 @ 1;3",
+            AdventError::Parse {
+                line: None,
+                column: Some(6),
+                text: "#1 @ 1;3".to_string(),
+                message: "expected `,`".to_string(),
+            },
+        )
+        .shifted(2);
+        assert_eq!(
+            AdventError::from(err),
+            AdventError::Parse {
+                line: Some(2),
+                column: Some(8),
+                text: "  #1 @ 1;3".to_string(),
+                message: "expected `,`".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_error_location() {
+        let boxed: Box<dyn Error> = ParseError::new(4, "xyz +1", "Invalid operation").into();
+        assert_eq!(
+            ErrorLocation::of(boxed.as_ref()),
+            Some(ErrorLocation {
+                line: 4,
+                column: None,
+                text: "xyz +1".to_string(),
+            })
+        );
+        assert_eq!(ErrorLocation::of(&AdventError::NoSolution), None);
+    }
+}
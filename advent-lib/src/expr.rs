@@ -0,0 +1,488 @@
+//! Arithmetic expressions, like `1 + 2 * -(3 - 4)`, with the precedence of
+//! the operators chosen at runtime.
+//!
+//! ```
+//! use advent_lib::expr::{Assoc, BinaryOp, Expr, Precedence};
+//!
+//! // `+` binds tighter than `*`
+//! let precedence = Precedence::new()
+//!     .level(Assoc::Left, &[BinaryOp::Mul])
+//!     .level(Assoc::Left, &[BinaryOp::Add]);
+//! let expr: Expr<u64> = Expr::parse("(1 + 2) * (3 + 4)", &precedence).unwrap();
+//! assert_eq!(expr.eval(), Ok(21));
+//! assert_eq!(expr.display(&precedence).to_string(), "1 + 2 * 3 + 4");
+//! ```
+
+use crate::error::AdventError;
+use num_traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Zero};
+use std::{error::Error, fmt, str::FromStr};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl BinaryOp {
+    const ALL: [Self; 4] = [Self::Add, Self::Sub, Self::Mul, Self::Div];
+
+    #[must_use]
+    pub const fn symbol(self) -> char {
+        match self {
+            Self::Add => '+',
+            Self::Sub => '-',
+            Self::Mul => '*',
+            Self::Div => '/',
+        }
+    }
+
+    fn from_symbol(c: char) -> Option<Self> {
+        Self::ALL.iter().copied().find(|op| op.symbol() == c)
+    }
+}
+
+impl fmt::Display for BinaryOp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+/// Which way a chain of operators at the same level groups.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Assoc {
+    /// `a - b - c` is `(a - b) - c`.
+    Left,
+    /// `a - b - c` is `a - (b - c)`.
+    Right,
+}
+
+/// The binary operators an expression may use, from the loosest binding to
+/// the tightest. Unary minus binds tighter than all of them.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Precedence {
+    levels: Vec<(Assoc, Vec<BinaryOp>)>,
+}
+
+impl Precedence {
+    /// A table with no operators, to add levels to.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The usual rules: `*` and `/` before `+` and `-`, all grouping to the
+    /// left.
+    #[must_use]
+    pub fn standard() -> Self {
+        Self::new()
+            .level(Assoc::Left, &[BinaryOp::Add, BinaryOp::Sub])
+            .level(Assoc::Left, &[BinaryOp::Mul, BinaryOp::Div])
+    }
+
+    /// Add a level that binds tighter than all the levels before it.
+    #[must_use]
+    pub fn level(mut self, assoc: Assoc, ops: &[BinaryOp]) -> Self {
+        self.levels.push((assoc, ops.to_vec()));
+        self
+    }
+
+    /// The level of `op`, counting from 0 for the loosest, and how it
+    /// groups. `None` if the operator isn't allowed.
+    fn find(&self, op: BinaryOp) -> Option<(usize, Assoc)> {
+        self.levels
+            .iter()
+            .enumerate()
+            .find(|(_, (_, ops))| ops.contains(&op))
+            .map(|(level, (assoc, _))| (level, *assoc))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expr<T> {
+    Num(T),
+    Neg(Box<Expr<T>>),
+    Binary(BinaryOp, Box<Expr<T>>, Box<Expr<T>>),
+}
+
+impl<T> Expr<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    /// Parse an expression of numbers, parentheses, unary minus, and the
+    /// operators in `precedence`.
+    ///
+    /// # Errors
+    ///
+    /// If the expression is malformed, uses an operator that isn't in
+    /// `precedence`, or has a number that isn't a valid `T`. The error has
+    /// the column of the problem.
+    pub fn parse(input: &str, precedence: &Precedence) -> Result<Self, AdventError> {
+        let mut parser = Parser {
+            input,
+            tokens: tokenize(input)?,
+            position: 0,
+            precedence,
+        };
+        let expr = parser.expr(0)?;
+        match parser.peek() {
+            None => Ok(expr),
+            Some(&(column, token)) => Err(parser.error(column, format!("unexpected {}", token))),
+        }
+    }
+}
+
+impl<T> Expr<T>
+where
+    T: Copy + Zero + CheckedAdd + CheckedSub + CheckedMul + CheckedDiv,
+{
+    /// The value of the expression.
+    ///
+    /// # Errors
+    ///
+    /// If any step overflows a `T`, or divides by zero.
+    pub fn eval(&self) -> Result<T, EvalError> {
+        match self {
+            Self::Num(n) => Ok(*n),
+            Self::Neg(expr) => T::zero()
+                .checked_sub(&expr.eval()?)
+                .ok_or(EvalError::Overflow),
+            Self::Binary(op, left, right) => {
+                let (left, right) = (left.eval()?, right.eval()?);
+                match op {
+                    BinaryOp::Add => left.checked_add(&right),
+                    BinaryOp::Sub => left.checked_sub(&right),
+                    BinaryOp::Mul => left.checked_mul(&right),
+                    BinaryOp::Div if right.is_zero() => return Err(EvalError::DivideByZero),
+                    BinaryOp::Div => left.checked_div(&right),
+                }
+                .ok_or(EvalError::Overflow)
+            }
+        }
+    }
+}
+
+impl<T> Expr<T> {
+    /// Show the expression with only the parentheses that parsing it with
+    /// `precedence` would need.
+    pub const fn display<'a>(&'a self, precedence: &'a Precedence) -> ExprDisplay<'a, T> {
+        ExprDisplay {
+            expr: self,
+            precedence,
+        }
+    }
+}
+
+/// An expression shown with minimal parentheses, from [`Expr::display`].
+#[derive(Debug)]
+pub struct ExprDisplay<'a, T> {
+    expr: &'a Expr<T>,
+    precedence: &'a Precedence,
+}
+
+impl<'a, T> ExprDisplay<'a, T> {
+    const fn child(&self, expr: &'a Expr<T>) -> Self {
+        Self {
+            expr,
+            precedence: self.precedence,
+        }
+    }
+
+    /// Whether `child`, on the left or right of `parent`, needs parentheses
+    /// to keep its place in the tree.
+    fn needs_parens(&self, parent: BinaryOp, child: &Expr<T>, is_left: bool) -> bool {
+        let op = match child {
+            Expr::Binary(op, _, _) => *op,
+            Expr::Num(_) | Expr::Neg(_) => return false,
+        };
+        match (self.precedence.find(parent), self.precedence.find(op)) {
+            (Some((parent_level, assoc)), Some((level, _))) => {
+                level < parent_level
+                    || (level == parent_level && is_left == (assoc == Assoc::Right))
+            }
+            // Operators that can't be parsed can't be placed either
+            _ => true,
+        }
+    }
+}
+
+impl<'a, T: fmt::Display> fmt::Display for ExprDisplay<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.expr {
+            Expr::Num(n) => write!(f, "{}", n),
+            Expr::Neg(expr) => match **expr {
+                Expr::Binary(..) => write!(f, "-({})", self.child(expr)),
+                Expr::Num(_) | Expr::Neg(_) => write!(f, "-{}", self.child(expr)),
+            },
+            Expr::Binary(op, left, right) => {
+                if self.needs_parens(*op, left, true) {
+                    write!(f, "({})", self.child(left))?;
+                } else {
+                    write!(f, "{}", self.child(left))?;
+                }
+                write!(f, " {} ", op)?;
+                if self.needs_parens(*op, right, false) {
+                    write!(f, "({})", self.child(right))
+                } else {
+                    write!(f, "{}", self.child(right))
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EvalError {
+    Overflow,
+    DivideByZero,
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Overflow => write!(f, "Expression overflowed"),
+            Self::DivideByZero => write!(f, "Expression divides by zero"),
+        }
+    }
+}
+
+impl Error for EvalError {}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Token<'a> {
+    Num(&'a str),
+    Op(char),
+    Open,
+    Close,
+}
+
+impl<'a> fmt::Display for Token<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Num(n) => write!(f, "number `{}`", n),
+            Self::Op(c) => write!(f, "`{}`", c),
+            Self::Open => write!(f, "`(`"),
+            Self::Close => write!(f, "`)`"),
+        }
+    }
+}
+
+/// Split an expression into tokens, each with its 1-based column.
+fn tokenize(input: &str) -> Result<Vec<(usize, Token)>, AdventError> {
+    let mut tokens = vec![];
+    let mut chars = input.char_indices().enumerate().peekable();
+    while let Some((column, (start, c))) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::Open,
+            ')' => Token::Close,
+            c if BinaryOp::from_symbol(c).is_some() => Token::Op(c),
+            c if c.is_ascii_digit() => {
+                let mut end = start + 1;
+                while let Some((_, (idx, c))) = chars.peek() {
+                    if !c.is_ascii_digit() {
+                        break;
+                    }
+                    end = idx + 1;
+                    chars.next();
+                }
+                Token::Num(&input[start..end])
+            }
+            c => {
+                return Err(AdventError::Parse {
+                    line: None,
+                    column: Some(column + 1),
+                    text: input.to_owned(),
+                    message: format!("unexpected `{}`", c),
+                })
+            }
+        };
+        tokens.push((column + 1, token));
+    }
+    Ok(tokens)
+}
+
+/// A precedence climbing parser.
+struct Parser<'a, 'p> {
+    input: &'a str,
+    tokens: Vec<(usize, Token<'a>)>,
+    position: usize,
+    precedence: &'p Precedence,
+}
+
+impl<'a, 'p> Parser<'a, 'p> {
+    fn peek(&self) -> Option<&(usize, Token<'a>)> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<(usize, Token<'a>)> {
+        let token = self.tokens.get(self.position).copied();
+        self.position += 1;
+        token
+    }
+
+    fn error<M: Into<String>>(&self, column: usize, message: M) -> AdventError {
+        AdventError::Parse {
+            line: None,
+            column: Some(column),
+            text: self.input.to_owned(),
+            message: message.into(),
+        }
+    }
+
+    fn end_column(&self) -> usize {
+        self.input.chars().count() + 1
+    }
+
+    /// An expression whose operators are all at `min_level` or tighter.
+    fn expr<T>(&mut self, min_level: usize) -> Result<Expr<T>, AdventError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let mut left = self.unary()?;
+        while let Some(&(column, Token::Op(c))) = self.peek() {
+            let op = BinaryOp::from_symbol(c).expect("only operators are tokenized as Op");
+            let (level, assoc) = self
+                .precedence
+                .find(op)
+                .ok_or_else(|| self.error(column, format!("operator `{}` is not allowed", op)))?;
+            if level < min_level {
+                break;
+            }
+            self.next();
+            let right = self.expr(match assoc {
+                Assoc::Left => level + 1,
+                Assoc::Right => level,
+            })?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    /// A number, a parenthesized expression, or a negation of either.
+    fn unary<T>(&mut self) -> Result<Expr<T>, AdventError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        match self.next() {
+            Some((_, Token::Op('-'))) => Ok(Expr::Neg(Box::new(self.unary()?))),
+            Some((column, Token::Num(digits))) => digits.parse().map(Expr::Num).map_err(|err| {
+                let type_name = std::any::type_name::<T>();
+                self.error(
+                    column,
+                    format!(
+                        "`{}` is not a valid {} ({})",
+                        digits,
+                        type_name.rsplit("::").next().unwrap_or(type_name),
+                        err
+                    ),
+                )
+            }),
+            Some((column, Token::Open)) => {
+                let expr = self.expr(0)?;
+                match self.next() {
+                    Some((_, Token::Close)) => Ok(expr),
+                    Some((column, token)) => {
+                        Err(self.error(column, format!("expected `)`, found {}", token)))
+                    }
+                    None => Err(self.error(column, "`(` is never closed")),
+                }
+            }
+            Some((column, token)) => {
+                Err(self.error(column, format!("expected a number or `(`, found {}", token)))
+            }
+            None => Err(self.error(self.end_column(), "expected a number or `(`")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flat() -> Precedence {
+        Precedence::new().level(Assoc::Left, &[BinaryOp::Add, BinaryOp::Mul])
+    }
+
+    fn eval(input: &str, precedence: &Precedence) -> i64 {
+        Expr::<i64>::parse(input, precedence)
+            .unwrap()
+            .eval()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_precedence() {
+        assert_eq!(eval("1 + 2 * 3", &Precedence::standard()), 7);
+        assert_eq!(eval("1 + 2 * 3", &flat()), 9);
+        assert_eq!(eval("10 - 4 - 3", &Precedence::standard()), 3);
+        let right = Precedence::new().level(Assoc::Right, &[BinaryOp::Sub]);
+        assert_eq!(eval("10 - 4 - 3", &right), 9);
+    }
+
+    #[test]
+    fn test_unary_minus() {
+        let standard = Precedence::standard();
+        assert_eq!(eval("-2 * 3", &standard), -6);
+        assert_eq!(eval("4 - -(1 + 2)", &standard), 7);
+        assert_eq!(eval("--5", &standard), 5);
+    }
+
+    #[test]
+    fn test_multiple_digits() {
+        assert_eq!(eval("123 + 4567", &Precedence::standard()), 4690);
+    }
+
+    #[test]
+    fn test_eval_errors() {
+        let standard = Precedence::standard();
+        let expr = Expr::<u8>::parse("1 - 2", &standard).unwrap();
+        assert_eq!(expr.eval(), Err(EvalError::Overflow));
+        let expr = Expr::<u8>::parse("1 / (2 - 2)", &standard).unwrap();
+        assert_eq!(expr.eval(), Err(EvalError::DivideByZero));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let column = |input: &str| match Expr::<u64>::parse(input, &flat()) {
+            Err(AdventError::Parse {
+                column, message, ..
+            }) => (column, message),
+            other => panic!("unexpected {:?}", other),
+        };
+        assert_eq!(
+            column("1 + +"),
+            (Some(5), "expected a number or `(`, found `+`".to_owned())
+        );
+        assert_eq!(
+            column("2 / 3"),
+            (Some(3), "operator `/` is not allowed".to_owned())
+        );
+        assert_eq!(
+            column("(1 + 2"),
+            (Some(1), "`(` is never closed".to_owned())
+        );
+        assert_eq!(column("1 2"), (Some(3), "unexpected number `2`".to_owned()));
+        assert_eq!(column("1 ^ 2"), (Some(3), "unexpected `^`".to_owned()));
+    }
+
+    #[test]
+    fn test_display() {
+        let standard = Precedence::standard();
+        let show = |input: &str| {
+            Expr::<i64>::parse(input, &standard)
+                .unwrap()
+                .display(&standard)
+                .to_string()
+        };
+        assert_eq!(show("((1 + 2)) + 3"), "1 + 2 + 3");
+        assert_eq!(show("1 + (2 + 3)"), "1 + (2 + 3)");
+        assert_eq!(show("(1 + 2) * (3 * 4)"), "(1 + 2) * (3 * 4)");
+        assert_eq!(show("1 + (2 * 3)"), "1 + 2 * 3");
+        assert_eq!(show("-(1 + 2) - -3"), "-(1 + 2) - -3");
+    }
+}
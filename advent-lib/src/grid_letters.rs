@@ -1,4 +1,6 @@
-use std::{error::Error, lazy::Lazy, ops::Deref};
+use crate::answer::Answer;
+use once_cell::unsync::Lazy;
+use std::{error::Error, fmt, ops::Deref};
 
 pub struct Recognizer<const W: usize> {
     alphabet: Alphabet<W>,
@@ -23,12 +25,15 @@ impl<const LETTER_WIDTH: usize> Recognizer<LETTER_WIDTH> {
             .step_by(self.alphabet.y_stride)
             .map(|line| line.chars().collect())
             .collect();
-        let mut result =
-            String::with_capacity(lines[0].len() / LETTER_WIDTH / self.alphabet.x_stride);
+        let width = match lines.first() {
+            Some(line) => line.len(),
+            None => return Err("There are no letters to recognize".into()),
+        };
+        let mut result = String::with_capacity(width / LETTER_WIDTH / self.alphabet.x_stride);
         let mut char_accumulator = Vec::with_capacity(LETTER_WIDTH / self.alphabet.x_stride);
 
         // Look at each column in turn
-        for cursor in (0..lines[0].len()).step_by(self.alphabet.x_stride) {
+        for cursor in (0..width).step_by(self.alphabet.x_stride) {
             // Pull the column's data from the input as bitstring
             let mut col_data = 0;
             for line in &lines {
@@ -61,6 +66,47 @@ impl<const LETTER_WIDTH: usize> Recognizer<LETTER_WIDTH> {
 
         Ok(result)
     }
+
+    /// Read the letters drawn in `input`. If they can't all be recognized, the
+    /// drawing itself is kept instead, so that it can still be read by eye.
+    #[must_use]
+    pub fn read(&self, input: &str) -> GridLetters {
+        self.parse(input)
+            .map_or_else(|_| GridLetters::from(input), GridLetters)
+    }
+}
+
+/// Letters drawn as pixels on a grid, which is how several puzzles present
+/// their answer. Holds the recognized text, or the drawing if it couldn't be
+/// recognized.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GridLetters(pub String);
+
+impl From<&str> for GridLetters {
+    /// Keep a drawing as-is, normalized so that trailing whitespace doesn't
+    /// affect comparisons.
+    fn from(drawing: &str) -> Self {
+        let lines: Vec<&str> = drawing.lines().map(str::trim_end).collect();
+        Self(lines.join("\n").trim_matches('\n').to_owned())
+    }
+}
+
+impl From<String> for GridLetters {
+    fn from(drawing: String) -> Self {
+        Self::from(drawing.as_str())
+    }
+}
+
+impl fmt::Display for GridLetters {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}", self.0)
+    }
+}
+
+impl Answer for GridLetters {
+    fn submission(&self) -> String {
+        self.0.clone()
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -92,6 +138,84 @@ pub const ALPHABET_2019_D11: Lazy<Alphabet<4>> = Lazy::new(|| Alphabet {
     ],
 });
 
+#[allow(clippy::declare_interior_mutable_const)]
+pub const ALPHABET_2018_D10: Lazy<Alphabet<6>> = Lazy::new(|| Alphabet {
+    x_stride: 1,
+    y_stride: 1,
+    letter_data: vec![
+        (
+            'A',
+            [
+                0b00_1111_1111,
+                0b01_0001_0000,
+                0b10_0001_0000,
+                0b10_0001_0000,
+                0b01_0001_0000,
+                0b00_1111_1111,
+            ],
+        ),
+        (
+            'L',
+            [
+                0b11_1111_1111,
+                0b00_0000_0001,
+                0b00_0000_0001,
+                0b00_0000_0001,
+                0b00_0000_0001,
+                0b00_0000_0001,
+            ],
+        ),
+        (
+            'N',
+            [
+                0b11_1111_1111,
+                0b01_1000_0000,
+                0b00_0110_0000,
+                0b00_0001_1000,
+                0b00_0000_0110,
+                0b11_1111_1111,
+            ],
+        ),
+        (
+            'P',
+            [
+                0b11_1111_1111,
+                0b10_0010_0000,
+                0b10_0010_0000,
+                0b10_0010_0000,
+                0b10_0010_0000,
+                0b01_1100_0000,
+            ],
+        ),
+        (
+            'R',
+            [
+                0b11_1111_1111,
+                0b10_0010_0000,
+                0b10_0010_0000,
+                0b10_0011_0000,
+                0b10_0010_1100,
+                0b01_1100_0011,
+            ],
+        ),
+    ],
+});
+
+// Letters in this font are 4 pixels wide, but the gap after a letter is
+// included, as letters such as `Y` use the full 5 columns.
+#[allow(clippy::declare_interior_mutable_const)]
+pub const ALPHABET_2019_D08: Lazy<Alphabet<5>> = Lazy::new(|| Alphabet {
+    x_stride: 2,
+    y_stride: 1,
+    letter_data: vec![
+        ('B', [0b11_1111, 0b10_1001, 0b10_1001, 0b01_0110, 0b00_0000]),
+        ('G', [0b01_1110, 0b10_0001, 0b10_0101, 0b01_0111, 0b00_0000]),
+        ('H', [0b11_1111, 0b00_1000, 0b00_1000, 0b11_1111, 0b00_0000]),
+        ('L', [0b11_1111, 0b00_0001, 0b00_0001, 0b00_0001, 0b00_0000]),
+        ('Y', [0b11_0000, 0b00_1000, 0b00_0111, 0b00_1000, 0b11_0000]),
+    ],
+});
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -128,4 +252,20 @@ mod tests {
             "RAPRCBPH".to_string()
         );
     }
+
+    #[test]
+    fn test_empty() {
+        let recognizer = Recognizer::new(ALPHABET_2019_D11);
+        assert!(recognizer.parse("").is_err());
+    }
+
+    #[test]
+    fn test_read_unrecognized() {
+        let grid = vec!["#   # ", "##### ", "#   #   "].join("\n");
+        let recognizer = Recognizer::new(ALPHABET_2018_D10);
+        assert_eq!(
+            recognizer.read(&grid),
+            GridLetters("#   #\n#####\n#   #".to_string())
+        );
+    }
 }
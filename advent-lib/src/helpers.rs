@@ -1,12 +1,124 @@
+use crate::{
+    error::{AdventError, ParseError},
+    twodee::{self, VecGrid},
+};
 use std::{
-    iter::Iterator,
+    error::Error,
+    fmt::Display,
+    iter::{Iterator, Peekable},
+    marker::PhantomData,
     ops::{Index, IndexMut},
-    str,
+    str::{self, FromStr},
 };
 
-pub trait StringAdventExt {
+pub trait StringAdventExt: AsRef<str> {
     fn trimmed_lines(&self) -> TrimmedLines;
     fn paragraphs(&self) -> Paragraphs;
+
+    /// Like [`trimmed_lines`](Self::trimmed_lines), but each line knows its
+    /// line number, so errors can say where they happened.
+    fn numbered_lines(&self) -> NumberedLines {
+        NumberedLines(self.as_ref().lines().enumerate())
+    }
+
+    /// Parse each non-blank line as a `T`. Errors say which line, and if
+    /// possible which column, couldn't be parsed.
+    fn parse_lines<T>(&self) -> ParseLines<T>
+    where
+        T: FromStr,
+        T::Err: Into<Box<dyn Error + Send + Sync>>,
+    {
+        ParseLines {
+            lines: self.numbered_lines(),
+            item: PhantomData,
+        }
+    }
+
+    /// The integers in the text, ignoring everything around them. A `-` or
+    /// `+` right before a number is its sign, unless it follows a letter or
+    /// digit, so `x=-1` is -1 but `1-3` is 1 and 3.
+    ///
+    /// # Panics
+    ///
+    /// When the iterator reaches a number that doesn't fit in a `T`. Use
+    /// [`try_ints`](Self::try_ints) to get an error instead.
+    fn ints<T: FromStr>(&self) -> Ints<T> {
+        Ints {
+            numbers: Numbers::new(self.as_ref(), true),
+            item: PhantomData,
+        }
+    }
+
+    /// The runs of digits in the text, ignoring any signs.
+    ///
+    /// # Panics
+    ///
+    /// When the iterator reaches a number that doesn't fit in a `T`. Use
+    /// [`try_unsigned_ints`](Self::try_unsigned_ints) to get an error instead.
+    fn unsigned_ints<T: FromStr>(&self) -> Ints<T> {
+        Ints {
+            numbers: Numbers::new(self.as_ref(), false),
+            item: PhantomData,
+        }
+    }
+
+    /// Like [`ints`](Self::ints), but a number that doesn't fit in a `T` is
+    /// an error, for parsers that report errors rather than panicking.
+    fn try_ints<T: FromStr>(&self) -> TryInts<T> {
+        TryInts {
+            numbers: Numbers::new(self.as_ref(), true),
+            item: PhantomData,
+        }
+    }
+
+    /// Like [`unsigned_ints`](Self::unsigned_ints), but a number that doesn't
+    /// fit in a `T` is an error.
+    fn try_unsigned_ints<T: FromStr>(&self) -> TryInts<T> {
+        TryInts {
+            numbers: Numbers::new(self.as_ref(), false),
+            item: PhantomData,
+        }
+    }
+
+    /// The runs of letters and digits in the text, skipping whitespace and
+    /// punctuation.
+    fn words(&self) -> Words {
+        Words(self.as_ref())
+    }
+
+    /// The comma separated values in the text, with the whitespace around each
+    /// trimmed, parsed as `T`s.
+    fn comma_separated<T: FromStr>(&self) -> CommaSeparated<T> {
+        let text = self.as_ref().trim();
+        CommaSeparated {
+            // An empty text has no values, rather than a single empty one
+            parts: if text.is_empty() {
+                None
+            } else {
+                Some(text.split(','))
+            },
+            item: PhantomData,
+        }
+    }
+
+    /// Split the non-blank lines of the text into sections, each starting at a
+    /// line that `is_header` accepts. Any lines before the first header form a
+    /// section with no header.
+    fn split_sections<F: FnMut(&str) -> bool>(&self, is_header: F) -> Sections<F> {
+        Sections {
+            lines: TrimmedLines(self.as_ref().lines()).peekable(),
+            is_header,
+        }
+    }
+
+    /// Parse each paragraph of the text as a [`Record`] of `key:value`
+    /// fields. Each record can be invalid without stopping the others.
+    fn records<T: Record>(&self) -> Records<T> {
+        Records {
+            paragraphs: Paragraphs(self.as_ref().lines()),
+            record: PhantomData,
+        }
+    }
 }
 
 impl StringAdventExt for &str {
@@ -46,6 +158,7 @@ impl<'a> Iterator for TrimmedLines<'a> {
     }
 }
 
+#[derive(Debug, Clone)]
 pub struct Paragraphs<'a>(std::str::Lines<'a>);
 
 impl<'a> Iterator for Paragraphs<'a> {
@@ -75,12 +188,273 @@ impl<'a> Iterator for Paragraphs<'a> {
     }
 }
 
+/// A non-blank line of a text, trimmed, with its position.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NumberedLine<'a> {
+    /// The 1-based line number.
+    pub number: usize,
+    /// The line with the whitespace around it trimmed.
+    pub text: &'a str,
+    /// The line as it appears in the text.
+    pub raw: &'a str,
+}
+
+impl<'a> NumberedLine<'a> {
+    /// Parse the line as a `T`, noting the line's position in any error.
+    ///
+    /// # Errors
+    ///
+    /// If the line isn't a valid `T`.
+    pub fn parse<T>(&self) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: Into<Box<dyn Error + Send + Sync>>,
+    {
+        self.parse_with(str::parse)
+    }
+
+    /// Like [`parse`](Self::parse), for parsers that need more than the text,
+    /// like [`Expr::parse`](crate::expr::Expr::parse).
+    ///
+    /// # Errors
+    ///
+    /// If `parse` fails.
+    pub fn parse_with<T, E, F>(&self, parse: F) -> Result<T, ParseError>
+    where
+        F: FnOnce(&'a str) -> Result<T, E>,
+        E: Into<Box<dyn Error + Send + Sync>>,
+    {
+        parse(self.text).map_err(|err| {
+            ParseError::new(self.number, self.raw.trim_end(), err).shifted(self.indent())
+        })
+    }
+
+    /// An error about the character at `column`, counting from 1 at the start
+    /// of the trimmed line.
+    pub fn error<M: Into<String>>(&self, column: usize, message: M) -> AdventError {
+        self.raw_error(column + self.indent(), message)
+    }
+
+    /// Like [`error`](Self::error), but `column` counts from 1 at the start of
+    /// the line as it appears in the text.
+    pub fn raw_error<M: Into<String>>(&self, column: usize, message: M) -> AdventError {
+        AdventError::Parse {
+            line: Some(self.number),
+            column: Some(column),
+            text: self.raw.trim_end().to_owned(),
+            message: message.into(),
+        }
+    }
+
+    /// The number of characters trimmed from the start of the line.
+    #[must_use]
+    pub fn indent(&self) -> usize {
+        let trimmed = self.raw.len() - self.raw.trim_start().len();
+        self.raw[..trimmed].chars().count()
+    }
+}
+
+/// The lines of a text, from [`StringAdventExt::numbered_lines`].
+#[derive(Debug, Clone)]
+pub struct NumberedLines<'a>(std::iter::Enumerate<std::str::Lines<'a>>);
+
+impl<'a> Iterator for NumberedLines<'a> {
+    type Item = NumberedLine<'a>;
+
+    fn next(&mut self) -> Option<NumberedLine<'a>> {
+        for (idx, raw) in &mut self.0 {
+            let text = raw.trim();
+            if !text.is_empty() {
+                return Some(NumberedLine {
+                    number: idx + 1,
+                    text,
+                    raw,
+                });
+            }
+        }
+        None
+    }
+}
+
+/// The parsed lines of a text, from [`StringAdventExt::parse_lines`].
+#[derive(Debug, Clone)]
+pub struct ParseLines<'a, T> {
+    lines: NumberedLines<'a>,
+    item: PhantomData<T>,
+}
+
+impl<'a, T> Iterator for ParseLines<'a, T>
+where
+    T: FromStr,
+    T::Err: Into<Box<dyn Error + Send + Sync>>,
+{
+    type Item = Result<T, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.lines.next().map(|line| line.parse())
+    }
+}
+
+/// The integers in a text, from [`StringAdventExt::ints`] or
+/// [`StringAdventExt::unsigned_ints`].
+#[derive(Debug, Clone)]
+pub struct Ints<'a, T> {
+    numbers: Numbers<'a>,
+    item: PhantomData<T>,
+}
+
+impl<'a, T: FromStr> Iterator for Ints<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let number = self.numbers.next()?;
+        match number.parse() {
+            Ok(n) => Some(n),
+            Err(_) => panic!(
+                "{} does not fit in a {}",
+                number,
+                std::any::type_name::<T>()
+            ),
+        }
+    }
+}
+
+/// The integers in a text, or the errors from parsing them, from
+/// [`StringAdventExt::try_ints`] or [`StringAdventExt::try_unsigned_ints`].
+#[derive(Debug, Clone)]
+pub struct TryInts<'a, T> {
+    numbers: Numbers<'a>,
+    item: PhantomData<T>,
+}
+
+impl<'a, T: FromStr> Iterator for TryInts<'a, T> {
+    type Item = Result<T, T::Err>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.numbers.next().map(str::parse)
+    }
+}
+
+/// The text of each integer in a text, with its sign if it has one.
+#[derive(Debug, Clone)]
+struct Numbers<'a> {
+    text: &'a str,
+    position: usize,
+    signed: bool,
+}
+
+impl<'a> Numbers<'a> {
+    const fn new(text: &'a str, signed: bool) -> Self {
+        Self {
+            text,
+            position: 0,
+            signed,
+        }
+    }
+}
+
+impl<'a> Iterator for Numbers<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        // Digits are always single bytes in UTF-8, so working on bytes is safe
+        let bytes = self.text.as_bytes();
+        let start = self.position + bytes[self.position..].iter().position(u8::is_ascii_digit)?;
+        let end = start
+            + bytes[start..]
+                .iter()
+                .position(|b| !b.is_ascii_digit())
+                .unwrap_or(bytes.len() - start);
+        self.position = end;
+
+        let has_sign = self.signed
+            && start > 0
+            && (bytes[start - 1] == b'-' || bytes[start - 1] == b'+')
+            && (start == 1 || !bytes[start - 2].is_ascii_alphanumeric());
+        Some(&self.text[if has_sign { start - 1 } else { start }..end])
+    }
+}
+
+/// The words in a text, from [`StringAdventExt::words`].
+#[derive(Debug, Clone)]
+pub struct Words<'a>(&'a str);
+
+impl<'a> Iterator for Words<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let rest = self.0.trim_start_matches(|c: char| !c.is_alphanumeric());
+        if rest.is_empty() {
+            return None;
+        }
+        let end = rest
+            .find(|c: char| !c.is_alphanumeric())
+            .unwrap_or_else(|| rest.len());
+        self.0 = &rest[end..];
+        Some(&rest[..end])
+    }
+}
+
+/// The values in a comma separated text, from
+/// [`StringAdventExt::comma_separated`].
+#[derive(Debug, Clone)]
+pub struct CommaSeparated<'a, T> {
+    parts: Option<str::Split<'a, char>>,
+    item: PhantomData<T>,
+}
+
+impl<'a, T: FromStr> Iterator for CommaSeparated<'a, T> {
+    type Item = Result<T, T::Err>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.parts.as_mut()?.next().map(|part| part.trim().parse())
+    }
+}
+
+/// A header line and the lines after it, up to the next header.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Section<'a> {
+    pub header: Option<&'a str>,
+    pub lines: Vec<&'a str>,
+}
+
+/// The sections of a text, from [`StringAdventExt::split_sections`].
+pub struct Sections<'a, F> {
+    lines: Peekable<TrimmedLines<'a>>,
+    is_header: F,
+}
+
+impl<'a, F: FnMut(&str) -> bool> Iterator for Sections<'a, F> {
+    type Item = Section<'a>;
+
+    fn next(&mut self) -> Option<Section<'a>> {
+        let first = *self.lines.peek()?;
+        let header = if (self.is_header)(first) {
+            self.lines.next()
+        } else {
+            None
+        };
+        let mut lines = vec![];
+        while let Some(line) = self.lines.peek() {
+            if (self.is_header)(line) {
+                break;
+            }
+            lines.push(*line);
+            self.lines.next();
+        }
+        Some(Section { header, lines })
+    }
+}
+
+/// A grid with `usize` coordinates.
+#[deprecated(note = "Use `twodee::VecGrid<usize, T>`, which this converts into")]
 #[derive(Debug)]
 pub struct Grid<T> {
     storage: Vec<T>,
     stride: usize,
 }
 
+#[allow(deprecated)]
 impl<T> Grid<T> {
     #[must_use]
     pub const fn width(&self) -> usize {
@@ -110,6 +484,7 @@ impl<T> Grid<T> {
     }
 }
 
+#[allow(deprecated)]
 impl<T: Default> Grid<T> {
     pub fn new(width: usize, height: usize) -> Self {
         let size = width * height;
@@ -122,18 +497,20 @@ impl<T: Default> Grid<T> {
     }
 }
 
+#[deprecated(note = "Use `twodee::Grid::iter_coords`")]
 pub struct GridCoordinateIterator {
     state: usize,
     width: usize,
     height: usize,
 }
 
+#[allow(deprecated)]
 impl Iterator for GridCoordinateIterator {
     type Item = Point;
 
     fn next(&mut self) -> Option<Self::Item> {
         let x = self.state % self.width;
-        let y = self.state / self.height;
+        let y = self.state / self.width;
         if y >= self.height {
             None
         } else {
@@ -143,11 +520,14 @@ impl Iterator for GridCoordinateIterator {
     }
 }
 
+#[deprecated(note = "Use `twodee::Grid::iter_values`")]
+#[allow(deprecated)]
 pub struct GridValIterator<'a, T> {
     state: usize,
     grid: &'a Grid<T>,
 }
 
+#[allow(deprecated)]
 impl<'a, T> Iterator for GridValIterator<'a, T> {
     type Item = &'a T;
 
@@ -162,6 +542,7 @@ impl<'a, T> Iterator for GridValIterator<'a, T> {
     }
 }
 
+#[allow(deprecated)]
 impl<T, I> Index<I> for Grid<T>
 where
     I: Into<Point>,
@@ -174,6 +555,7 @@ where
     }
 }
 
+#[allow(deprecated)]
 impl<T, I> IndexMut<I> for Grid<T>
 where
     I: Into<Point>,
@@ -184,6 +566,7 @@ where
     }
 }
 
+#[allow(deprecated)]
 impl<T> std::fmt::Display for Grid<Option<T>>
 where
     T: std::fmt::Display,
@@ -217,12 +600,23 @@ where
     }
 }
 
+#[allow(deprecated)]
+impl<T> From<Grid<T>> for VecGrid<usize, T> {
+    fn from(grid: Grid<T>) -> Self {
+        let bounds = twodee::Bounds::new(0, 0, grid.width(), grid.height());
+        Self::from_cells(bounds, grid.storage)
+    }
+}
+
+/// A point with `usize` coordinates.
+#[deprecated(note = "Use `twodee::Point<usize>`, which this converts into")]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct Point {
     pub x: usize,
     pub y: usize,
 }
 
+#[allow(deprecated)]
 impl Point {
     pub fn manhattan_distance<'a, T: Into<&'a Self>>(&self, other: T) -> usize {
         let other: &Self = other.into();
@@ -230,12 +624,14 @@ impl Point {
     }
 }
 
+#[allow(deprecated)]
 impl std::fmt::Display for Point {
     fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(fmt, "({}, {})", self.x, self.y)
     }
 }
 
+#[allow(deprecated)]
 impl std::str::FromStr for Point {
     type Err = Box<dyn std::error::Error>;
 
@@ -252,14 +648,29 @@ impl std::str::FromStr for Point {
     }
 }
 
+#[allow(deprecated)]
 impl From<(usize, usize)> for Point {
     fn from((x, y): (usize, usize)) -> Self {
         Self { x, y }
     }
 }
 
+#[allow(deprecated)]
+impl From<Point> for twodee::Point<usize> {
+    fn from(Point { x, y }: Point) -> Self {
+        Self::new(x, y)
+    }
+}
+
+#[allow(deprecated)]
+impl From<twodee::Point<usize>> for Point {
+    fn from(twodee::Point { x, y }: twodee::Point<usize>) -> Self {
+        Self { x, y }
+    }
+}
+
 trait Difference {
-    type Out = usize;
+    type Out;
     fn difference(&self, other: &Self) -> Self::Out;
 }
 
@@ -274,32 +685,393 @@ impl Difference for usize {
     }
 }
 
+#[deprecated(note = "Use `advent_lib::extremes`, or `bounds::Bounds` for points")]
 pub trait Bounds<T> {
     fn bounds(self) -> Option<(T, T)>;
 }
 
+#[allow(deprecated)]
 impl<Iter, T> Bounds<T> for Iter
 where
     Iter: Iterator<Item = T> + Sized,
     T: Ord + Clone,
 {
     fn bounds(self) -> Option<(T, T)> {
-        self.fold(None, |bounds, next| {
-            if let Some((prev_min, prev_max)) = bounds {
-                if next < prev_min {
-                    Some((next, prev_max))
-                } else if next > prev_max {
-                    Some((prev_min, next))
+        crate::extremes(self)
+    }
+}
+
+/// Parse `input` against a pattern, where each `{}` is a field, and return the
+/// fields as a tuple of the given types. Everything else in the pattern has to
+/// match exactly, and `{{` and `}}` stand for literal braces. A field can be
+/// named, like `{id}`, to make errors clearer.
+///
+/// Fields end at the first match of the text that follows them, so two fields
+/// must be separated by some text.
+///
+/// ```
+/// use advent_lib::parse_fmt;
+///
+/// let claim = parse_fmt!("#{id} @ {x},{y}: {w}x{h}", "#1 @ 1,3: 4x4" => (u32, usize, usize, usize, usize));
+/// assert_eq!(claim, Ok((1, 1, 3, 4, 4)));
+///
+/// let err = parse_fmt!("#{id} @ {x},{y}", "#1 @ 1,z" => (u32, usize, usize)).unwrap_err();
+/// assert_eq!(
+///     err.to_string(),
+///     "Parse error at column 8: field `y` is not a valid usize (invalid digit found in string) in `#1 @ 1,z`"
+/// );
+/// ```
+#[macro_export]
+macro_rules! parse_fmt {
+    ($pattern: expr, $input: expr => ($($t: ty),+ $(,)?)) => {
+        $crate::helpers::FmtFields::new(
+            $pattern,
+            $input,
+            [$(stringify!($t)),+].len(),
+        )
+        .and_then(|mut fields| Ok(($(fields.parse::<$t>()?,)+)))
+    };
+}
+
+#[derive(Debug, PartialEq)]
+enum FmtToken<'p> {
+    Literal(String),
+    Field(&'p str),
+}
+
+fn fmt_tokens(pattern: &str) -> Result<Vec<FmtToken>, AdventError> {
+    let mut tokens = vec![];
+    let mut literal = String::new();
+    let mut chars = pattern.char_indices().peekable();
+    while let Some((idx, c)) = chars.next() {
+        match c {
+            '{' if chars.peek().map(|(_, c)| *c) == Some('{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek().map(|(_, c)| *c) == Some('}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let end = pattern[idx..]
+                    .find('}')
+                    .ok_or_else(|| AdventError::parse(pattern, "Unclosed `{` in pattern"))?;
+                tokens.push(FmtToken::Literal(std::mem::take(&mut literal)));
+                tokens.push(FmtToken::Field(&pattern[idx + 1..idx + end]));
+                while chars.peek().map_or(false, |(i, _)| *i <= idx + end) {
+                    chars.next();
+                }
+            }
+            '}' => return Err(AdventError::parse(pattern, "Unmatched `}` in pattern")),
+            c => literal.push(c),
+        }
+    }
+    tokens.push(FmtToken::Literal(literal));
+    Ok(tokens)
+}
+
+/// The fields of a line matched against a pattern by [`parse_fmt!`], ready to
+/// be parsed in order.
+#[derive(Debug)]
+pub struct FmtFields<'p, 'a> {
+    input: &'a str,
+    /// The name, byte offset in `input`, and text of each field.
+    fields: std::vec::IntoIter<(&'p str, usize, &'a str)>,
+    position: usize,
+}
+
+impl<'p, 'a> FmtFields<'p, 'a> {
+    /// Match `input` against `pattern`, which should have `count` fields.
+    ///
+    /// # Errors
+    ///
+    /// If the pattern doesn't have `count` fields, or the input doesn't
+    /// match it.
+    pub fn new(pattern: &'p str, input: &'a str, count: usize) -> Result<Self, AdventError> {
+        let tokens = fmt_tokens(pattern)?;
+        let error = |offset: usize, message: String| AdventError::Parse {
+            line: None,
+            column: Some(input[..offset].chars().count() + 1),
+            text: input.to_owned(),
+            message,
+        };
+
+        // Tokens alternate between literals and fields, starting and ending
+        // with a (possibly empty) literal
+        let literal = |idx: usize| match &tokens[idx] {
+            FmtToken::Literal(literal) => literal.as_str(),
+            FmtToken::Field(_) => unreachable!(),
+        };
+        let field_count = tokens.len() / 2;
+        if field_count != count {
+            return Err(AdventError::parse(
+                pattern,
+                format!(
+                    "Pattern has {} fields, but {} types were given",
+                    field_count, count
+                ),
+            ));
+        }
+
+        if !input.starts_with(literal(0)) {
+            return Err(error(0, format!("expected `{}`", literal(0))));
+        }
+        let mut offset = literal(0).len();
+        let mut fields = vec![];
+        for field in 0..field_count {
+            let name = match &tokens[field * 2 + 1] {
+                FmtToken::Field(name) => *name,
+                FmtToken::Literal(_) => unreachable!(),
+            };
+            let next = literal(field * 2 + 2);
+            let rest = &input[offset..];
+            let len = if field == field_count - 1 {
+                // The last field runs up to the text that ends the pattern
+                if rest.ends_with(next) {
+                    rest.len() - next.len()
                 } else {
-                    Some((prev_min, prev_max))
+                    return Err(error(input.len(), format!("expected `{}`", next)));
                 }
+            } else if next.is_empty() {
+                return Err(AdventError::parse(
+                    pattern,
+                    "Fields in a pattern must be separated by some text",
+                ));
+            } else {
+                rest.find(next)
+                    .ok_or_else(|| error(offset, format!("expected `{}`", next)))?
+            };
+            fields.push((name, offset, &rest[..len]));
+            offset += len + next.len();
+        }
+
+        Ok(Self {
+            input,
+            fields: fields.into_iter(),
+            position: 0,
+        })
+    }
+
+    /// Parse the next field as a `T`.
+    ///
+    /// # Errors
+    ///
+    /// If the field isn't a valid `T`, or there are no fields left.
+    pub fn parse<T>(&mut self) -> Result<T, AdventError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.position += 1;
+        let (name, offset, text) = self.fields.next().ok_or_else(|| {
+            AdventError::parse(self.input, format!("There is no field {}", self.position))
+        })?;
+        text.parse().map_err(|err| {
+            let field = if name.is_empty() {
+                format!("field {}", self.position)
             } else {
-                Some((next.clone(), next.clone()))
+                format!("field `{}`", name)
+            };
+            let type_name = std::any::type_name::<T>();
+            AdventError::Parse {
+                line: None,
+                column: Some(self.input[..offset].chars().count() + 1),
+                text: self.input.to_owned(),
+                message: format!(
+                    "{} is not a valid {} ({})",
+                    field,
+                    type_name.rsplit("::").next().unwrap_or(type_name),
+                    err
+                ),
             }
         })
     }
 }
 
+pub use advent_derive::{FieldValue, Record};
+
+/// A record made of `key:value` fields, like `ecl:gry pid:860033327`. Derive
+/// it with `#[derive(Record)]`, which reads each field of a struct from the
+/// key with the same name.
+///
+/// ```
+/// use advent_lib::helpers::{FieldValue, Record};
+///
+/// #[derive(Debug, FieldValue)]
+/// enum Color {
+///     Red,
+///     #[record(value = "grn")]
+///     Green,
+/// }
+///
+/// #[derive(Debug, Record)]
+/// struct Package {
+///     #[record(key = "w", range(1, 10))]
+///     width: u32,
+///     color: Color,
+///     label: Option<String>,
+/// }
+///
+/// let package = Package::parse_record("color:grn w:3").unwrap();
+/// assert_eq!(package.width, 3);
+///
+/// let err = Package::parse_record("w:30 shape:square").unwrap_err();
+/// assert_eq!(
+///     err.to_string(),
+///     "field `w` has invalid value `30` (not between 1 and 10); unknown field `shape`; missing \
+///      field `color`"
+/// );
+/// ```
+pub trait Record: Sized {
+    /// Build a record from its fields, in the order they were given.
+    ///
+    /// # Errors
+    ///
+    /// Every field that is missing, unknown, or invalid.
+    fn from_fields(fields: &[(&str, &str)]) -> Result<Self, RecordError>;
+
+    /// Parse a record from whitespace separated `key:value` fields.
+    ///
+    /// # Errors
+    ///
+    /// Every field that isn't a `key:value` pair, is given more than once, or
+    /// that [`from_fields`](Self::from_fields) rejects.
+    fn parse_record(text: &str) -> Result<Self, RecordError> {
+        let mut errors = vec![];
+        let mut fields: Vec<(&str, &str)> = vec![];
+        for token in text.split_whitespace() {
+            match token.find(':') {
+                Some(idx) => {
+                    let key = &token[..idx];
+                    if fields.iter().any(|(seen, _)| *seen == key) {
+                        errors.push(FieldError::Duplicate(key.to_owned()));
+                    } else {
+                        fields.push((key, &token[idx + 1..]));
+                    }
+                }
+                None => errors.push(FieldError::Malformed(token.to_owned())),
+            }
+        }
+
+        match Self::from_fields(&fields) {
+            Ok(record) if errors.is_empty() => Ok(record),
+            Ok(_) => Err(RecordError { errors }),
+            Err(err) => {
+                errors.extend(err.errors);
+                Err(RecordError { errors })
+            }
+        }
+    }
+}
+
+/// Something wrong with one field of a record.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FieldError {
+    /// A part of the record that isn't a `key:value` pair.
+    Malformed(String),
+    Duplicate(String),
+    Unknown(String),
+    Missing(&'static str),
+    Invalid {
+        key: &'static str,
+        value: String,
+        reason: String,
+    },
+}
+
+impl std::fmt::Display for FieldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Malformed(text) => write!(f, "`{}` is not a `key:value` field", text),
+            Self::Duplicate(key) => write!(f, "field `{}` is given more than once", key),
+            Self::Unknown(key) => write!(f, "unknown field `{}`", key),
+            Self::Missing(key) => write!(f, "missing field `{}`", key),
+            Self::Invalid { key, value, reason } => write!(
+                f,
+                "field `{}` has invalid value `{}` ({})",
+                key, value, reason
+            ),
+        }
+    }
+}
+
+/// Everything wrong with a record, so that one bad field doesn't hide the
+/// others.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecordError {
+    pub errors: Vec<FieldError>,
+}
+
+impl RecordError {
+    /// Whether any of the fields that a record needs were left out.
+    #[must_use]
+    pub fn is_missing_fields(&self) -> bool {
+        self.errors
+            .iter()
+            .any(|err| matches!(err, FieldError::Missing(_)))
+    }
+}
+
+impl std::fmt::Display for RecordError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for (idx, err) in self.errors.iter().enumerate() {
+            if idx > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{}", err)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for RecordError {}
+
+/// Parse the value of one field for `#[derive(Record)]`, and store it in
+/// `slot` if it is valid.
+#[doc(hidden)]
+pub fn parse_record_field<T, F>(
+    key: &'static str,
+    raw: &str,
+    slot: &mut Option<T>,
+    errors: &mut Vec<FieldError>,
+    check: F,
+) where
+    T: FromStr,
+    T::Err: Display,
+    F: FnOnce(&str, &T) -> Result<(), String>,
+{
+    let parsed = raw
+        .parse()
+        .map_err(|err: T::Err| err.to_string())
+        .and_then(|value| check(raw, &value).map(|()| value));
+    match parsed {
+        Ok(value) => *slot = Some(value),
+        Err(reason) => errors.push(FieldError::Invalid {
+            key,
+            value: raw.to_owned(),
+            reason,
+        }),
+    }
+}
+
+/// The records in a text, one per paragraph.
+#[derive(Debug, Clone)]
+pub struct Records<'a, T> {
+    paragraphs: Paragraphs<'a>,
+    record: PhantomData<T>,
+}
+
+impl<'a, T: Record> Iterator for Records<'a, T> {
+    type Item = Result<T, RecordError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.paragraphs
+            .next()
+            .map(|paragraph| T::parse_record(&paragraph))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -344,4 +1116,285 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_parse_fmt() {
+        assert_eq!(
+            parse_fmt!("{} players; last marble is worth {} points", "9 players; last marble is worth 25 points" => (u32, u32)),
+            Ok((9, 25))
+        );
+        assert_eq!(
+            parse_fmt!("Step {} must be finished before step {} can begin.", "Step C must be finished before step A can begin." => (char, char)),
+            Ok(('C', 'A'))
+        );
+        assert_eq!(parse_fmt!("{{{}}}", "{-3}" => (i32)), Ok((-3,)));
+    }
+
+    #[test]
+    fn test_parse_fmt_errors() {
+        let err = parse_fmt!("#{id} @ {x},{y}", "#1 @ 1;3" => (u32, u32, u32)).unwrap_err();
+        assert_eq!(
+            err,
+            AdventError::Parse {
+                line: None,
+                column: Some(6),
+                text: "#1 @ 1;3".to_owned(),
+                message: "expected `,`".to_owned(),
+            }
+        );
+
+        let err = parse_fmt!("{} is {}", "x is 1" => (u8, u8)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parse error at column 1: field 1 is not a valid u8 (invalid digit found in string) \
+             in `x is 1`"
+        );
+
+        let err = parse_fmt!("{} is {}!", "1 is 2" => (u8, u8)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parse error at column 7: expected `!` in `1 is 2`"
+        );
+
+        assert!(parse_fmt!("{}{}", "12" => (u8, u8)).is_err());
+        assert!(parse_fmt!("{} {}", "1 2" => (u8)).is_err());
+    }
+
+    #[test]
+    fn test_ints() {
+        let xs: Vec<i32> = "<x=-1, y=0, z=+2>".ints().collect();
+        assert_eq!(xs, vec![-1, 0, 2]);
+        let xs: Vec<u32> = "class: 1-3 or 5-7".ints().collect();
+        assert_eq!(xs, vec![1, 3, 5, 7]);
+        let xs: Vec<i64> = "-5-3".ints().collect();
+        assert_eq!(xs, vec![-5, 3]);
+        let xs: Vec<u8> = "position=< 7, -3>".unsigned_ints().collect();
+        assert_eq!(xs, vec![7, 3]);
+        assert_eq!("no numbers".ints::<i32>().next(), None);
+    }
+
+    #[test]
+    fn test_try_ints() {
+        let xs: Result<Vec<i8>, _> = "<x=-1, y=0, z=+2>".try_ints().collect();
+        assert_eq!(xs, Ok(vec![-1, 0, 2]));
+        let mut xs = "1-300".try_unsigned_ints::<u8>();
+        assert_eq!(xs.next(), Some(Ok(1)));
+        assert!(xs.next().unwrap().is_err());
+        assert_eq!(xs.next(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "300 does not fit in a u8")]
+    fn test_ints_overflow() {
+        "1 300".ints::<u8>().for_each(drop);
+    }
+
+    #[test]
+    fn test_words() {
+        let words: Vec<_> = "Step C must be finished, before step A.".words().collect();
+        assert_eq!(
+            words,
+            vec!["Step", "C", "must", "be", "finished", "before", "step", "A"]
+        );
+        assert_eq!(" -- ".words().next(), None);
+    }
+
+    #[test]
+    fn test_comma_separated() {
+        let xs: Result<Vec<u32>, _> = "7, 1,14\n".comma_separated().collect();
+        assert_eq!(xs, Ok(vec![7, 1, 14]));
+        assert!("1,,2".comma_separated::<u32>().any(|x| x.is_err()));
+        assert_eq!("".comma_separated::<u32>().next(), None);
+    }
+
+    #[test]
+    fn test_split_sections() {
+        let text = "class: 1-3\n\nyour ticket:\n7,1\n\nnearby tickets:\n7,3\n40,4\n";
+        let sections: Vec<_> = text.split_sections(|line| line.ends_with(':')).collect();
+        assert_eq!(
+            sections,
+            vec![
+                Section {
+                    header: None,
+                    lines: vec!["class: 1-3"]
+                },
+                Section {
+                    header: Some("your ticket:"),
+                    lines: vec!["7,1"]
+                },
+                Section {
+                    header: Some("nearby tickets:"),
+                    lines: vec!["7,3", "40,4"]
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_numbered_lines() {
+        let lines: Vec<_> = "\n  a\n\nb  \n"
+            .numbered_lines()
+            .map(|l| (l.number, l.text))
+            .collect();
+        assert_eq!(lines, vec![(2, "a"), (4, "b")]);
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let xs: Result<Vec<u32>, _> = "1\n2\n".parse_lines().collect();
+        assert_eq!(xs.unwrap(), vec![1, 2]);
+
+        let err = "1\n2\nx\n"
+            .parse_lines::<u32>()
+            .nth(2)
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parse error at line 3: invalid digit found in string in `x`"
+        );
+    }
+
+    #[test]
+    fn test_parse_lines_column() {
+        #[derive(Debug)]
+        struct Pair(u32, u32);
+
+        impl FromStr for Pair {
+            type Err = AdventError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let (a, b) = parse_fmt!("{a},{b}", s => (u32, u32))?;
+                Ok(Self(a, b))
+            }
+        }
+
+        let err = "1,2\n  3,x"
+            .parse_lines::<Pair>()
+            .nth(1)
+            .unwrap()
+            .unwrap_err();
+        assert_eq!((err.line, err.column), (2, Some(5)));
+        assert_eq!(err.text, "  3,x");
+    }
+
+    #[derive(Debug, PartialEq, FieldValue)]
+    enum Unit {
+        #[record(value = "cm")]
+        Centimeters,
+        #[record(value = "in")]
+        Inches,
+    }
+
+    #[derive(Debug, PartialEq, Record)]
+    struct Measurement {
+        #[record(key = "n", range(1, 100))]
+        amount: u32,
+        unit: Unit,
+        #[record(len = 3)]
+        tag: Option<String>,
+    }
+
+    #[test]
+    fn test_records() {
+        let records: Vec<_> = "n:5 unit:cm\n\nunit:in tag:abc\nn:99"
+            .records::<Measurement>()
+            .collect();
+        assert_eq!(
+            records,
+            vec![
+                Ok(Measurement {
+                    amount: 5,
+                    unit: Unit::Centimeters,
+                    tag: None
+                }),
+                Ok(Measurement {
+                    amount: 99,
+                    unit: Unit::Inches,
+                    tag: Some("abc".to_owned())
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_record_errors() {
+        let err = Measurement::parse_record("n:0 unit:ft tag:abcd n:2 size").unwrap_err();
+        assert_eq!(
+            err.errors,
+            vec![
+                FieldError::Duplicate("n".to_owned()),
+                FieldError::Malformed("size".to_owned()),
+                FieldError::Invalid {
+                    key: "n",
+                    value: "0".to_owned(),
+                    reason: "not between 1 and 100".to_owned()
+                },
+                FieldError::Invalid {
+                    key: "unit",
+                    value: "ft".to_owned(),
+                    reason: "expected one of cm, in".to_owned()
+                },
+                FieldError::Invalid {
+                    key: "tag",
+                    value: "abcd".to_owned(),
+                    reason: "expected 3 characters".to_owned()
+                },
+            ]
+        );
+        assert!(!err.is_missing_fields());
+
+        let err = Measurement::parse_record("tag:abc").unwrap_err();
+        assert_eq!(
+            err.errors,
+            vec![FieldError::Missing("n"), FieldError::Missing("unit")]
+        );
+        assert!(err.is_missing_fields());
+    }
+
+    /// Fields named like the locals of the generated code.
+    #[derive(Debug, PartialEq, Record)]
+    struct Shadowing {
+        #[record(key = "k")]
+        key: String,
+        raw: u32,
+        fields: Option<u32>,
+        errors: Option<u32>,
+    }
+
+    #[test]
+    fn test_record_field_names() {
+        assert_eq!(
+            Shadowing::parse_record("k:a raw:1 errors:2"),
+            Ok(Shadowing {
+                key: "a".to_owned(),
+                raw: 1,
+                fields: None,
+                errors: Some(2),
+            })
+        );
+        assert_eq!(
+            Shadowing::parse_record("raw:1").unwrap_err().errors,
+            vec![FieldError::Missing("k")]
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_legacy_grid() {
+        let mut grid: Grid<usize> = Grid::new(3, 2);
+        let coords: Vec<Point> = grid.iter_coordinates().collect();
+        assert_eq!(coords.len(), 6);
+        assert_eq!(coords[5], (2, 1).into());
+        for (idx, p) in coords.into_iter().enumerate() {
+            grid[p] = idx;
+        }
+
+        let grid: VecGrid<usize, usize> = grid.into();
+        assert_eq!(grid[twodee::Point::new(2, 1)], 5);
+        assert_eq!(
+            twodee::Point::from(Point { x: 2, y: 1 }),
+            twodee::Point::new(2, 1)
+        );
+    }
 }
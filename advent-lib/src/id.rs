@@ -0,0 +1,68 @@
+use crate::error::AdventError;
+use std::{fmt, str::FromStr};
+
+/// Identifies one part of one day's puzzle, such as `2019-D15-P2`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PuzzleId {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+}
+
+impl PuzzleId {
+    #[must_use]
+    pub const fn new(year: u16, day: u8, part: u8) -> Self {
+        Self { year, day, part }
+    }
+}
+
+impl fmt::Display for PuzzleId {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}-D{:02}-P{}", self.year, self.day, self.part)
+    }
+}
+
+impl FromStr for PuzzleId {
+    type Err = AdventError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split('-').collect();
+        let (year, day, part) = match parts.as_slice() {
+            [year, day, part] => (year, day, part),
+            _ => return Err(AdventError::parse(s, "Expected a name like 2019-D15-P2")),
+        };
+
+        let year = year
+            .parse()
+            .map_err(|_| AdventError::parse(s, "Invalid year"))?;
+        let day = day
+            .strip_prefix('D')
+            .and_then(|day| day.parse().ok())
+            .ok_or_else(|| AdventError::parse(s, "Invalid day"))?;
+        let part = part
+            .strip_prefix('P')
+            .and_then(|part| part.parse().ok())
+            .ok_or_else(|| AdventError::parse(s, "Invalid part"))?;
+
+        Ok(Self { year, day, part })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let id: PuzzleId = "2019-D05-P2".parse().expect("Should parse");
+        assert_eq!(id, PuzzleId::new(2019, 5, 2));
+        assert_eq!(id.to_string(), "2019-D05-P2");
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!("2019-D05".parse::<PuzzleId>().is_err());
+        assert!("2019-05-P1".parse::<PuzzleId>().is_err());
+        assert!("twenty-D05-P1".parse::<PuzzleId>().is_err());
+    }
+}
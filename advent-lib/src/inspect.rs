@@ -0,0 +1,288 @@
+//! A summary of a puzzle input's shape, for writing a parser for it, or for
+//! working out why a solution that works on one input fails on another.
+
+use crate::helpers::StringAdventExt;
+use std::{collections::BTreeMap, fmt};
+
+/// The shape of an input. Lines are split on `\n` only, so that `\r`s show
+/// up as an issue rather than being quietly removed.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InputReport {
+    pub lines: usize,
+    /// Lines that are empty or only whitespace.
+    pub blank_lines: usize,
+    /// How many lines have each length, in characters. Blank lines aren't
+    /// counted.
+    pub line_lengths: BTreeMap<usize, usize>,
+    /// How many times each character appears, other than line endings.
+    pub chars: BTreeMap<char, usize>,
+    pub numbers: Option<NumberRange>,
+    /// The number of lines in each run of non-blank lines.
+    pub sections: Vec<usize>,
+    /// The 1-based numbers of lines that end in whitespace.
+    pub trailing_whitespace: Vec<usize>,
+    /// Lines that end in `\r\n`.
+    pub crlf_lines: usize,
+    pub final_newline: bool,
+    /// Blank lines after the last non-blank one.
+    pub trailing_blank_lines: usize,
+}
+
+/// The integers in an input, as [`StringAdventExt::ints`] would find them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NumberRange {
+    pub count: usize,
+    pub min: i128,
+    pub max: i128,
+}
+
+impl InputReport {
+    #[must_use]
+    pub fn new(input: &str) -> Self {
+        let mut report = Self {
+            final_newline: input.ends_with('\n'),
+            ..Self::default()
+        };
+
+        let mut lines: Vec<&str> = input.split('\n').collect();
+        if report.final_newline || input.is_empty() {
+            // The text after the last newline isn't a line
+            lines.pop();
+        }
+        report.lines = lines.len();
+
+        let mut section = 0;
+        for (idx, line) in lines.iter().enumerate() {
+            let line = match line.strip_suffix('\r') {
+                Some(line) => {
+                    report.crlf_lines += 1;
+                    line
+                }
+                None => line,
+            };
+            if line.len() != line.trim_end().len() {
+                report.trailing_whitespace.push(idx + 1);
+            }
+            for c in line.chars() {
+                *report.chars.entry(c).or_default() += 1;
+            }
+
+            if line.trim().is_empty() {
+                report.blank_lines += 1;
+                report.trailing_blank_lines += 1;
+                if section > 0 {
+                    report.sections.push(section);
+                    section = 0;
+                }
+            } else {
+                report.trailing_blank_lines = 0;
+                *report.line_lengths.entry(line.chars().count()).or_default() += 1;
+                section += 1;
+            }
+        }
+        if section > 0 {
+            report.sections.push(section);
+        }
+
+        // Numbers too big for an i128 aren't worth reporting a range for
+        let numbers: Vec<i128> = input.try_ints().filter_map(Result::ok).collect();
+        if let (Some(min), Some(max)) = (numbers.iter().min(), numbers.iter().max()) {
+            report.numbers = Some(NumberRange {
+                count: numbers.len(),
+                min: *min,
+                max: *max,
+            });
+        }
+
+        report
+    }
+
+    /// Anything about the input that commonly trips up parsers.
+    #[must_use]
+    pub fn issues(&self) -> Vec<String> {
+        let mut issues = vec![];
+        if self.lines == 0 {
+            issues.push("The input is empty".to_owned());
+            return issues;
+        }
+        if !self.trailing_whitespace.is_empty() {
+            issues.push(format!(
+                "Whitespace at the end of {}",
+                line_list(&self.trailing_whitespace)
+            ));
+        }
+        if self.crlf_lines > 0 {
+            issues.push(format!(
+                "{} {} with Windows line endings (\\r\\n)",
+                self.crlf_lines,
+                plural(self.crlf_lines, "line", "lines")
+            ));
+        }
+        if !self.final_newline {
+            issues.push("No newline at the end of the input".to_owned());
+        }
+        if self.trailing_blank_lines > 0 {
+            issues.push(format!(
+                "{} blank {} at the end of the input",
+                self.trailing_blank_lines,
+                plural(self.trailing_blank_lines, "line", "lines")
+            ));
+        }
+        issues
+    }
+}
+
+impl fmt::Display for InputReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Lines:        {} ({} blank)",
+            self.lines, self.blank_lines
+        )?;
+
+        write!(f, "Line lengths: ")?;
+        let lengths: Vec<(usize, usize)> =
+            self.line_lengths.iter().map(|(l, c)| (*l, *c)).collect();
+        match lengths.as_slice() {
+            [] => writeln!(f, "none")?,
+            [(length, _)] => writeln!(f, "all {}", length)?,
+            lengths if lengths.len() <= 6 => {
+                let counts: Vec<String> = lengths
+                    .iter()
+                    .map(|(length, count)| format!("{} ×{}", length, count))
+                    .collect();
+                writeln!(f, "{}", counts.join(", "))?;
+            }
+            lengths => {
+                let (min, _) = lengths[0];
+                let (max, _) = lengths[lengths.len() - 1];
+                writeln!(
+                    f,
+                    "{} to {}, in {} different lengths",
+                    min,
+                    max,
+                    lengths.len()
+                )?;
+            }
+        }
+
+        let chars: Vec<String> = self
+            .chars
+            .iter()
+            .map(|(c, count)| format!("{:?} ×{}", c, count))
+            .collect();
+        writeln!(f, "Characters:   {}", chars.join(", "))?;
+
+        match self.numbers {
+            Some(NumberRange { count, min, max }) => {
+                writeln!(f, "Numbers:      {}, from {} to {}", count, min, max)?;
+            }
+            None => writeln!(f, "Numbers:      none")?,
+        }
+
+        write!(f, "Sections:     {}", self.sections.len())?;
+        if self.sections.len() > 1 {
+            let sizes: Vec<String> = self.sections.iter().map(ToString::to_string).collect();
+            if sizes.len() <= 6 {
+                write!(f, ", of {} lines", sizes.join(", "))?;
+            } else {
+                let min = self.sections.iter().min().unwrap_or(&0);
+                let max = self.sections.iter().max().unwrap_or(&0);
+                write!(f, ", of {} to {} lines", min, max)?;
+            }
+        }
+        writeln!(f)?;
+
+        for issue in self.issues() {
+            writeln!(f, "Warning:      {}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+/// Describe some line numbers, listing only the first few.
+fn line_list(lines: &[usize]) -> String {
+    const SHOWN: usize = 5;
+    let shown: Vec<String> = lines.iter().take(SHOWN).map(ToString::to_string).collect();
+    let more = if lines.len() > SHOWN {
+        format!(" and {} more", lines.len() - SHOWN)
+    } else {
+        String::new()
+    };
+    format!(
+        "{} {}{}",
+        plural(lines.len(), "line", "lines"),
+        shown.join(", "),
+        more
+    )
+}
+
+const fn plural<'a>(count: usize, one: &'a str, many: &'a str) -> &'a str {
+    if count == 1 {
+        one
+    } else {
+        many
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report() {
+        let report = InputReport::new("a: 1, -2\nbb: 30\n\ncc 4 \n");
+        assert_eq!(report.lines, 4);
+        assert_eq!(report.blank_lines, 1);
+        assert_eq!(
+            report.line_lengths,
+            vec![(5, 1), (6, 1), (8, 1)].into_iter().collect()
+        );
+        assert_eq!(report.chars[&'c'], 2);
+        assert_eq!(
+            report.numbers,
+            Some(NumberRange {
+                count: 4,
+                min: -2,
+                max: 30
+            })
+        );
+        assert_eq!(report.sections, vec![2, 1]);
+        assert_eq!(report.trailing_whitespace, vec![4]);
+        assert_eq!(
+            report.issues(),
+            vec!["Whitespace at the end of line 4".to_owned()]
+        );
+    }
+
+    #[test]
+    fn test_line_endings() {
+        let report = InputReport::new("abc\r\ndef\r\n\n");
+        assert_eq!(report.lines, 3);
+        assert_eq!(report.crlf_lines, 2);
+        assert_eq!(report.trailing_whitespace, vec![]);
+        assert_eq!(report.trailing_blank_lines, 1);
+        assert_eq!(
+            report.issues(),
+            vec![
+                "2 lines with Windows line endings (\\r\\n)".to_owned(),
+                "1 blank line at the end of the input".to_owned(),
+            ]
+        );
+
+        let report = InputReport::new("abc");
+        assert_eq!(report.lines, 1);
+        assert_eq!(
+            report.issues(),
+            vec!["No newline at the end of the input".to_owned()]
+        );
+    }
+
+    #[test]
+    fn test_empty() {
+        let report = InputReport::new("");
+        assert_eq!(report.lines, 0);
+        assert_eq!(report.numbers, None);
+        assert_eq!(report.issues(), vec!["The input is empty".to_owned()]);
+    }
+}
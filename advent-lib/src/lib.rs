@@ -11,34 +11,42 @@
 
     clippy::use_self, // doesn't work well with generics
 )]
-#![feature(
-    associated_type_defaults,
-    step_trait,
-    try_trait,
-    min_const_generics,
-    once_cell
-)]
+
+// Lets the code that `advent_derive` generates name this crate from inside it
+extern crate self as advent_lib;
 
 use std::cmp;
 
+pub mod answer;
+pub mod bounds;
 pub mod cases;
+pub mod context;
+pub mod diff;
+pub mod error;
+pub mod expr;
 pub mod grid_letters;
 pub mod helpers;
+pub mod id;
+pub mod inspect;
+pub mod plugin;
+pub mod property;
 pub mod twodee;
 
-/// Find the lowest and highest value in `items`.
+/// Find the lowest and highest value in `items`. This is the one dimensional
+/// version of collecting points into a [`bounds::Bounds`], except that the
+/// highest value is included.
 pub fn extremes<T>(items: T) -> Option<(T::Item, T::Item)>
 where
     T: IntoIterator,
-    T::Item: Ord + Copy,
+    T::Item: Ord + Clone,
 {
     let mut items = items.into_iter();
     if let Some(first) = items.next() {
-        let mut min = first;
+        let mut min = first.clone();
         let mut max = first;
 
         for item in items {
-            min = cmp::min(min, item);
+            min = cmp::min(min, item.clone());
             max = cmp::max(max, item);
         }
 
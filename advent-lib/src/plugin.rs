@@ -0,0 +1,144 @@
+//! Registering puzzles from a year crate built as a dynamic library, so a
+//! runner can load it at startup instead of being compiled with it.
+//!
+//! A year crate exports its puzzles with [`export_plugin!`], which defines two
+//! C ABI functions. The runner first calls [`BUILD_ID_SYMBOL`], and only if the
+//! plugin's [`BUILD_ID`] matches its own does it call [`REGISTER_SYMBOL`] with
+//! a [`Registrar`] to add the puzzles to.
+//!
+//! This is not a stable C ABI. The two functions are `extern "C"`, but the
+//! [`Registrar`] they are handed and the `Box<dyn Puzzle>`s registered with it
+//! are Rust types, whose layout is unspecified, and errors from puzzles are
+//! told apart by their `TypeId`. So a plugin only works with a runner from the
+//! same build: the same toolchain, target, profile, and features, and the same
+//! source of this crate, which is what the build id checks. It can't check that
+//! both sides' dependencies are the same, so build plugins in the runner's
+//! workspace, as the runner's `plugins` module describes.
+
+use crate::cases::Puzzle;
+use std::os::raw::c_char;
+
+/// What this crate was built with. A plugin can only be loaded by a runner
+/// with the same build id.
+///
+/// It has the version of rustc, the target, the profile, and the version, a
+/// hash of the source, and the features of this crate.
+pub const BUILD_ID: &str = env!("ADVENT_LIB_BUILD_ID");
+
+/// [`BUILD_ID`], null terminated, for [`export_plugin!`] to hand to the runner.
+#[doc(hidden)]
+pub const BUILD_ID_NUL: &str = concat!(env!("ADVENT_LIB_BUILD_ID"), "\0");
+
+/// The name of the function that returns a plugin's build id, null terminated
+/// for looking it up in a dynamic library.
+pub const BUILD_ID_SYMBOL: &[u8] = b"advent_plugin_build_id\0";
+
+/// The signature of the build id function. It returns the plugin's
+/// [`BUILD_ID`], as a null terminated string.
+pub type BuildIdFn = extern "C" fn() -> *const c_char;
+
+/// The name of the function that [`export_plugin!`] defines to register the
+/// puzzles, null terminated for looking it up in a dynamic library.
+pub const REGISTER_SYMBOL: &[u8] = b"advent_plugin_register\0";
+
+/// The signature of the registration function. It must only be called if the
+/// plugin's build id matches the runner's.
+pub type RegisterFn = unsafe extern "C" fn(registrar: *mut Registrar);
+
+/// Collects the puzzles that a plugin registers.
+#[derive(Debug, Default)]
+pub struct Registrar {
+    puzzles: Vec<Box<dyn Puzzle>>,
+}
+
+impl Registrar {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register<I: IntoIterator<Item = Box<dyn Puzzle>>>(&mut self, puzzles: I) {
+        self.puzzles.extend(puzzles);
+    }
+
+    #[must_use]
+    pub fn into_puzzles(self) -> Vec<Box<dyn Puzzle>> {
+        self.puzzles
+    }
+}
+
+/// Export the puzzles returned by a function as a plugin. Year crates use this
+/// behind their `plugin` feature, so that the symbol isn't defined twice when
+/// several years are compiled into one runner.
+///
+/// ```ignore
+/// #[cfg(feature = "plugin")]
+/// advent_lib::export_plugin!(get_puzzles);
+/// ```
+#[macro_export]
+macro_rules! export_plugin {
+    ($get_puzzles: path) => {
+        /// The build id of the `advent-lib` this crate was built with.
+        #[no_mangle]
+        pub extern "C" fn advent_plugin_build_id() -> *const ::std::os::raw::c_char {
+            $crate::plugin::BUILD_ID_NUL.as_ptr().cast()
+        }
+
+        /// Register this crate's puzzles with a runner.
+        ///
+        /// # Safety
+        ///
+        /// `registrar` must point to a valid `Registrar`, from a runner with
+        /// the same build id as this crate.
+        #[no_mangle]
+        pub unsafe extern "C" fn advent_plugin_register(registrar: *mut $crate::plugin::Registrar) {
+            (*registrar).register($get_puzzles());
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cases::PuzzleCase;
+    use std::ffi::CStr;
+
+    #[derive(Debug)]
+    struct Empty;
+
+    impl Puzzle for Empty {
+        fn name(&self) -> String {
+            "2015-D01-P1".to_owned()
+        }
+
+        fn cases(&self) -> Result<Vec<Box<dyn PuzzleCase>>, Box<dyn std::error::Error>> {
+            Ok(vec![])
+        }
+    }
+
+    fn get_puzzles() -> Vec<Box<dyn Puzzle>> {
+        vec![Box::new(Empty)]
+    }
+
+    export_plugin!(get_puzzles);
+
+    #[test]
+    fn test_build_id() {
+        let build_id: BuildIdFn = advent_plugin_build_id;
+        let build_id = unsafe { CStr::from_ptr(build_id()) };
+        assert_eq!(build_id.to_str().unwrap(), BUILD_ID);
+        assert!(BUILD_ID.contains("rustc "), "{}", BUILD_ID);
+        assert!(BUILD_ID.contains(env!("CARGO_PKG_VERSION")), "{}", BUILD_ID);
+        assert!(BUILD_ID.contains("opt-level "), "{}", BUILD_ID);
+    }
+
+    #[test]
+    fn test_register() {
+        let register: RegisterFn = advent_plugin_register;
+        let mut registrar = Registrar::new();
+        unsafe { register(&mut registrar) };
+        let puzzles = registrar.into_puzzles();
+        assert_eq!(puzzles.len(), 1);
+        assert_eq!(puzzles[0].name(), "2015-D01-P1");
+    }
+}
@@ -0,0 +1,424 @@
+use crate::{
+    answer::Answer,
+    cases::{PuzzleCase, PuzzleResult, PuzzleResultStatus, PuzzleRunner},
+    context::RunContext,
+};
+use std::{
+    fmt,
+    marker::PhantomData,
+    ops::{Add, Div, RangeInclusive, Sub},
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+/// A small, seedable random number generator (xorshift64*), so that a failing
+/// property check can be reproduced from its seed.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    #[must_use]
+    pub const fn new(seed: u64) -> Self {
+        // xorshift gets stuck on zero
+        Self(if seed == 0 {
+            0x9E37_79B9_7F4A_7C15
+        } else {
+            seed
+        })
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// A number in `range`, inclusive of both ends.
+    ///
+    /// # Panics
+    ///
+    /// If the range is empty.
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "Empty range {}..={}", start, end);
+        match (end.wrapping_sub(start) as u64).checked_add(1) {
+            Some(width) => start.wrapping_add((self.next_u64() % width) as i64),
+            // The range covers every i64
+            None => self.next_u64() as i64,
+        }
+    }
+
+    /// A number in `0..n`.
+    ///
+    /// # Panics
+    ///
+    /// If `n` is zero.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "Can't choose from an empty range");
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// A random item from `items`.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+}
+
+/// Smaller versions of an integer, moving towards zero.
+pub fn shrink_int<T>(n: T) -> Vec<T>
+where
+    T: Copy + PartialOrd + From<u8> + Add<Output = T> + Sub<Output = T> + Div<Output = T>,
+{
+    let zero = T::from(0);
+    let one = T::from(1);
+    if n == zero {
+        return vec![];
+    }
+
+    let mut candidates = vec![zero, n / T::from(2)];
+    candidates.push(if n > zero { n - one } else { n + one });
+    candidates.dedup();
+    candidates.retain(|c| *c != n);
+    candidates
+}
+
+/// Smaller versions of a list: first with each item removed, then with each
+/// item replaced by a smaller version of itself.
+pub fn shrink_vec<T, F>(items: &[T], shrink_item: F) -> Vec<Vec<T>>
+where
+    T: Clone,
+    F: Fn(&T) -> Vec<T>,
+{
+    let mut candidates = vec![];
+    for idx in 0..items.len() {
+        let mut smaller = items.to_vec();
+        smaller.remove(idx);
+        candidates.push(smaller);
+    }
+    for (idx, item) in items.iter().enumerate() {
+        for replacement in shrink_item(item) {
+            let mut smaller = items.to_vec();
+            smaller[idx] = replacement;
+            candidates.push(smaller);
+        }
+    }
+    candidates
+}
+
+/// The seed for property cases that aren't given one, by the case or by the
+/// context they run in, so that runs are repeatable.
+pub const DEFAULT_SEED: u64 = 0x2015_1201;
+
+/// The environment variable that runners read a seed from, to pass to
+/// property cases with [`RunContext::with_seed`].
+pub const SEED_VAR: &str = "ADVENT_SEED";
+
+/// Parse a seed given by the user, which can be a number, or `random` for a
+/// new seed each run.
+///
+/// # Errors
+///
+/// If the seed is neither.
+pub fn parse_seed(seed: &str) -> Result<u64, String> {
+    if seed == "random" {
+        Ok(SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_nanos() as u64))
+    } else {
+        seed.parse()
+            .map_err(|_| format!("The seed should be a number or `random`, not `{}`", seed))
+    }
+}
+
+type Generator<I> = Box<dyn Fn(&mut Rng) -> I + Send + Sync>;
+type Shrinker<I> = Box<dyn Fn(&I) -> Vec<I> + Send + Sync>;
+type Oracle<I, O> = Box<dyn Fn(&I) -> Option<O> + Send + Sync>;
+
+/// A case that runs a puzzle on randomly generated inputs, and compares its
+/// output to a simpler reference solution (the oracle).
+///
+/// The oracle can return `None` to skip inputs it can't answer, for example
+/// because brute force would take too long. When the puzzle and oracle
+/// disagree, the input is shrunk to the smallest version that still disagrees
+/// before being reported.
+pub struct PropertyCase<T: PuzzleRunner> {
+    name: String,
+    runs: usize,
+    seed: Option<u64>,
+    generate: Generator<T::Input>,
+    shrink: Shrinker<T::Input>,
+    oracle: Oracle<T::Input, T::Output>,
+    phantom: PhantomData<T>,
+}
+
+impl<T> PropertyCase<T>
+where
+    T: PuzzleRunner,
+    T::Input: Clone + fmt::Debug,
+    T::Output: Answer + PartialEq,
+    T::Error: Into<Box<dyn std::error::Error>>,
+{
+    pub fn new<S, G, F>(name: S, generate: G, oracle: F) -> Self
+    where
+        S: Into<String>,
+        G: Fn(&mut Rng) -> T::Input + Send + Sync + 'static,
+        F: Fn(&T::Input) -> Option<T::Output> + Send + Sync + 'static,
+    {
+        Self {
+            name: name.into(),
+            runs: 100,
+            seed: None,
+            generate: Box::new(generate),
+            shrink: Box::new(|_| vec![]),
+            oracle: Box::new(oracle),
+            phantom: PhantomData,
+        }
+    }
+
+    /// How to make smaller versions of a failing input.
+    #[must_use]
+    pub fn with_shrink<F>(mut self, shrink: F) -> Self
+    where
+        F: Fn(&T::Input) -> Vec<T::Input> + Send + Sync + 'static,
+    {
+        self.shrink = Box::new(shrink);
+        self
+    }
+
+    /// How many inputs to generate. Defaults to 100.
+    #[must_use]
+    pub const fn with_runs(mut self, runs: usize) -> Self {
+        self.runs = runs;
+        self
+    }
+
+    /// Use a fixed seed, such as one from a failure report, instead of the one
+    /// from the [`RunContext`], or [`DEFAULT_SEED`].
+    #[must_use]
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Describe how the puzzle's output for `input` differs from `expected`,
+    /// or `None` if it doesn't.
+    fn compare(&self, input: &T::Input, expected: &T::Output, ctx: &RunContext) -> Option<String> {
+        match T::try_run_puzzle_with_context(input.clone(), ctx) {
+            Ok(actual) if actual == *expected => None,
+            Ok(actual) => Some(format!(
+                "expected {} got {}",
+                expected.submission(),
+                actual.submission()
+            )),
+            Err(err) => Some(format!(
+                "expected {} got error: {}",
+                expected.submission(),
+                err.into()
+            )),
+        }
+    }
+
+    /// Find the smallest version of `input` that still disagrees.
+    fn shrink(
+        &self,
+        mut input: T::Input,
+        mut problem: String,
+        ctx: &RunContext,
+    ) -> (T::Input, String) {
+        'shrinking: loop {
+            for candidate in (self.shrink)(&input) {
+                if ctx.is_cancelled() {
+                    break 'shrinking;
+                }
+                let candidate_problem = (self.oracle)(&candidate)
+                    .and_then(|expected| self.compare(&candidate, &expected, ctx));
+                if let Some(candidate_problem) = candidate_problem {
+                    input = candidate;
+                    problem = candidate_problem;
+                    continue 'shrinking;
+                }
+            }
+            break;
+        }
+        (input, problem)
+    }
+}
+
+impl<T> PuzzleCase for PropertyCase<T>
+where
+    T: PuzzleRunner,
+    T::Input: Clone + fmt::Debug,
+    T::Output: Answer + PartialEq,
+    T::Error: Into<Box<dyn std::error::Error>>,
+{
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn run(&self) -> PuzzleResult {
+        self.run_with_context(&RunContext::new())
+    }
+
+    fn run_with_context(&self, ctx: &RunContext) -> PuzzleResult {
+        let start = Instant::now();
+        let seed = self.seed.or_else(|| ctx.seed()).unwrap_or(DEFAULT_SEED);
+        let mut rng = Rng::new(seed);
+        let mut checked = 0;
+
+        for run in 0..self.runs {
+            ctx.progress(run, self.runs);
+            if let Err(err) = ctx.check() {
+                return PuzzleResult {
+                    status: PuzzleResultStatus::Cancelled,
+                    description: err.to_string(),
+                    duration: start.elapsed(),
+                    answer: None,
+                    mismatch: None,
+                    location: None,
+                    prepare: None,
+                };
+            }
+
+            let input = (self.generate)(&mut rng);
+            let expected = match (self.oracle)(&input) {
+                Some(expected) => expected,
+                None => continue,
+            };
+            checked += 1;
+
+            if let Some(problem) = self.compare(&input, &expected, ctx) {
+                let (input, problem) = self.shrink(input, problem, ctx);
+                return PuzzleResult {
+                    status: PuzzleResultStatus::Fail,
+                    description: format!("seed {}: for {:?} {}", seed, input, problem),
+                    duration: start.elapsed(),
+                    answer: None,
+                    mismatch: None,
+                    location: None,
+                    prepare: None,
+                };
+            }
+        }
+
+        PuzzleResult {
+            status: PuzzleResultStatus::Match,
+            description: format!("{} generated inputs match the oracle", checked),
+            duration: start.elapsed(),
+            answer: None,
+            mismatch: None,
+            location: None,
+            prepare: None,
+        }
+    }
+}
+
+impl<T: PuzzleRunner> fmt::Debug for PropertyCase<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("PropertyCase")
+            .field("name", &self.name)
+            .field("runs", &self.runs)
+            .field("seed", &self.seed)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cases::{DefaultError, PuzzleCase};
+
+    #[test]
+    fn test_rng_range() {
+        let mut rng = Rng::new(42);
+        for _ in 0..1000 {
+            let n = rng.range(-3..=3);
+            assert!((-3..=3).contains(&n));
+        }
+        assert_eq!(Rng::new(7).next_u64(), Rng::new(7).next_u64());
+    }
+
+    #[test]
+    fn test_rng_full_range() {
+        let mut rng = Rng::new(42);
+        let mut expected = Rng::new(42);
+        for _ in 0..100 {
+            assert_eq!(rng.range(i64::MIN..=i64::MAX), expected.next_u64() as i64);
+        }
+        assert_eq!(Rng::new(42).range(i64::MAX..=i64::MAX), i64::MAX);
+    }
+
+    #[test]
+    fn test_parse_seed() {
+        assert_eq!(parse_seed("1234"), Ok(1234));
+        assert!(parse_seed("random").is_ok());
+        assert_eq!(
+            parse_seed("lucky"),
+            Err("The seed should be a number or `random`, not `lucky`".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_shrink_int() {
+        assert_eq!(shrink_int(10_i32), vec![0, 5, 9]);
+        assert_eq!(shrink_int(-1_i64), vec![0]);
+        assert_eq!(shrink_int(0_u64), vec![]);
+    }
+
+    #[derive(Debug)]
+    struct BrokenSum;
+
+    impl PuzzleRunner for BrokenSum {
+        type Input = Vec<u32>;
+        type Output = u32;
+        type Error = DefaultError;
+
+        fn name(&self) -> String {
+            "Broken sum".to_owned()
+        }
+
+        fn cases(&self) -> Result<Vec<Box<dyn PuzzleCase>>, Box<dyn std::error::Error>> {
+            Ok(vec![])
+        }
+
+        fn run_puzzle(input: Self::Input) -> Self::Output {
+            // Forgets about anything over 100
+            input.into_iter().filter(|n| *n < 100).sum()
+        }
+    }
+
+    #[test]
+    fn test_shrinks_counterexample() {
+        let case = PropertyCase::<BrokenSum>::new(
+            "Random",
+            |rng| (0..5).map(|_| rng.range(0..=200) as u32).collect(),
+            |input| Some(input.iter().sum()),
+        )
+        .with_shrink(|input| shrink_vec(input, |n| shrink_int(*n)))
+        .with_seed(1);
+
+        let result = case.run();
+        assert_eq!(result.status, PuzzleResultStatus::Fail);
+        assert_eq!(result.description, "seed 1: for [100] expected 100 got 0");
+    }
+
+    #[test]
+    fn test_default_seed() {
+        let case = PropertyCase::<BrokenSum>::new(
+            "Random",
+            |rng| vec![rng.range(0..=200) as u32],
+            |input| Some(input.iter().sum()),
+        );
+        let result = case.run();
+        assert!(result
+            .description
+            .starts_with(&format!("seed {}:", DEFAULT_SEED)));
+        assert_eq!(case.run().description, result.description);
+
+        let result = case.run_with_context(&RunContext::new().with_seed(7));
+        assert!(result.description.starts_with("seed 7:"));
+        let result = case
+            .with_seed(1)
+            .run_with_context(&RunContext::new().with_seed(7));
+        assert!(result.description.starts_with("seed 1:"));
+    }
+}
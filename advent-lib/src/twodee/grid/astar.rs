@@ -3,19 +3,18 @@ use crate::twodee::{
     Point,
     PointAxe,
 };
-use num_traits::{bounds::Bounded, sign::Signed};
+use num_traits::{bounds::Bounded, cast::AsPrimitive, sign::Signed};
 use std::{
     collections::{HashMap, HashSet},
     fmt,
     hash::Hash,
-    iter,
 };
 
 impl<'a, C, I> HashGrid<C, I>
 where
     Self: 'a + Grid<C, I>,
     C: Default + fmt::Debug + Hash + Eq + Copy + fmt::Display,
-    I: PointAxe + Hash + Ord + Signed + Bounded + fmt::Display + iter::Step + Into<isize>,
+    I: PointAxe + Hash + Ord + Signed + Bounded + fmt::Display + AsPrimitive<isize>,
 {
     pub fn astar(
         &self,
@@ -1,15 +1,20 @@
-use crate::twodee::{
-    grid::{patch::PatchedGrid, Grid},
-    Bounds,
-    Point,
-    PointAxe,
+use crate::{
+    error::AdventError,
+    helpers::NumberedLine,
+    twodee::{
+        grid::{coordinate, patch::PatchedGrid, Grid},
+        Bounds,
+        Point,
+        PointAxe,
+    },
 };
+use num_traits::AsPrimitive;
 use std::{
     cmp,
     collections::{hash_map::Entry, HashMap},
+    convert::TryFrom,
     fmt,
     hash::Hash,
-    iter::Step,
 };
 
 #[derive(Clone, Debug)]
@@ -23,7 +28,7 @@ where
 
 impl<C, I> Grid<C, I> for HashGrid<C, I>
 where
-    I: PointAxe + Hash + fmt::Display + Step + Into<isize> + cmp::Ord,
+    I: PointAxe + Hash + fmt::Display + AsPrimitive<isize> + cmp::Ord,
     C: Copy + fmt::Display,
 {
     fn bounds(&self) -> Bounds<I> {
@@ -42,7 +47,7 @@ where
 
 impl<C, I> HashGrid<C, I>
 where
-    I: PointAxe + Hash + fmt::Display + Step + Into<isize> + cmp::Ord,
+    I: PointAxe + Hash + fmt::Display + AsPrimitive<isize> + cmp::Ord,
     C: Copy + fmt::Debug + fmt::Display,
 {
     pub fn entry(&mut self, p: Point<I>) -> Entry<Point<I>, C> {
@@ -58,6 +63,51 @@ where
     }
 }
 
+impl<C, I> HashGrid<C, I>
+where
+    I: PointAxe + Hash + Eq + TryFrom<usize>,
+    C: TryFrom<char>,
+    C::Error: fmt::Display,
+{
+    /// Parse a grid where only some characters are cells, like the `#`s of a
+    /// map. The first character of the first line is at `(0, 0)`, and every
+    /// other character is at its column and line in the input, counting
+    /// whitespace and blank lines. Whitespace and characters that `is_present`
+    /// rejects are left out, and rows may be different widths.
+    ///
+    /// # Errors
+    ///
+    /// If a present character isn't a valid `C`. The error has the line and
+    /// column of the character.
+    pub fn parse_sparse<F>(input: &str, mut is_present: F) -> Result<Self, AdventError>
+    where
+        F: FnMut(char) -> bool,
+    {
+        let mut cells = HashMap::new();
+        for (y, raw) in input.lines().enumerate() {
+            let line = NumberedLine {
+                number: y + 1,
+                text: raw.trim(),
+                raw,
+            };
+            let indent = line.indent();
+            for (x, c) in line.text.chars().enumerate() {
+                if !is_present(c) {
+                    continue;
+                }
+                let cell = C::try_from(c)
+                    .map_err(|err| line.error(x + 1, format!("Unknown cell `{}`: {}", c, err)))?;
+                let point = Point::new(
+                    coordinate(&line, x + 1, indent + x)?,
+                    coordinate(&line, x + 1, y)?,
+                );
+                cells.insert(point, cell);
+            }
+        }
+        Ok(Self { cells })
+    }
+}
+
 impl<C> Default for HashGrid<C>
 where
     C: Default + fmt::Debug + Hash + fmt::Display,
@@ -68,3 +118,58 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sparse() {
+        let grid: HashGrid<char, i32> = HashGrid::parse_sparse(".#.\n#\n", |c| c == '#').unwrap();
+        let mut points: Vec<_> = grid.cells.keys().copied().collect();
+        points.sort_by_key(|p| (p.y, p.x));
+        assert_eq!(points, vec![Point::new(1, 0), Point::new(0, 1)]);
+    }
+
+    #[test]
+    fn test_parse_sparse_positions() {
+        let grid: HashGrid<char, i32> = HashGrid::parse_sparse(
+            "  #
+#..
+
+ #",
+            |c| c == '#',
+        )
+        .unwrap();
+        let mut points: Vec<_> = grid.cells.keys().copied().collect();
+        points.sort_by_key(|p| (p.y, p.x));
+        assert_eq!(
+            points,
+            vec![Point::new(2, 0), Point::new(0, 1), Point::new(1, 3)]
+        );
+    }
+
+    #[test]
+    fn test_parse_sparse_unknown() {
+        #[derive(Clone, Copy, Debug)]
+        struct Rock;
+
+        impl TryFrom<char> for Rock {
+            type Error = &'static str;
+
+            fn try_from(c: char) -> Result<Self, Self::Error> {
+                if c == '#' {
+                    Ok(Self)
+                } else {
+                    Err("expected `#`")
+                }
+            }
+        }
+
+        let err = HashGrid::<Rock, i32>::parse_sparse("#.\n.x", |c| c != '.').unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parse error at line 2, column 2: Unknown cell `x`: expected `#` in `.x`"
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use super::{Bounds, Point, PointAxe};
+use num_traits::AsPrimitive;
 use std::{
     fmt::{self, Debug},
-    iter::{self, Step},
     marker::PhantomData,
     ops::AddAssign,
 };
@@ -15,10 +15,22 @@ use fmt::Display;
 pub use hash::HashGrid;
 pub use vec::VecGrid;
 
+use crate::{error::AdventError, helpers::NumberedLine};
+use std::convert::TryFrom;
+
+/// Convert a position in a parsed grid to a coordinate, for grids with small
+/// coordinate types.
+fn coordinate<I: TryFrom<usize>>(
+    line: &NumberedLine,
+    column: usize,
+    n: usize,
+) -> Result<I, AdventError> {
+    I::try_from(n).map_err(|_| line.error(column, "The grid is too large for its coordinates"))
+}
+
 pub trait Grid<C, I>
 where
-    I: PointAxe + fmt::Display + Step + Into<isize>,
-    C: fmt::Display,
+    I: PointAxe + fmt::Display + AsPrimitive<isize>,
 {
     fn bounds(&self) -> Bounds<I>;
     fn get(&self, p: Point<I>) -> Option<&C>;
@@ -26,31 +38,31 @@ where
 
     /// # Errors
     /// Will return an error if any of called formatters do.
-    fn display(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    fn display(&self, f: &mut fmt::Formatter) -> fmt::Result
+    where
+        C: fmt::Display,
+    {
         let bounds = self.bounds();
+        let last = match bounds.max_inclusive() {
+            Some(last) => last,
+            None => return Ok(()),
+        };
 
         writeln!(f, "{}", bounds.top_left())?;
 
-        for y in (bounds.top)..=(bounds.bottom) {
-            for x in (bounds.left)..=(bounds.right) {
-                if let Some(cell) = self.get(Point::new(x, y)) {
-                    write!(f, "{}", cell)?;
-                } else {
-                    write!(f, " ")?;
-                }
+        for p in bounds.iter_points() {
+            if let Some(cell) = self.get(p) {
+                write!(f, "{}", cell)?;
+            } else {
+                write!(f, " ")?;
+            }
+            if p.x == last.x {
+                writeln!(f)?;
             }
-            writeln!(f)?;
         }
 
-        let padding_mag: isize = (bounds.right - bounds.left).into();
-        write!(
-            f,
-            "{}{}",
-            iter::repeat(" ")
-                .take(padding_mag.abs() as usize)
-                .collect::<String>(),
-            bounds.bottom_right()
-        )?;
+        let padding: isize = (last.x - bounds.top_left().x).as_();
+        write!(f, "{}{}", " ".repeat(padding as usize), last)?;
 
         Ok(())
     }
@@ -101,7 +113,7 @@ where
         if self.bounds.contains(p) {
             self.cursor.x += I::one();
             if !self.bounds.contains(self.cursor) {
-                self.cursor.x = self.bounds.left;
+                self.cursor.x = self.bounds.top_left().x;
                 self.cursor.y += I::one();
             }
 
@@ -115,8 +127,7 @@ where
 pub struct ValueIterator<'a, G, I, C>
 where
     G: Grid<C, I>,
-    C: Display,
-    I: Debug + Display + PointAxe + Step + Into<isize>,
+    I: Debug + Display + PointAxe + AsPrimitive<isize>,
 {
     coord_iter: CoordinateIterator<I>,
     grid: &'a G,
@@ -126,8 +137,7 @@ where
 impl<'a, G, I, C> ValueIterator<'a, G, I, C>
 where
     G: Grid<C, I>,
-    C: Display,
-    I: Debug + Display + PointAxe + Step + Into<isize>,
+    I: Debug + Display + PointAxe + AsPrimitive<isize>,
 {
     pub fn new(grid: &'a G) -> Self {
         Self {
@@ -141,8 +151,8 @@ where
 impl<'a, G, I, C> Iterator for ValueIterator<'a, G, I, C>
 where
     G: Grid<C, I>,
-    C: Display + 'a,
-    I: Debug + Display + PointAxe + Step + Into<isize> + AddAssign,
+    C: 'a,
+    I: Debug + Display + PointAxe + AsPrimitive<isize> + AddAssign,
 {
     type Item = &'a C;
 
@@ -3,12 +3,13 @@ use crate::twodee::{
     Point,
     PointAxe,
 };
-use std::{collections::HashMap, fmt, hash::Hash, iter::Step};
+use num_traits::AsPrimitive;
+use std::{collections::HashMap, fmt, hash::Hash};
 
 #[derive(Debug, Clone)]
 pub struct PatchedGrid<'a, G, C, I>
 where
-    I: PointAxe + Hash + fmt::Display + Step + Into<isize>,
+    I: PointAxe + Hash + fmt::Display + AsPrimitive<isize>,
     G: Grid<C, I> + Clone + fmt::Debug,
     C: fmt::Display,
 {
@@ -19,7 +20,7 @@ where
 impl<'a, G, C, I> PatchedGrid<'a, G, C, I>
 where
     G: Grid<C, I> + Clone + fmt::Debug,
-    I: PointAxe + Hash + fmt::Display + Step + Into<isize>,
+    I: PointAxe + Hash + fmt::Display + AsPrimitive<isize>,
     C: fmt::Display + Clone,
 {
     pub fn new(grid: &'a G) -> Self {
@@ -38,7 +39,7 @@ where
 impl<'a, G, C, I> Grid<C, I> for PatchedGrid<'a, G, C, I>
 where
     G: Grid<C, I> + Clone + fmt::Debug,
-    I: PointAxe + Hash + fmt::Display + Step + Into<isize>,
+    I: PointAxe + Hash + fmt::Display + AsPrimitive<isize>,
     C: fmt::Display + Clone,
 {
     fn bounds(&self) -> Bounds<I> {
@@ -56,7 +57,7 @@ where
 
 impl<'a, G, C, I> fmt::Display for PatchedGrid<'a, G, C, I>
 where
-    I: PointAxe + Hash + fmt::Display + Step + Into<isize>,
+    I: PointAxe + Hash + fmt::Display + AsPrimitive<isize>,
     G: Grid<C, I> + Clone + fmt::Debug,
     C: fmt::Display + Clone,
 {
@@ -1,7 +1,20 @@
-use std::{fmt, ops::Mul};
-use std::{fmt::Display, iter::Step, ops::Index};
+use num_traits::AsPrimitive;
+use std::{convert::TryFrom, fmt};
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
 
-use crate::twodee::{grid::Grid, Bounds, Point, PointAxe};
+use crate::{
+    error::AdventError,
+    helpers::{NumberedLine, StringAdventExt},
+    twodee::{
+        grid::{coordinate, Grid},
+        Bounds,
+        Point,
+        PointAxe,
+    },
+};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct VecGrid<I, C> {
@@ -11,12 +24,11 @@ pub struct VecGrid<I, C> {
 
 impl<I, C> VecGrid<I, C>
 where
-    I: PointAxe,
-    <I as Mul<I>>::Output: Into<isize>,
+    I: PointAxe + AsPrimitive<isize>,
     C: Default,
 {
     pub fn new(bounds: Bounds<I>) -> Self {
-        let capacity: isize = (bounds.width() * bounds.height()).into();
+        let capacity: isize = (bounds.width() * bounds.height()).as_();
         let mut cells = Vec::with_capacity(capacity as usize);
         cells.resize_with(capacity as usize, Default::default);
 
@@ -26,21 +38,138 @@ where
 
 impl<I, C> VecGrid<I, C>
 where
-    I: PointAxe,
-    <I as Mul<I>>::Output: Into<isize>,
+    I: PointAxe + TryFrom<usize>,
+    C: TryFrom<char>,
+    C::Error: Display,
 {
+    /// Parse a grid with a cell for each character, with the top left
+    /// character at `(0, 0)`. Blank lines are skipped, as is whitespace at the
+    /// end of each line, and any indent that every row shares. Every row must
+    /// then be the same width, so a row that is indented differently from the
+    /// rest is an error.
+    ///
+    /// # Errors
+    ///
+    /// If the rows aren't all the same width, or a character isn't a valid
+    /// `C`. The error has the line and column of the problem.
+    pub fn parse(input: &str) -> Result<Self, AdventError> {
+        let lines: Vec<NumberedLine> = input.numbered_lines().collect();
+        let indent = lines.iter().map(NumberedLine::indent).min().unwrap_or(0);
+        let mut cells = vec![];
+        let mut width = None;
+
+        for line in &lines {
+            let row: Vec<char> = line.raw.trim_end().chars().skip(indent).collect();
+            match width {
+                None => width = Some(row.len()),
+                Some(width) if width != row.len() => {
+                    return Err(line.raw_error(
+                        indent + width.min(row.len()) + 1,
+                        format!(
+                            "Row is {} wide, but the first row is {} wide",
+                            row.len(),
+                            width
+                        ),
+                    ))
+                }
+                Some(_) => (),
+            }
+            for (x, &c) in row.iter().enumerate() {
+                let cell = C::try_from(c).map_err(|err| {
+                    line.raw_error(indent + x + 1, format!("Unknown cell `{}`: {}", c, err))
+                })?;
+                cells.push(cell);
+            }
+        }
+
+        let height = lines.len();
+        match (width, lines.last()) {
+            (Some(width), Some(line)) => Ok(Self {
+                cells,
+                bounds: Bounds::new(
+                    I::zero(),
+                    I::zero(),
+                    coordinate(line, width, width)?,
+                    coordinate(line, 1, height)?,
+                ),
+            }),
+            _ => Err(AdventError::parse(
+                input.trim(),
+                "Expected at least one row",
+            )),
+        }
+    }
+}
+
+impl<I, C> VecGrid<I, C>
+where
+    I: PointAxe + AsPrimitive<isize>,
+{
+    /// A grid from its cells, in rows from top to bottom.
+    pub(crate) fn from_cells(bounds: Bounds<I>, cells: Vec<C>) -> Self {
+        assert_eq!(
+            (bounds.width() * bounds.height()).as_(),
+            cells.len() as isize
+        );
+        Self { cells, bounds }
+    }
+
     fn point_to_idx(&self, p: Point<I>) -> usize {
-        let x = p.x - self.bounds.left;
-        let y = p.y - self.bounds.top;
-        let idx: isize = (x + y * self.bounds.width()).into();
+        let offset = p - self.bounds.top_left();
+        let idx: isize = (offset.x + offset.y * self.bounds.width()).as_();
         idx as usize
     }
 }
 
+impl<I, T> VecGrid<I, Option<T>>
+where
+    I: PointAxe + AsPrimitive<isize>,
+    T: Display,
+{
+    /// Display a grid of cells that might be empty, with `.` for the empty
+    /// ones. Every cell is centered in the width of the widest one, so that
+    /// the columns line up.
+    pub fn display_options(&self) -> OptionDisplay<'_, I, T> {
+        OptionDisplay(self)
+    }
+}
+
+pub struct OptionDisplay<'a, I, T>(&'a VecGrid<I, Option<T>>);
+
+impl<'a, I, T> fmt::Display for OptionDisplay<'a, I, T>
+where
+    I: PointAxe + AsPrimitive<isize>,
+    T: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbols: Vec<String> = self
+            .0
+            .cells
+            .iter()
+            .map(|cell| {
+                cell.as_ref()
+                    .map_or_else(|| ".".to_owned(), ToString::to_string)
+            })
+            .collect();
+        let cell_width = symbols.iter().map(String::len).max().unwrap_or(0);
+        let row_width = self.0.bounds.width().as_() as usize;
+        if row_width == 0 {
+            return Ok(());
+        }
+
+        for row in symbols.chunks(row_width) {
+            for symbol in row {
+                write!(f, "{:^width$}", symbol, width = cell_width)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
 impl<C, I> Grid<C, I> for VecGrid<I, C>
 where
-    C: Display,
-    I: PointAxe + Display + Step + Into<isize>,
+    I: PointAxe + Display + AsPrimitive<isize>,
     [C]: Index<usize, Output = C>,
 {
     fn bounds(&self) -> Bounds<I> {
@@ -61,12 +190,120 @@ where
     }
 }
 
+impl<I, C> Index<Point<I>> for VecGrid<I, C>
+where
+    I: PointAxe + Display + AsPrimitive<isize>,
+{
+    type Output = C;
+
+    fn index(&self, p: Point<I>) -> &C {
+        assert!(self.bounds.contains(p), "{} is outside of the grid", p);
+        &self.cells[self.point_to_idx(p)]
+    }
+}
+
+impl<I, C> IndexMut<Point<I>> for VecGrid<I, C>
+where
+    I: PointAxe + Display + AsPrimitive<isize>,
+{
+    fn index_mut(&mut self, p: Point<I>) -> &mut C {
+        assert!(self.bounds.contains(p), "{} is outside of the grid", p);
+        let idx = self.point_to_idx(p);
+        &mut self.cells[idx]
+    }
+}
+
 impl<I, C> fmt::Display for VecGrid<I, C>
 where
-    I: PointAxe + fmt::Display + Step + Into<isize>,
+    I: PointAxe + fmt::Display + AsPrimitive<isize>,
     C: fmt::Display + Clone,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.display(f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Cell {
+        Open,
+        Wall,
+    }
+
+    impl TryFrom<char> for Cell {
+        type Error = String;
+
+        fn try_from(c: char) -> Result<Self, Self::Error> {
+            match c {
+                '.' => Ok(Self::Open),
+                '#' => Ok(Self::Wall),
+                _ => Err("expected `.` or `#`".to_owned()),
+            }
+        }
+    }
+
+    impl Display for Cell {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}", if *self == Self::Wall { '#' } else { '.' })
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid: VecGrid<isize, Cell> = VecGrid::parse("\n  .#.\n  ##.\n").unwrap();
+        assert_eq!(grid.bounds(), Bounds::new(0, 0, 3, 2));
+        assert_eq!(grid.get(Point::new(1, 0)), Some(&Cell::Wall));
+        assert_eq!(grid.get(Point::new(2, 1)), Some(&Cell::Open));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = VecGrid::<isize, Cell>::parse(".#.\n.x.").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parse error at line 2, column 2: Unknown cell `x`: expected `.` or `#` in `.x.`"
+        );
+
+        let err = VecGrid::<isize, Cell>::parse("  .#.\n  .#").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parse error at line 2, column 5: Row is 2 wide, but the first row is 3 wide in `  .#`"
+        );
+
+        let err = VecGrid::<isize, Cell>::parse("  .#.\n   .#.").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parse error at line 2, column 6: Row is 4 wide, but the first row is 3 wide in `   \
+             .#.`"
+        );
+
+        assert!(VecGrid::<isize, Cell>::parse("\n").is_err());
+    }
+
+    #[test]
+    fn test_index() {
+        let mut grid: VecGrid<usize, usize> = VecGrid::new(Bounds::new(0, 0, 3, 2));
+        grid[Point::new(2, 1)] = 7;
+        assert_eq!(grid[Point::new(2, 1)], 7);
+        assert_eq!(grid.iter_values().sum::<usize>(), 7);
+        assert_eq!(grid.iter_coords().last(), Some(Point::new(2, 1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "(3,0) is outside of the grid")]
+    fn test_index_outside() {
+        let grid: VecGrid<usize, usize> = VecGrid::new(Bounds::new(0, 0, 3, 2));
+        let _ = grid[Point::new(3, 0)];
+    }
+
+    #[test]
+    fn test_display_options() {
+        let mut grid: VecGrid<usize, Option<usize>> = VecGrid::new(Bounds::new(0, 0, 3, 2));
+        grid[Point::new(0, 0)] = Some(4);
+        grid[Point::new(2, 1)] = Some(12);
+        assert_eq!(grid.display_options().to_string(), "4 . . \n. . 12\n");
+    }
+}
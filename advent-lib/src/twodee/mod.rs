@@ -1,5 +1,5 @@
 use num_traits::{sign::Signed, Num};
-use std::{cmp, fmt, iter, ops};
+use std::{fmt, ops};
 
 mod grid;
 
@@ -10,8 +10,43 @@ pub use self::grid::VecGrid;
 pub trait PointAxe: fmt::Debug + Clone + Copy + Eq + Num + PartialOrd {}
 impl<T> PointAxe for T where T: fmt::Debug + Clone + Copy + Eq + Num + PartialOrd {}
 
+/// Every value from `start` to `end`, inclusive. This works for any axis type,
+/// unlike `start..=end`, which can only be iterated for the primitive integers.
+pub fn axis_range<T: PointAxe>(start: T, end: T) -> AxisRange<T> {
+    AxisRange {
+        next: start,
+        end,
+        done: start > end,
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AxisRange<T> {
+    next: T,
+    end: T,
+    // Stepping past `end` could overflow, so stop on reaching it instead
+    done: bool,
+}
+
+impl<T: PointAxe> Iterator for AxisRange<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let next = self.next;
+        if next == self.end {
+            self.done = true;
+        } else {
+            self.next = next + T::one();
+        }
+        Some(next)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct Point<T: PointAxe> {
+pub struct Point<T> {
     pub x: T,
     pub y: T,
 }
@@ -120,11 +155,18 @@ impl<T: PointAxe> Point<T> {
     pub fn zero() -> Self {
         Self::new(T::zero(), T::zero())
     }
-}
 
-impl<T: PointAxe + Signed> Point<T> {
+    /// The distance to `rhs` moving only along the axes. Unlike
+    /// `manhattan_magnitude`, this works for unsigned coordinates too.
     pub fn manhattan_distance(&self, rhs: Point<T>) -> T {
-        (self.x - rhs.x).abs() + (self.y - rhs.y).abs()
+        fn difference<T: PointAxe>(a: T, b: T) -> T {
+            if a > b {
+                a - b
+            } else {
+                b - a
+            }
+        }
+        difference(self.x, rhs.x) + difference(self.y, rhs.y)
     }
 }
 
@@ -493,6 +535,8 @@ macro_rules! point_from {
 
 point_from!(usize, i32);
 point_from!(i32, usize);
+point_from!(usize, isize);
+point_from!(isize, usize);
 
 impl<T> fmt::Display for Point<T>
 where
@@ -504,73 +548,35 @@ where
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
-pub struct Bounds<I> {
-    top: I,
-    left: I,
-    right: I,
-    bottom: I,
-}
+/// Bounds of 2D points. The top and left edges are in the bounds, and the
+/// bottom and right edges are just outside of them.
+pub type Bounds<I> = crate::bounds::Bounds<Point<I>>;
 
 impl<I> Bounds<I>
 where
     I: PointAxe,
 {
+    /// # Panics
+    ///
+    /// If `bottom` is above `top`, or `right` is left of `left`.
     pub fn new(top: I, left: I, right: I, bottom: I) -> Self {
-        assert!(top <= bottom);
-        assert!(left <= right);
-        Self {
-            top,
-            left,
-            right,
-            bottom,
-        }
+        Self::exclusive(Point::new(left, top), Point::new(right, bottom))
     }
 
     pub fn top_left(&self) -> Point<I> {
-        Point::new(self.left, self.top)
+        self.min()
     }
 
+    /// The corner just outside of the bounds, below and to the right of them.
     pub fn bottom_right(&self) -> Point<I> {
-        Point::new(self.right, self.bottom)
+        self.max_exclusive()
     }
 
     pub fn width(&self) -> I {
-        self.right - self.left
+        self.size(0)
     }
 
     pub fn height(&self) -> I {
-        self.bottom - self.top
-    }
-
-    pub fn contains(&self, p: Point<I>) -> bool {
-        (self.left..self.right).contains(&p.x) && (self.top..self.bottom).contains(&p.y)
-    }
-}
-
-impl<I> iter::FromIterator<Point<I>> for Bounds<I>
-where
-    I: PointAxe + cmp::Ord,
-{
-    fn from_iter<T: IntoIterator<Item = Point<I>>>(iter: T) -> Self {
-        let mut iter = iter.into_iter();
-        let (mut top, mut right, mut bottom, mut left) = match iter.next() {
-            Some(first) => (first.y, first.x, first.y, first.x),
-            None => (I::zero(), I::zero(), I::zero(), I::zero()),
-        };
-
-        for point in iter {
-            left = cmp::min(left, point.x);
-            right = cmp::max(right, point.x);
-            top = cmp::min(top, point.y);
-            bottom = cmp::max(bottom, point.y);
-        }
-
-        Self {
-            top,
-            right,
-            bottom,
-            left,
-        }
+        self.size(1)
     }
 }
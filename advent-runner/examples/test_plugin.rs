@@ -0,0 +1,46 @@
+//! A plugin with a puzzle that is only half solved, for the `plugins` module's
+//! tests to load. `cargo test` builds it as a dynamic library along with them.
+
+use advent_lib::{
+    cases::{DayCase, DayRunner, DefaultError, Puzzle},
+    error::AdventError,
+};
+
+fn get_puzzles() -> Vec<Box<dyn Puzzle>> {
+    Day01.into_puzzles()
+}
+
+#[derive(Debug)]
+struct Day01;
+
+impl DayRunner for Day01 {
+    type Input = String;
+    type Shared = ();
+    type Output1 = usize;
+    type Output2 = usize;
+    type Error = DefaultError;
+
+    fn name(&self) -> String {
+        "2015-D01".to_owned()
+    }
+
+    fn cases(&self) -> Result<Vec<DayCase<Self>>, Box<dyn std::error::Error>> {
+        Ok(DayCase::<Self>::build_set()
+            .case("Example", "(()", 3, None)
+            .collect())
+    }
+
+    fn prepare(_input: &Self::Input) -> Result<Self::Shared, Self::Error> {
+        Ok(())
+    }
+
+    fn part1(input: &Self::Input, _shared: &Self::Shared) -> Result<Self::Output1, Self::Error> {
+        Ok(input.len())
+    }
+
+    fn part2(_input: &Self::Input, _shared: &Self::Shared) -> Result<Self::Output2, Self::Error> {
+        Err(AdventError::NotImplemented.into())
+    }
+}
+
+advent_lib::export_plugin!(get_puzzles);
@@ -12,25 +12,64 @@
     clippy::use_self, // Doesn't work well with generics
 )]
 
-use advent_lib::cases::{Puzzle, PuzzleResultStatus};
-use colored::Colorize;
+use advent_lib::{
+    cases::{Puzzle, PuzzleResultStatus},
+    id::PuzzleId,
+    inspect::InputReport,
+    property::{parse_seed, SEED_VAR},
+};
+use advent_runner::{
+    all_puzzles,
+    calendar::{self, Calendar},
+    memory::MemoryStats,
+    plugins,
+    report::{self, ReportEntry, ReportFormat},
+    reporter::{
+        format_sum_duration,
+        status_label,
+        write_cross_check,
+        OutputFormat,
+        TerminalReporter,
+    },
+    runner::group_by_puzzle,
+    scaffold,
+    serve,
+    Filter,
+    Runner,
+};
 use num_format::{Locale, ToFormattedString};
-use reqwest::StatusCode;
 use std::{
+    cmp,
+    collections::BTreeMap,
     convert::{TryFrom, TryInto},
-    fmt::{self, Display},
+    fmt::Display,
     fs,
-    path::PathBuf,
-    time::Duration,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::{self, Stdio},
+    str::FromStr,
+    time::{Duration, Instant},
 };
 use structopt::StructOpt;
 
+// Counting allocations slows every allocation down a little, so it is only
+// done in runners built to measure memory
+#[cfg(feature = "memory")]
+#[global_allocator]
+static ALLOCATOR: advent_runner::memory::CountingAllocator =
+    advent_runner::memory::CountingAllocator;
+
 #[derive(StructOpt, Debug)]
 struct Opt {
     /// Verbose mode, can be repeated (-v, -vv, -vvv, etc.)
     #[structopt(short, long, parse(from_occurrences))]
     verbose: u8,
 
+    /// Load year plugins from this directory, instead of `plugins` next to the
+    /// executable
+    #[structopt(long, env = "ADVENT_PLUGINS", parse(from_os_str))]
+    plugins: Option<PathBuf>,
+
     #[structopt(subcommand)]
     cmd: Command,
 }
@@ -46,6 +85,41 @@ enum Command {
         /// Verbose mode, can be repeated (-v, -vv, -vvv, etc.)
         #[structopt(short, long, parse(from_occurrences))]
         verbose: u8,
+
+        /// Copy the answer of the last case run to the clipboard
+        #[structopt(short, long)]
+        copy: bool,
+
+        /// Stop any case that runs for longer than this many seconds
+        #[structopt(short, long)]
+        timeout: Option<f64>,
+
+        /// Run every implementation of each puzzle, and compare their answers
+        #[structopt(long)]
+        cross_check: bool,
+
+        /// Measure the peak memory use and number of allocations of each case.
+        /// The runner has to be built with the `memory` feature, and memory
+        /// used by puzzles from plugins isn't counted.
+        #[structopt(short, long)]
+        memory: bool,
+
+        /// Show the slowest or hungriest puzzles first (time or memory)
+        #[structopt(long)]
+        sort_by: Option<SortBy>,
+
+        /// The seed for property cases, or `random` for a new one each run.
+        /// Defaults to a fixed seed, so that runs are repeatable.
+        #[structopt(long, env = SEED_VAR, parse(try_from_str = parse_seed))]
+        seed: Option<u64>,
+
+        /// How to show the results (terminal, json, or junit)
+        #[structopt(short, long, default_value = "terminal")]
+        format: OutputFormat,
+
+        /// Write the results to this file instead of standard output
+        #[structopt(short, long, parse(from_os_str))]
+        output: Option<PathBuf>,
     },
 
     /// Lists puzzle that could run
@@ -59,6 +133,85 @@ enum Command {
         verbose: u8,
     },
 
+    /// Runs a single case over and over, for use with a sampling profiler
+    Profile {
+        /// Only run the case who's name contains this string
+        #[structopt()]
+        filter: Vec<String>,
+
+        /// Stop after running the case this many times
+        #[structopt(short = "n", long)]
+        iterations: Option<usize>,
+
+        /// Stop after running for this many seconds. Defaults to 10 seconds if
+        /// no iteration count is given.
+        #[structopt(short, long)]
+        duration: Option<f64>,
+    },
+
+    /// Runs puzzles and shows which days of each year are solved. Results are
+    /// saved, so that later calendars can use them with --cached.
+    Calendar {
+        /// Only show the calendar for this year
+        #[structopt(short, long)]
+        year: Option<u16>,
+
+        /// Stop any case that runs for longer than this many seconds
+        #[structopt(short, long)]
+        timeout: Option<f64>,
+
+        /// Use the results saved by the last run, and only run puzzles without
+        /// one. Saved results can be stale if the puzzles have changed since.
+        #[structopt(short, long)]
+        cached: bool,
+    },
+
+    /// Browses puzzles and their cases interactively
+    #[cfg(feature = "tui")]
+    Tui,
+
+    /// Serves a page and JSON API for running puzzles and seeing their results
+    Serve {
+        /// The port to listen on, on localhost. Use 0 to pick any free port.
+        #[structopt(short, long, default_value = "8000")]
+        port: u16,
+
+        /// Stop any case that runs for longer than this many seconds
+        #[structopt(short, long)]
+        timeout: Option<f64>,
+    },
+
+    /// Runs puzzles and writes a table of their timings
+    Report {
+        /// Only include puzzles who's name contains this string
+        #[structopt()]
+        filter: Vec<String>,
+
+        /// The format of the report (markdown or csv)
+        #[structopt(short, long, default_value = "markdown")]
+        format: ReportFormat,
+
+        /// Write the report to this file instead of standard output
+        #[structopt(short, long, parse(from_os_str))]
+        output: Option<PathBuf>,
+
+        /// Stop any case that runs for longer than this many seconds
+        #[structopt(short, long)]
+        timeout: Option<f64>,
+    },
+
+    /// Describes a day's input, to help write or debug a parser for it
+    Inspect {
+        /// The day to inspect, like 2020-D04. Its input is read from the
+        /// day's directory, so run this from the root of the workspace.
+        #[structopt()]
+        day: Option<String>,
+
+        /// Inspect this file instead
+        #[structopt(short, long, parse(from_os_str))]
+        file: Option<PathBuf>,
+    },
+
     /// Adds a puzzle, templating the code and fetching the input
     AddDay {
         /// The day of the puzzle to add (1 through 25)
@@ -77,36 +230,60 @@ enum Command {
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let opt = Opt::from_args();
+    let plugin_dir = opt.plugins.clone().or_else(plugins::default_dir);
+    let memory = matches!(opt.cmd, Command::Run { memory: true, .. });
+    let puzzles = || load_puzzles(plugin_dir.as_deref(), memory);
 
     match opt.cmd {
-        Command::Run { .. } => run(opt),
-        Command::List { .. } => list(opt),
+        Command::Run { .. } => run(puzzles()?, opt)?,
+        Command::List { .. } => list(puzzles()?, opt)?,
+        Command::Profile { .. } => profile(puzzles()?, opt)?,
+        Command::Report { .. } => report(puzzles()?, opt)?,
+        Command::Calendar { .. } => calendar(puzzles()?, opt)?,
+        Command::Serve { port, timeout } => {
+            serve::serve(puzzles()?, port, timeout.map(Duration::from_secs_f64))?
+        }
+        #[cfg(feature = "tui")]
+        Command::Tui => advent_runner::tui::browse(puzzles()?),
+        Command::Inspect { day, file } => inspect(day.as_deref(), file.as_deref())?,
         Command::AddDay { .. } => add_puzzle(opt)?,
     }
 
     Ok(())
 }
 
-fn get_puzzles() -> Vec<Box<dyn Puzzle>> {
-    let mut puzzles = vec![];
-
-    #[cfg(feature = "year2015")]
-    puzzles.extend(year2015::get_puzzles());
-    #[cfg(feature = "year2017")]
-    puzzles.extend(year2017::get_puzzles());
-    #[cfg(feature = "year2018")]
-    puzzles.extend(year2018::get_puzzles());
-    #[cfg(feature = "year2019")]
-    puzzles.extend(year2019::get_puzzles());
-    #[cfg(feature = "year2020")]
-    puzzles.extend(year2020::get_puzzles());
-
-    puzzles
+/// The puzzles compiled into the runner, and any from plugins in `plugin_dir`.
+/// Plugins don't use the runner's allocator, so if `memory` will be measured,
+/// warn that it won't be for their puzzles.
+fn load_puzzles(
+    plugin_dir: Option<&Path>,
+    memory: bool,
+) -> Result<Vec<Box<dyn Puzzle>>, Box<dyn std::error::Error>> {
+    let mut puzzles = all_puzzles();
+    if let Some(dir) = plugin_dir {
+        let compiled_in = puzzles.len();
+        plugins::merge(&mut puzzles, plugins::load_dir(dir)?);
+        if memory && puzzles.len() > compiled_in {
+            eprintln!(
+                "Warning: the memory used by the {} puzzles loaded from plugins won't be measured",
+                puzzles.len() - compiled_in
+            );
+        }
+    }
+    Ok(puzzles)
 }
 
 struct RunOptions {
     filter: Vec<String>,
     verbose: bool,
+    copy: bool,
+    timeout: Option<Duration>,
+    cross_check: bool,
+    memory: bool,
+    sort_by: Option<SortBy>,
+    seed: Option<u64>,
+    format: OutputFormat,
+    output: Option<PathBuf>,
 }
 
 impl<'a> TryFrom<Opt> for RunOptions {
@@ -121,11 +298,28 @@ impl<'a> TryFrom<Opt> for RunOptions {
         if let Command::Run {
             filter,
             verbose: cmd_verbose,
+            copy,
+            timeout,
+            cross_check,
+            memory,
+            sort_by,
+            seed,
+            format,
+            output,
         } = cmd
         {
             Ok(Self {
                 filter,
                 verbose: cmd_verbose + top_verbose > 0,
+                copy,
+                timeout: timeout.map(Duration::from_secs_f64),
+                cross_check,
+                // Sorting by memory needs it to be measured
+                memory: memory || matches!(sort_by, Some(SortBy::Memory)),
+                sort_by,
+                seed,
+                format,
+                output,
             })
         } else {
             Err("Incorrect subcommand, expected run".to_owned())
@@ -133,128 +327,131 @@ impl<'a> TryFrom<Opt> for RunOptions {
     }
 }
 
-fn run<O: TryInto<RunOptions>>(opts: O)
+fn run<O: TryInto<RunOptions>>(
+    puzzles: Vec<Box<dyn Puzzle>>,
+    opts: O,
+) -> Result<(), Box<dyn std::error::Error>>
 where
     O::Error: Display,
 {
     let opts = opts.try_into().unwrap_or_else(|err| panic!("{}", err));
 
-    let filter_parts: Vec<String> = opts
-        .filter
-        .iter()
-        .flat_map(|f| f.split(' '))
-        .map(|p| p.to_string().to_lowercase())
-        .collect();
-
-    for puzzle in get_puzzles() {
-        let results: Vec<_> = puzzle
-            .cases()
-            .unwrap_or_else(|err| {
-                panic!(format!(
-                    "Could not determine cases for {}: {}",
-                    puzzle.name(),
-                    err
-                ))
-            })
-            .into_iter()
-            .filter(|case| {
-                if filter_parts.is_empty() {
-                    true
-                } else {
-                    let haystack = format!("{} {}", puzzle.name(), case.name()).to_lowercase();
-                    filter_parts.iter().all(|needle| haystack.contains(needle))
-                }
-            })
-            .map(|case| {
-                let result = case.run();
-                (case, result)
-            })
-            .collect();
+    if opts.memory && !cfg!(feature = "memory") {
+        return Err(
+            "Memory use can't be measured, as the runner was built without the `memory` feature"
+                .into(),
+        );
+    }
 
-        if results.is_empty() {
-            continue;
-        }
+    let mut runner = Runner::new(puzzles)
+        .with_timeout(opts.timeout)
+        .with_seed(opts.seed)
+        .with_memory(opts.memory)
+        .with_progress(true);
+    for filter in &opts.filter {
+        runner = runner.filter(filter);
+    }
 
-        print!("{:<12}", puzzle.name());
-        if opts.verbose {
-            println!();
-            for (case, result) in results {
-                print!(
-                    "    {} ",
-                    match result.status {
-                        PuzzleResultStatus::Match => "PASS".green(),
-                        PuzzleResultStatus::Unknown => "UNKO".yellow(),
-                        PuzzleResultStatus::Fail => "FAIL".red(),
-                        PuzzleResultStatus::Error => "ERRO".black().on_red(),
-                    }
-                );
-                print!("{:<10} ", case.name());
-                match result.status {
-                    PuzzleResultStatus::Match => (),
-                    PuzzleResultStatus::Unknown
-                    | PuzzleResultStatus::Fail
-                    | PuzzleResultStatus::Error => {
-                        print!(" -> {}", result.description)
-                    }
-                }
-                print!("{}", format_sum_duration(&[result.duration]));
-                println!();
+    if opts.cross_check {
+        write_cross_check(io::stdout(), &runner.cross_check())?;
+        return Ok(());
+    }
+
+    let out: Box<dyn Write> = match &opts.output {
+        Some(path) => Box::new(fs::File::create(path)?),
+        None => Box::new(io::stdout()),
+    };
+    let mut reporter = opts.format.reporter(out, opts.verbose);
+
+    let reports = match opts.sort_by {
+        None => runner.run_with(reporter.as_mut())?,
+        Some(sort_by) => {
+            // Everything has to run before the slowest can be shown first
+            let reports = runner.run();
+            let mut puzzles = group_by_puzzle(&reports);
+            match sort_by {
+                SortBy::Time => puzzles.sort_by_key(|cases| {
+                    cmp::Reverse(cases.iter().map(|c| c.result.duration).sum::<Duration>())
+                }),
+                SortBy::Memory => puzzles.sort_by_key(|cases| {
+                    let memory = MemoryStats::combine(cases.iter().filter_map(|c| c.memory));
+                    cmp::Reverse(memory.map(|m| m.peak_bytes))
+                }),
             }
-        } else {
-            for (_, result) in &results {
-                match result.status {
-                    PuzzleResultStatus::Match => print!("{}", "✔".green()),
-                    PuzzleResultStatus::Unknown => print!("{}", "?".yellow()),
-                    PuzzleResultStatus::Fail => print!("{}", "✗".red()),
-                    PuzzleResultStatus::Error => print!("{}", "!".black().on_red()),
-                }
+            for cases in puzzles {
+                reporter.puzzle(cases)?;
             }
-
-            let spacer = (results.len()..10).map(|_| " ").collect::<String>();
-            let durations: Vec<_> = results.iter().map(|(_, res)| res.duration).collect();
-            println!("{}{}", spacer, format_sum_duration(&durations));
-
-            for (case, result) in results {
-                match result.status {
-                    PuzzleResultStatus::Unknown => println!(
-                        "   {} {:<10} -> {}",
-                        "UNKO".yellow(),
-                        case.name(),
-                        result.description
-                    ),
-                    PuzzleResultStatus::Fail => println!(
-                        "   {} {:<10} -> {}",
-                        "FAIL".red(),
-                        case.name(),
-                        result.description
-                    ),
-                    PuzzleResultStatus::Error => println!(
-                        "   {} {:<10} -> {}",
-                        "ERRO".black().on_red(),
-                        case.name(),
-                        result.description
-                    ),
-                    PuzzleResultStatus::Match => (),
+            reporter.finish()?;
+            reports
+        }
+    };
+
+    if opts.copy {
+        match reports.iter().rev().find(|r| r.result.answer.is_some()) {
+            Some(report) => {
+                let source = format!("{} {}", report.puzzle, report.case);
+                let answer = report.result.answer.clone().unwrap_or_default();
+                match copy_to_clipboard(&answer) {
+                    Ok(()) => println!("Copied answer of {} to the clipboard", source),
+                    Err(err) => println!("Could not copy answer of {}: {}", source, err),
                 }
             }
+            None => println!("No answer to copy"),
         }
     }
+
+    Ok(())
+}
+
+#[derive(Clone, Copy, Debug)]
+enum SortBy {
+    Time,
+    Memory,
 }
 
-fn format_sum_duration(ds: &[Duration]) -> impl fmt::Display {
-    let sum: u128 = ds.iter().map(Duration::as_micros).sum();
-    let s = sum.to_formatted_string(&Locale::en);
-    let s = format!("{:>10} μs ", s);
-    match sum {
-        0 => s.bright_black(),
-        d if d < 1_000 => s.bright_black(),
-        d if d < 10_000 => s.blue(),
-        d if d < 100_000 => s.yellow(),
-        d if d < 1_000_000 => s.red(),
-        _ => s.black().on_red(),
+impl FromStr for SortBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "time" => Ok(Self::Time),
+            "memory" => Ok(Self::Memory),
+            _ => Err(format!("Can't sort by {}, expected time or memory", s)),
+        }
     }
 }
 
+/// Copy text to the system clipboard, using whichever clipboard tool is
+/// available.
+fn copy_to_clipboard(text: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let tools: &[(&str, &[&str])] = &[
+        ("pbcopy", &[]),
+        ("wl-copy", &[]),
+        ("xclip", &["-selection", "clipboard"]),
+        ("xsel", &["--clipboard", "--input"]),
+        ("clip.exe", &[]),
+    ];
+
+    for (program, args) in tools {
+        let mut child = match process::Command::new(program)
+            .args(*args)
+            .stdin(Stdio::piped())
+            .spawn()
+        {
+            Ok(child) => child,
+            Err(_) => continue,
+        };
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(text.as_bytes())?;
+        }
+        if child.wait()?.success() {
+            return Ok(());
+        }
+    }
+
+    Err("no clipboard tool found (tried pbcopy, wl-copy, xclip, xsel and clip.exe)".into())
+}
+
 #[derive(Debug)]
 struct AddDayOptions {
     day: u8,
@@ -289,47 +486,346 @@ where
     O::Error: Display,
 {
     let opts = opts.try_into().unwrap_or_else(|err| panic!("{}", err));
+    scaffold::add_day(opts.year, opts.day, &opts.advent_cookie)
+}
+
+/// Show the shape of a day's input, or of any file.
+fn inspect(day: Option<&str>, file: Option<&Path>) -> Result<(), Box<dyn std::error::Error>> {
+    let path = match (day, file) {
+        (_, Some(file)) => file.to_owned(),
+        (Some(day), None) => {
+            // Both parts of a day share an input, so the part is optional
+            let id = day
+                .parse::<PuzzleId>()
+                .or_else(|err| format!("{}-P1", day).parse().map_err(|_| err))?;
+            scaffold::day_path(id.year, id.day).join("input")
+        }
+        (None, None) => return Err("Give a day, like 2020-D04, or a file to inspect".into()),
+    };
+    let input = fs::read_to_string(&path)
+        .map_err(|err| format!("Could not read {}: {}", path.display(), err))?;
+
+    println!("{}", path.display());
+    print!("{}", InputReport::new(&input));
+    Ok(())
+}
 
-    let day_padded = format!("{:0>2}", opts.day.to_string());
+struct ProfileOptions {
+    filter: Vec<String>,
+    iterations: Option<usize>,
+    duration: Option<Duration>,
+}
 
-    let puzzle_path = PathBuf::from(format!("./year{}/src/day{}", opts.year, day_padded));
-    fs::create_dir_all(&puzzle_path)?;
+impl<'a> TryFrom<Opt> for ProfileOptions {
+    type Error = String;
 
-    let mut mod_path = puzzle_path.clone();
-    mod_path.push("mod.rs");
-    if !mod_path.exists() {
-        let mod_template = String::from_utf8(fs::read("./template/mod.rs.tmpl")?)?
-            .replace("{{YEAR}}", &opts.year.to_string())
-            .replace("{{DAY_PADDED}}", &day_padded);
-        fs::write(mod_path, mod_template)?;
+    fn try_from(opt: Opt) -> Result<Self, Self::Error> {
+        let Opt { cmd, .. } = opt;
+        if let Command::Profile {
+            filter,
+            iterations,
+            duration,
+        } = cmd
+        {
+            let duration = match (iterations, duration) {
+                (None, None) => Some(Duration::from_secs(10)),
+                (_, duration) => duration.map(Duration::from_secs_f64),
+            };
+            Ok(Self {
+                filter,
+                iterations,
+                duration,
+            })
+        } else {
+            Err("Incorrect subcommand, expected profile".to_owned())
+        }
     }
+}
 
-    let mut input_path = puzzle_path;
-    input_path.push("input");
-    if !input_path.exists() {
-        let url = format!(
-            "https://adventofcode.com/{}/day/{}/input",
-            opts.year, opts.day,
-        );
+fn profile<O: TryInto<ProfileOptions>>(
+    puzzles: Vec<Box<dyn Puzzle>>,
+    opts: O,
+) -> Result<(), Box<dyn std::error::Error>>
+where
+    O::Error: Display,
+{
+    let opts = opts.try_into().unwrap_or_else(|err| panic!("{}", err));
+
+    let filter = Filter::new(&opts.filter);
+    let mut matches = Runner::new(puzzles).filter(opts.filter.join(" ")).cases()?;
+
+    // Individual words of a filter like "P2 Example 1" can match many cases, so
+    // prefer cases that match the filter as a whole.
+    if matches.len() > 1 {
+        let exact: Vec<_> = matches
+            .iter()
+            .enumerate()
+            .filter(|(_, (puzzle_name, case))| filter.matches_whole(puzzle_name, &case.name()))
+            .map(|(idx, _)| idx)
+            .collect();
+        if let [idx] = exact[..] {
+            matches = vec![matches.remove(idx)];
+        }
+    }
+
+    let (puzzle_name, case) = match matches.len() {
+        0 => return Err("No cases match the filter".into()),
+        1 => matches.remove(0),
+        _ => {
+            println!("The filter matches more than one case:");
+            for (puzzle_name, case) in matches {
+                println!("  {} {}", puzzle_name, case.name());
+            }
+            return Err("Narrow the filter to a single case to profile it".into());
+        }
+    };
+
+    // Print the pid so a profiler can be attached to this process
+    println!(
+        "Profiling {} {} in process {}",
+        puzzle_name,
+        case.name(),
+        process::id()
+    );
+
+    let start = Instant::now();
+    let mut timings = vec![];
+    loop {
+        // Start from scratch each time, so that work shared with other cases,
+        // like the state a day prepares for both of its parts, is profiled too
+        case.reset();
+        let result = case.run();
+        if timings.is_empty() && result.status != PuzzleResultStatus::Match {
+            println!(
+                "{} {} -> {}",
+                status_label(result.status),
+                case.name(),
+                result.description
+            );
+        }
+        timings.push(result.duration + result.prepare.unwrap_or_default());
+
+        let enough_iterations = opts
+            .iterations
+            .map_or(false, |iterations| timings.len() >= iterations);
+        let enough_time = opts
+            .duration
+            .map_or(false, |duration| start.elapsed() >= duration);
+        if enough_iterations || enough_time {
+            break;
+        }
+    }
+
+    timings.sort();
+    let total: Duration = timings.iter().sum();
+    println!(
+        "{:>12} iterations in {}",
+        timings.len().to_formatted_string(&Locale::en),
+        format_sum_duration(&[total])
+    );
+    println!(
+        "{:>12} {}",
+        "mean",
+        format_sum_duration(&[total / timings.len() as u32])
+    );
+    println!("{:>12} {}", "min", format_sum_duration(&timings[..1]));
+    println!(
+        "{:>12} {}",
+        "median",
+        format_sum_duration(&[timings[timings.len() / 2]])
+    );
+    println!(
+        "{:>12} {}",
+        "max",
+        format_sum_duration(&timings[timings.len() - 1..])
+    );
+
+    Ok(())
+}
+
+struct ReportOptions {
+    filter: Vec<String>,
+    format: ReportFormat,
+    output: Option<PathBuf>,
+    timeout: Option<Duration>,
+}
 
-        let client = reqwest::Client::new();
-        let mut res = client
-            .get(&url)
-            .header(
-                reqwest::header::COOKIE,
-                format!("session={}", opts.advent_cookie),
-            )
-            .send()?;
-
-        if res.status() == StatusCode::NOT_FOUND {
-            println!("No input for this puzzle");
+impl<'a> TryFrom<Opt> for ReportOptions {
+    type Error = String;
+
+    fn try_from(opt: Opt) -> Result<Self, Self::Error> {
+        let Opt { cmd, .. } = opt;
+        if let Command::Report {
+            filter,
+            format,
+            output,
+            timeout,
+        } = cmd
+        {
+            Ok(Self {
+                filter,
+                format,
+                output,
+                timeout: timeout.map(Duration::from_secs_f64),
+            })
         } else {
-            let body = res.text()?;
-            if !res.status().is_success() {
-                println!("Error: {}", body);
-                res.error_for_status()?;
+            Err("Incorrect subcommand, expected report".to_owned())
+        }
+    }
+}
+
+fn report<O: TryInto<ReportOptions>>(
+    puzzles: Vec<Box<dyn Puzzle>>,
+    opts: O,
+) -> Result<(), Box<dyn std::error::Error>>
+where
+    O::Error: Display,
+{
+    let opts = opts.try_into().unwrap_or_else(|err| panic!("{}", err));
+
+    let mut runner = Runner::new(puzzles)
+        .with_timeout(opts.timeout)
+        .with_progress(true);
+    for filter in &opts.filter {
+        runner = runner.filter(filter);
+    }
+    // Show how the run is going on stderr, so it doesn't mix with the report
+    let reports = runner.run_with(&mut TerminalReporter::new(io::stderr(), false))?;
+
+    let mut entries = vec![];
+    for cases in group_by_puzzle(&reports) {
+        let name = &cases[0].puzzle;
+        match name.parse() {
+            Ok(id) => entries.push(ReportEntry::new(
+                id,
+                cases.iter().map(|c| (c.result.status, c.result.duration)),
+            )),
+            Err(_) => eprintln!("Skipping {}, its name is not a puzzle id", name),
+        }
+    }
+
+    let report = match opts.format {
+        ReportFormat::Markdown => report::markdown(&entries),
+        ReportFormat::Csv => report::csv(&entries),
+    };
+    match opts.output {
+        Some(path) => fs::write(path, report)?,
+        None => print!("{}", report),
+    }
+
+    Ok(())
+}
+
+struct CalendarOptions {
+    year: Option<u16>,
+    timeout: Option<Duration>,
+    cached: bool,
+}
+
+impl<'a> TryFrom<Opt> for CalendarOptions {
+    type Error = String;
+
+    fn try_from(opt: Opt) -> Result<Self, Self::Error> {
+        let Opt { cmd, .. } = opt;
+        if let Command::Calendar {
+            year,
+            timeout,
+            cached,
+        } = cmd
+        {
+            Ok(Self {
+                year,
+                timeout: timeout.map(Duration::from_secs_f64),
+                cached,
+            })
+        } else {
+            Err("Incorrect subcommand, expected calendar".to_owned())
+        }
+    }
+}
+
+fn calendar<O: TryInto<CalendarOptions>>(
+    puzzles: Vec<Box<dyn Puzzle>>,
+    opts: O,
+) -> Result<(), Box<dyn std::error::Error>>
+where
+    O::Error: Display,
+{
+    let opts = opts.try_into().unwrap_or_else(|err| panic!("{}", err));
+
+    // Group the puzzles by year, so each calendar can be shown as soon as its
+    // year has run
+    let mut years: BTreeMap<u16, Vec<Box<dyn Puzzle>>> = BTreeMap::new();
+    for puzzle in puzzles {
+        match puzzle.id() {
+            Some(id) if opts.year.map_or(true, |year| year == id.year) => {
+                years.entry(id.year).or_default().push(puzzle);
             }
-            fs::write(input_path, body)?;
+            Some(_) => (),
+            None => eprintln!("Skipping {}, its name is not a puzzle id", puzzle.name()),
+        }
+    }
+
+    if years.is_empty() {
+        return Err(match opts.year {
+            Some(year) => format!("No puzzles for {}", year).into(),
+            None => "No puzzles".into(),
+        });
+    }
+
+    // Running every puzzle can take minutes, so the results from the last time
+    // can be used instead, if asked for
+    let results_path = calendar::default_results_path();
+    let mut saved = match &results_path {
+        Some(path) if opts.cached => fs::read_to_string(path)
+            .map(|csv| report::read_csv(&csv))
+            .unwrap_or_default(),
+        _ => vec![],
+    };
+
+    let mut reused = 0;
+    for (year, puzzles) in years {
+        let ids: Vec<PuzzleId> = puzzles.iter().filter_map(|puzzle| puzzle.id()).collect();
+        saved.retain(|entry| entry.id.year != year || ids.contains(&entry.id));
+        let (known, unknown): (Vec<_>, Vec<_>) = puzzles.into_iter().partition(|puzzle| {
+            puzzle
+                .id()
+                .map_or(false, |id| saved.iter().any(|entry| entry.id == id))
+        });
+        reused += known.len();
+
+        if !unknown.is_empty() {
+            let reports = Runner::new(unknown)
+                .with_timeout(opts.timeout)
+                .with_progress(true)
+                .run();
+            saved.extend(group_by_puzzle(&reports).into_iter().filter_map(|cases| {
+                let id = cases[0].puzzle.parse().ok()?;
+                Some(ReportEntry::new(
+                    id,
+                    cases.iter().map(|c| (c.result.status, c.result.duration)),
+                ))
+            }));
+        }
+
+        let entries: Vec<_> = saved
+            .iter()
+            .filter(|entry| entry.id.year == year)
+            .copied()
+            .collect();
+        println!("{}", Calendar::new(year, &entries));
+    }
+    println!("{}", Calendar::legend());
+    if reused > 0 {
+        println!(
+            "Used saved results for {} puzzles, run without --cached to run them again",
+            reused
+        );
+    }
+
+    if let Some(path) = results_path {
+        if let Err(err) = fs::write(&path, report::csv(&saved)) {
+            eprintln!("Could not save results to {}: {}", path.display(), err);
         }
     }
 
@@ -365,44 +861,30 @@ impl<'a> TryFrom<Opt> for ListOptions {
     }
 }
 
-fn list<O: TryInto<ListOptions>>(opts: O)
+fn list<O: TryInto<ListOptions>>(
+    puzzles: Vec<Box<dyn Puzzle>>,
+    opts: O,
+) -> Result<(), Box<dyn std::error::Error>>
 where
     O::Error: Display,
 {
     let opts = opts.try_into().unwrap_or_else(|err| panic!("{}", err));
 
-    let filter_parts: Vec<String> = opts
-        .filter
-        .iter()
-        .flat_map(|f| f.split(' '))
-        .map(|p| p.to_string().to_lowercase())
-        .collect();
-
-    for puzzle in get_puzzles() {
-        let results: Vec<_> = puzzle
-            .cases()
-            .expect("Could not determine cases")
-            .into_iter()
-            .filter(|case| {
-                if filter_parts.is_empty() {
-                    true
-                } else {
-                    let haystack = format!("{} {}", puzzle.name(), case.name()).to_lowercase();
-                    filter_parts.iter().all(|needle| haystack.contains(needle))
-                }
-            })
-            .collect();
+    let mut runner = Runner::new(puzzles);
+    for filter in &opts.filter {
+        runner = runner.filter(filter);
+    }
 
-        if results.is_empty() {
-            continue;
+    let mut last_puzzle = None;
+    for (puzzle_name, case) in runner.cases()? {
+        if last_puzzle.as_ref() != Some(&puzzle_name) {
+            println!("{:<12}", puzzle_name);
+            last_puzzle = Some(puzzle_name);
         }
-
-        println!("{:<12}", puzzle.name());
-
         if opts.verbose {
-            for case in results {
-                println!("  {:<10} ", case.name());
-            }
+            println!("  {:<10} ", case.name());
         }
     }
+
+    Ok(())
 }
@@ -0,0 +1,167 @@
+use crate::report::ReportEntry;
+use advent_lib::cases::PuzzleResultStatus;
+use colored::{ColoredString, Colorize};
+use std::{
+    env,
+    fmt::{self, Write},
+    path::PathBuf,
+};
+
+const DAYS: u8 = 25;
+const COLUMNS: u8 = 5;
+
+/// The Advent of Code calendar for one year, with a cell for each day showing
+/// the state of both of its parts. Parts without a puzzle, such as days that
+/// don't have a module yet, are shown as missing. Day 25 has no second
+/// puzzle, so its second star is earned along with all of the others.
+pub struct Calendar<'a> {
+    year: u16,
+    entries: &'a [ReportEntry],
+}
+
+impl<'a> Calendar<'a> {
+    /// A calendar for `year`, using the entries that belong to it.
+    pub const fn new(year: u16, entries: &'a [ReportEntry]) -> Self {
+        Self { year, entries }
+    }
+
+    fn part(&self, day: u8, part: u8) -> Option<&ReportEntry> {
+        self.entries
+            .iter()
+            .find(|e| e.id.year == self.year && e.id.day == day && e.id.part == part)
+    }
+
+    /// The status of each part of each day, in order.
+    fn statuses(&self) -> impl Iterator<Item = Option<PuzzleResultStatus>> + '_ {
+        (1..=DAYS).flat_map(move |day| (1..=2).map(move |part| self.status(day, part)))
+    }
+
+    fn status(&self, day: u8, part: u8) -> Option<PuzzleResultStatus> {
+        let status = self.part(day, part).map(|e| e.status);
+        if status.is_none() && day == DAYS && part == 2 {
+            let others = (1..=DAYS)
+                .flat_map(|day| (1..=2).map(move |part| (day, part)))
+                .filter(|&(day, part)| day != DAYS || part != 2);
+            let earned = others
+                .map(|(day, part)| self.part(day, part).map(|e| e.status))
+                .all(|status| status == Some(PuzzleResultStatus::Match));
+            if earned {
+                return Some(PuzzleResultStatus::Match);
+            }
+        }
+        status
+    }
+
+    fn count(&self, status: PuzzleResultStatus) -> usize {
+        self.statuses().filter(|s| *s == Some(status)).count()
+    }
+
+    /// A summary of the whole legend, for printing once under all calendars.
+    pub fn legend() -> String {
+        let mut legend = String::new();
+        for (symbol, meaning) in &[
+            (part_symbol(Some(PuzzleResultStatus::Match)), "passes"),
+            (
+                part_symbol(Some(PuzzleResultStatus::Unknown)),
+                "no answer to check",
+            ),
+            (part_symbol(Some(PuzzleResultStatus::Todo)), "template"),
+            (part_symbol(Some(PuzzleResultStatus::Fail)), "wrong answer"),
+            (part_symbol(Some(PuzzleResultStatus::Error)), "error"),
+            (
+                part_symbol(Some(PuzzleResultStatus::Cancelled)),
+                "timed out",
+            ),
+            (part_symbol(None), "missing"),
+        ] {
+            write!(legend, "{} {}  ", symbol, meaning).unwrap();
+        }
+        legend.trim_end().to_owned()
+    }
+}
+
+impl<'a> fmt::Display for Calendar<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(fmt, "{}", self.year.to_string().bold())?;
+        for day in 1..=DAYS {
+            write!(fmt, "  {:>2} ", day)?;
+            for part in 1..=2 {
+                write!(fmt, "{}", part_symbol(self.status(day, part)))?;
+            }
+            if day % COLUMNS == 0 {
+                writeln!(fmt)?;
+            } else {
+                write!(fmt, "  ")?;
+            }
+        }
+
+        let stars = self.count(PuzzleResultStatus::Match);
+        write!(fmt, "  {}/{} stars", stars, usize::from(DAYS) * 2)?;
+        for (status, name) in &[
+            (PuzzleResultStatus::Unknown, "unknown"),
+            (PuzzleResultStatus::Todo, "template"),
+            (PuzzleResultStatus::Fail, "failing"),
+            (PuzzleResultStatus::Error, "erroring"),
+            (PuzzleResultStatus::Cancelled, "timed out"),
+        ] {
+            let count = self.count(*status);
+            if count > 0 {
+                write!(fmt, ", {} {}", count, name)?;
+            }
+        }
+        writeln!(fmt)
+    }
+}
+
+/// Where `advent calendar` keeps the results of the puzzles it has run, for
+/// `advent calendar --cached` to use: `calendar.csv`, next to the runner's
+/// executable.
+#[must_use]
+pub fn default_results_path() -> Option<PathBuf> {
+    let exe = env::current_exe().ok()?;
+    Some(exe.parent()?.join("calendar.csv"))
+}
+
+fn part_symbol(status: Option<PuzzleResultStatus>) -> ColoredString {
+    match status {
+        Some(PuzzleResultStatus::Match) => "*".bright_yellow().bold(),
+        Some(PuzzleResultStatus::Unknown) => "?".yellow(),
+        Some(PuzzleResultStatus::Todo) => "-".cyan(),
+        Some(PuzzleResultStatus::Fail) => "✗".red(),
+        Some(PuzzleResultStatus::Error) => "!".black().on_red(),
+        Some(PuzzleResultStatus::Cancelled) => "~".magenta(),
+        None => ".".bright_black(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent_lib::id::PuzzleId;
+    use std::time::Duration;
+
+    fn entry(day: u8, part: u8, status: PuzzleResultStatus) -> ReportEntry {
+        ReportEntry {
+            id: PuzzleId::new(2017, day, part),
+            status,
+            duration: Duration::default(),
+        }
+    }
+
+    #[test]
+    fn test_final_star() {
+        let mut entries: Vec<_> = (1..=DAYS)
+            .flat_map(|day| (1..=2).map(move |part| (day, part)))
+            .filter(|&(day, part)| day != DAYS || part != 2)
+            .map(|(day, part)| entry(day, part, PuzzleResultStatus::Match))
+            .collect();
+        let calendar = Calendar::new(2017, &entries);
+        assert_eq!(calendar.status(25, 2), Some(PuzzleResultStatus::Match));
+        assert_eq!(calendar.count(PuzzleResultStatus::Match), 50);
+
+        entries[0].status = PuzzleResultStatus::Fail;
+        let calendar = Calendar::new(2017, &entries);
+        assert_eq!(calendar.status(25, 2), None);
+        assert_eq!(calendar.count(PuzzleResultStatus::Match), 48);
+    }
+}
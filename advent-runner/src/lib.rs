@@ -0,0 +1,63 @@
+#![deny(clippy::all, clippy::pedantic)]
+#![warn(clippy::nursery)]
+#![allow(
+    // Not useful here
+    clippy::filter_map,
+    clippy::cast_sign_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_possible_wrap,
+    clippy::cast_precision_loss,
+    clippy::non_ascii_literal,
+
+    clippy::use_self, // Doesn't work well with generics
+)]
+
+//! Finding, running, and reporting on puzzles. The `advent` command is a thin
+//! layer over this, and other tools can use it the same way:
+//!
+//! ```no_run
+//! use advent_runner::{reporter::TerminalReporter, Runner};
+//!
+//! let mut reporter = TerminalReporter::new(std::io::stdout(), false);
+//! let reports = Runner::new(advent_runner::all_puzzles())
+//!     .filter("2019 P2")
+//!     .run_with(&mut reporter)
+//!     .unwrap();
+//! ```
+
+use advent_lib::cases::Puzzle;
+
+pub mod calendar;
+pub mod memory;
+pub mod plugins;
+pub mod progress;
+pub mod report;
+pub mod reporter;
+pub mod runner;
+pub mod scaffold;
+pub mod serve;
+#[cfg(test)]
+mod testing;
+#[cfg(feature = "tui")]
+pub mod tui;
+
+pub use runner::{CaseReport, Filter, Runner};
+
+/// Every puzzle from the years enabled as features.
+#[must_use]
+pub fn all_puzzles() -> Vec<Box<dyn Puzzle>> {
+    let mut puzzles = vec![];
+
+    #[cfg(feature = "year2015")]
+    puzzles.extend(year2015::get_puzzles());
+    #[cfg(feature = "year2017")]
+    puzzles.extend(year2017::get_puzzles());
+    #[cfg(feature = "year2018")]
+    puzzles.extend(year2018::get_puzzles());
+    #[cfg(feature = "year2019")]
+    puzzles.extend(year2019::get_puzzles());
+    #[cfg(feature = "year2020")]
+    puzzles.extend(year2020::get_puzzles());
+
+    puzzles
+}
@@ -0,0 +1,96 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicBool, AtomicIsize, AtomicUsize, Ordering},
+};
+
+/// A global allocator that can count allocations, to measure how much memory
+/// each case uses. Counting is off unless a measurement is in progress, so the
+/// only cost the rest of the time is checking a flag.
+pub struct CountingAllocator;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+/// Bytes allocated since the measurement started, minus bytes freed. This can
+/// go negative if memory from before the measurement is freed during it.
+static CURRENT: AtomicIsize = AtomicIsize::new(0);
+static PEAK: AtomicIsize = AtomicIsize::new(0);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+fn record(change: isize, is_new_allocation: bool) {
+    if ENABLED.load(Ordering::Relaxed) {
+        let current = CURRENT.fetch_add(change, Ordering::Relaxed) + change;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+        if is_new_allocation {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record(layout.size() as isize, true);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record(layout.size() as isize, true);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record(-(layout.size() as isize), false);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record(new_size as isize - layout.size() as isize, true);
+        }
+        new_ptr
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MemoryStats {
+    /// The most memory in use at once, beyond what was in use at the start.
+    pub peak_bytes: usize,
+    /// The number of allocations and reallocations made.
+    pub allocations: usize,
+}
+
+impl MemoryStats {
+    /// The combined memory use of cases that ran one after another, so the
+    /// peak is the largest of any of them. `None` if there are no stats.
+    pub fn combine<I: IntoIterator<Item = Self>>(stats: I) -> Option<Self> {
+        stats.into_iter().fold(None, |acc: Option<Self>, stats| {
+            let acc = acc.unwrap_or_default();
+            Some(Self {
+                peak_bytes: acc.peak_bytes.max(stats.peak_bytes),
+                allocations: acc.allocations + stats.allocations,
+            })
+        })
+    }
+}
+
+/// Run `f`, counting the memory it allocates. Allocations made by other
+/// threads at the same time are counted too.
+pub fn measure<T, F: FnOnce() -> T>(f: F) -> (T, MemoryStats) {
+    CURRENT.store(0, Ordering::Relaxed);
+    PEAK.store(0, Ordering::Relaxed);
+    ALLOCATIONS.store(0, Ordering::Relaxed);
+    ENABLED.store(true, Ordering::SeqCst);
+    let rv = f();
+    ENABLED.store(false, Ordering::SeqCst);
+
+    let stats = MemoryStats {
+        peak_bytes: PEAK.load(Ordering::Relaxed).max(0) as usize,
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+    };
+    (rv, stats)
+}
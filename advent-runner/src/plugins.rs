@@ -0,0 +1,200 @@
+//! Loading year crates that were built as plugins, so that one runner can run
+//! any set of years without being recompiled. Year crates are normally only
+//! built as Rust libraries, so build a plugin (with Cargo 1.64 or later) with
+//!
+//! ```text
+//! cargo rustc --release -p year2017 --lib --features plugin --crate-type cdylib
+//! ```
+//!
+//! and copy `target/release/libyear2017.so` (or `.dylib`, or `.dll`) into the
+//! plugin directory. Plugins are Rust code sharing Rust types with the runner,
+//! not a stable C ABI, so build them in the same workspace, with the same
+//! toolchain and profile as the runner. A plugin whose `advent-lib` build
+//! doesn't match the runner's won't be loaded.
+//!
+//! A plugin has its own global allocator, so the memory that puzzles from
+//! plugins use isn't counted by
+//! [`CountingAllocator`](crate::memory::CountingAllocator), and `advent run
+//! --memory` under-reports it.
+
+use advent_lib::{
+    cases::Puzzle,
+    plugin::{BuildIdFn, RegisterFn, Registrar, BUILD_ID, BUILD_ID_SYMBOL, REGISTER_SYMBOL},
+};
+use libloading::{Library, Symbol};
+use std::{
+    collections::HashSet,
+    env,
+    error::Error,
+    ffi::CStr,
+    fmt,
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug)]
+pub enum PluginError {
+    Load(PathBuf, libloading::Error),
+    /// The library doesn't export a registration function.
+    NotAPlugin(PathBuf),
+    /// The plugin was built with a different toolchain or `advent-lib` than
+    /// the runner, so its puzzles can't be used. Holds the plugin's build id.
+    WrongBuild(PathBuf, String),
+}
+
+impl fmt::Display for PluginError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Load(path, err) => write!(f, "Could not load {}: {}", path.display(), err),
+            Self::NotAPlugin(path) => write!(f, "{} is not a puzzle plugin", path.display()),
+            Self::WrongBuild(path, build_id) => write!(
+                f,
+                "{} was built with {}, but the runner was built with {}; rebuild it with the same \
+                 toolchain and advent-lib",
+                path.display(),
+                build_id,
+                BUILD_ID
+            ),
+        }
+    }
+}
+
+impl Error for PluginError {}
+
+/// The directory to look for plugins in if none is given: `plugins`, next to
+/// the runner's executable.
+#[must_use]
+pub fn default_dir() -> Option<PathBuf> {
+    let exe = env::current_exe().ok()?;
+    Some(exe.parent()?.join("plugins"))
+}
+
+/// Load the puzzles from one plugin.
+///
+/// The library is never unloaded, because the puzzles, and any cases they
+/// hand out, point into it.
+///
+/// # Errors
+///
+/// If the library can't be loaded, or isn't a plugin for this runner.
+pub fn load(path: &Path) -> Result<Vec<Box<dyn Puzzle>>, PluginError> {
+    let library = Library::new(path).map_err(|err| PluginError::Load(path.to_owned(), err))?;
+    let puzzles = unsafe {
+        let build_id: Symbol<BuildIdFn> = library
+            .get(BUILD_ID_SYMBOL)
+            .map_err(|_| PluginError::NotAPlugin(path.to_owned()))?;
+        let build_id = CStr::from_ptr(build_id()).to_string_lossy();
+        // Nothing else in the plugin is safe to touch unless it matches
+        if build_id != BUILD_ID {
+            return Err(PluginError::WrongBuild(
+                path.to_owned(),
+                build_id.into_owned(),
+            ));
+        }
+        let register: Symbol<RegisterFn> = library
+            .get(REGISTER_SYMBOL)
+            .map_err(|_| PluginError::NotAPlugin(path.to_owned()))?;
+        let mut registrar = Registrar::new();
+        register(&mut registrar);
+        registrar.into_puzzles()
+    };
+    std::mem::forget(library);
+    Ok(puzzles)
+}
+
+/// Load the puzzles from every plugin in `dir`, in the order of their file
+/// names. A directory that doesn't exist has no plugins.
+///
+/// # Errors
+///
+/// If the directory can't be read, or any plugin in it can't be loaded.
+pub fn load_dir(dir: &Path) -> Result<Vec<Box<dyn Puzzle>>, Box<dyn Error>> {
+    if !dir.is_dir() {
+        return Ok(vec![]);
+    }
+
+    let mut paths = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path
+            .extension()
+            .map_or(false, |ext| ext == env::consts::DLL_EXTENSION)
+        {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    let mut puzzles = vec![];
+    for path in paths {
+        puzzles.extend(load(&path)?);
+    }
+    Ok(puzzles)
+}
+
+/// Add the puzzles from plugins to `puzzles`, skipping any that are already
+/// there. A year that is both compiled in and loaded as a plugin would
+/// otherwise have two of each of its puzzles.
+pub fn merge(puzzles: &mut Vec<Box<dyn Puzzle>>, plugins: Vec<Box<dyn Puzzle>>) {
+    let known: HashSet<(String, Option<String>)> = puzzles
+        .iter()
+        .map(|puzzle| (puzzle.name(), puzzle.implementation()))
+        .collect();
+    puzzles.extend(
+        plugins
+            .into_iter()
+            .filter(|puzzle| !known.contains(&(puzzle.name(), puzzle.implementation()))),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent_lib::cases::PuzzleResultStatus;
+
+    /// The plugin built from `examples/test_plugin.rs`, next to the tests.
+    fn test_plugin() -> PathBuf {
+        let exe = env::current_exe().unwrap();
+        let examples = exe
+            .parent()
+            .and_then(Path::parent)
+            .unwrap()
+            .join("examples");
+        examples.join(format!(
+            "{}test_plugin{}",
+            env::consts::DLL_PREFIX,
+            env::consts::DLL_SUFFIX
+        ))
+    }
+
+    #[test]
+    fn test_load() {
+        let path = test_plugin();
+        assert!(
+            path.exists(),
+            "{} hasn't been built, run the tests with `cargo test` to build it",
+            path.display()
+        );
+        let puzzles = load(&path).unwrap();
+        let results: Vec<_> = puzzles
+            .iter()
+            .map(|puzzle| {
+                let cases = puzzle.cases().unwrap();
+                (puzzle.name(), cases[0].run().status)
+            })
+            .collect();
+        assert_eq!(
+            results,
+            vec![
+                ("2015-D01-P1".to_owned(), PuzzleResultStatus::Match),
+                ("2015-D01-P2".to_owned(), PuzzleResultStatus::Todo),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_load_not_a_plugin() {
+        let err = load(Path::new("no-such-plugin.so")).unwrap_err();
+        assert!(matches!(err, PluginError::Load(..)), "{:?}", err);
+    }
+}
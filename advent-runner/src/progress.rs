@@ -0,0 +1,76 @@
+use advent_lib::{
+    cases::{PuzzleCase, PuzzleResult},
+    context::RunContext,
+};
+use atty::Stream;
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+/// Run a single case, showing a progress bar on stderr while it runs, if
+/// stderr is a terminal. Elsewhere, such as in CI logs or a file, every
+/// redraw would stay in the output.
+pub fn run_case(case: &dyn PuzzleCase, label: String, ctx: RunContext) -> PuzzleResult {
+    if !atty::is(Stream::Stderr) {
+        return case.run_with_context(&ctx);
+    }
+    let progress = ProgressBar::new(label);
+    let result = case.run_with_context(&progress.attach(ctx));
+    progress.clear();
+    result
+}
+
+/// A progress bar for a single case, drawn on stderr while the case runs.
+struct ProgressBar {
+    label: String,
+    /// The last percentage drawn, plus one. Zero means nothing has been drawn.
+    drawn: Arc<AtomicUsize>,
+}
+
+impl ProgressBar {
+    const WIDTH: usize = 30;
+    const DELAY: Duration = Duration::from_millis(250);
+
+    fn new(label: String) -> Self {
+        Self {
+            label,
+            drawn: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// `ctx`, drawing this bar when the case reports progress.
+    fn attach(&self, ctx: RunContext) -> RunContext {
+        let label = self.label.clone();
+        let drawn = self.drawn.clone();
+        let start = Instant::now();
+        ctx.with_progress(move |done, total| {
+            // Don't flash a bar for cases that finish quickly
+            if start.elapsed() < Self::DELAY {
+                return;
+            }
+            let percent = (done * 100).checked_div(total).unwrap_or(0).min(100);
+            // Only redraw when the bar has visibly changed
+            if drawn.swap(percent + 1, Ordering::Relaxed) != percent + 1 {
+                let filled = percent * Self::WIDTH / 100;
+                eprint!(
+                    "\r{} [{}{}] {:>3}%",
+                    label,
+                    "#".repeat(filled),
+                    " ".repeat(Self::WIDTH - filled),
+                    percent
+                );
+            }
+        })
+    }
+
+    /// Erase the bar, if it was ever drawn.
+    fn clear(&self) {
+        if self.drawn.load(Ordering::Relaxed) > 0 {
+            eprint!("\r{}\r", " ".repeat(self.label.len() + Self::WIDTH + 8));
+        }
+    }
+}
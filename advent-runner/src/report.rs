@@ -0,0 +1,347 @@
+use advent_lib::{cases::PuzzleResultStatus, id::PuzzleId};
+use std::{
+    cmp,
+    collections::BTreeMap,
+    fmt::{self, Write},
+    str::FromStr,
+    time::Duration,
+};
+
+/// How many puzzles to list in the "slowest" section of a report.
+const SLOWEST_COUNT: usize = 10;
+
+#[derive(Clone, Copy, Debug)]
+pub enum ReportFormat {
+    Markdown,
+    Csv,
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "markdown" | "md" => Ok(Self::Markdown),
+            "csv" => Ok(Self::Csv),
+            _ => Err(format!(
+                "Unknown report format {}, expected markdown or csv",
+                s
+            )),
+        }
+    }
+}
+
+/// The combined result of all of the cases of one puzzle.
+#[derive(Clone, Copy, Debug)]
+pub struct ReportEntry {
+    pub id: PuzzleId,
+    pub status: PuzzleResultStatus,
+    pub duration: Duration,
+}
+
+impl ReportEntry {
+    /// Combine the results of a puzzle's cases. The puzzle gets the most
+    /// serious status of any of its cases, and the total of their durations.
+    /// A puzzle without any cases hasn't been solved, so it's `Todo`.
+    pub fn new<I>(id: PuzzleId, results: I) -> Self
+    where
+        I: IntoIterator<Item = (PuzzleResultStatus, Duration)>,
+    {
+        let mut status = None;
+        let mut duration = Duration::default();
+        for (result, time) in results {
+            duration += time;
+            status = match status {
+                Some(status) if severity(status) >= severity(result) => Some(status),
+                _ => Some(result),
+            };
+        }
+        Self {
+            id,
+            status: status.unwrap_or(PuzzleResultStatus::Todo),
+            duration,
+        }
+    }
+}
+
+/// How serious a status is, so that one result can stand for several.
+pub const fn severity(status: PuzzleResultStatus) -> u8 {
+    match status {
+        PuzzleResultStatus::Match => 0,
+        PuzzleResultStatus::Todo => 1,
+        PuzzleResultStatus::Unknown => 2,
+        PuzzleResultStatus::Cancelled => 3,
+        PuzzleResultStatus::Fail => 4,
+        PuzzleResultStatus::Error => 5,
+    }
+}
+
+pub const fn status_symbol(status: PuzzleResultStatus) -> &'static str {
+    match status {
+        PuzzleResultStatus::Match => "✔",
+        PuzzleResultStatus::Unknown => "?",
+        PuzzleResultStatus::Fail => "✗",
+        PuzzleResultStatus::Error => "!",
+        PuzzleResultStatus::Todo => "-",
+        PuzzleResultStatus::Cancelled => "~",
+    }
+}
+
+pub const fn status_name(status: PuzzleResultStatus) -> &'static str {
+    match status {
+        PuzzleResultStatus::Match => "pass",
+        PuzzleResultStatus::Unknown => "unknown",
+        PuzzleResultStatus::Fail => "fail",
+        PuzzleResultStatus::Error => "error",
+        PuzzleResultStatus::Todo => "todo",
+        PuzzleResultStatus::Cancelled => "cancelled",
+    }
+}
+
+/// The status with the name given by `status_name`.
+#[must_use]
+pub fn status_from_name(name: &str) -> Option<PuzzleResultStatus> {
+    [
+        PuzzleResultStatus::Match,
+        PuzzleResultStatus::Unknown,
+        PuzzleResultStatus::Fail,
+        PuzzleResultStatus::Error,
+        PuzzleResultStatus::Todo,
+        PuzzleResultStatus::Cancelled,
+    ]
+    .iter()
+    .copied()
+    .find(|status| status_name(*status) == name)
+}
+
+/// A duration in the largest unit that keeps it readable.
+struct HumanDuration(Duration);
+
+impl fmt::Display for HumanDuration {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let micros = self.0.as_micros();
+        if micros < 1_000 {
+            write!(fmt, "{} μs", micros)
+        } else if micros < 1_000_000 {
+            write!(fmt, "{:.1} ms", self.0.as_secs_f64() * 1_000.0)
+        } else {
+            write!(fmt, "{:.2} s", self.0.as_secs_f64())
+        }
+    }
+}
+
+/// Entries grouped by year, then by day, with a slot for each part.
+type Calendar<'a> = BTreeMap<u16, BTreeMap<u8, [Option<&'a ReportEntry>; 2]>>;
+
+fn calendar(entries: &[ReportEntry]) -> Calendar<'_> {
+    let mut calendar = Calendar::new();
+    for entry in entries {
+        let parts = calendar
+            .entry(entry.id.year)
+            .or_default()
+            .entry(entry.id.day)
+            .or_default();
+        if let Some(slot) = parts.get_mut(usize::from(entry.id.part).wrapping_sub(1)) {
+            *slot = Some(entry);
+        }
+    }
+    calendar
+}
+
+fn total_duration<'a, I: IntoIterator<Item = &'a ReportEntry>>(entries: I) -> Duration {
+    entries.into_iter().map(|entry| entry.duration).sum()
+}
+
+/// A Markdown report with a table for each year, a table of yearly totals, and
+/// a list of the slowest puzzles.
+pub fn markdown(entries: &[ReportEntry]) -> String {
+    let mut out = String::new();
+    let calendar = calendar(entries);
+
+    for (year, days) in &calendar {
+        writeln!(out, "## {}\n", year).unwrap();
+        writeln!(out, "| Day | Part 1 | Part 2 |").unwrap();
+        writeln!(out, "| --: | -----: | -----: |").unwrap();
+        for (day, parts) in days {
+            let cells: Vec<String> = parts
+                .iter()
+                .map(|part| match part {
+                    Some(entry) => format!(
+                        "{} {}",
+                        HumanDuration(entry.duration),
+                        status_symbol(entry.status)
+                    ),
+                    None => String::new(),
+                })
+                .collect();
+            writeln!(out, "| {} | {} |", day, cells.join(" | ")).unwrap();
+        }
+        let part_totals: Vec<String> = (0..2)
+            .map(|idx| {
+                let total = total_duration(days.values().filter_map(|parts| parts[idx]));
+                format!("**{}**", HumanDuration(total))
+            })
+            .collect();
+        writeln!(out, "| **Total** | {} |\n", part_totals.join(" | ")).unwrap();
+    }
+
+    writeln!(out, "## Totals\n").unwrap();
+    writeln!(out, "| Year | Puzzles | Passing | Time |").unwrap();
+    writeln!(out, "| ---: | ------: | ------: | ---: |").unwrap();
+    for year in calendar.keys() {
+        let year_entries: Vec<_> = entries.iter().filter(|e| e.id.year == *year).collect();
+        writeln!(
+            out,
+            "| {} | {} | {} | {} |",
+            year,
+            year_entries.len(),
+            year_entries
+                .iter()
+                .filter(|e| e.status == PuzzleResultStatus::Match)
+                .count(),
+            HumanDuration(total_duration(year_entries.iter().copied()))
+        )
+        .unwrap();
+    }
+    writeln!(
+        out,
+        "| **All** | **{}** | **{}** | **{}** |\n",
+        entries.len(),
+        entries
+            .iter()
+            .filter(|e| e.status == PuzzleResultStatus::Match)
+            .count(),
+        HumanDuration(total_duration(entries))
+    )
+    .unwrap();
+
+    writeln!(out, "## Slowest {}\n", SLOWEST_COUNT).unwrap();
+    writeln!(out, "| Puzzle | Time |").unwrap();
+    writeln!(out, "| ------ | ---: |").unwrap();
+    for entry in slowest(entries) {
+        writeln!(
+            out,
+            "| {} | {} {} |",
+            entry.id,
+            HumanDuration(entry.duration),
+            status_symbol(entry.status)
+        )
+        .unwrap();
+    }
+
+    out
+}
+
+/// A CSV report with a row for each day, for tracking timings over time.
+/// Durations are in microseconds, and totals are left to the spreadsheet.
+pub fn csv(entries: &[ReportEntry]) -> String {
+    let mut out = String::from("year,day,part1_us,part1_status,part2_us,part2_status\n");
+    for (year, days) in calendar(entries) {
+        for (day, parts) in days {
+            let cells: Vec<String> = parts
+                .iter()
+                .map(|part| match part {
+                    Some(entry) => format!(
+                        "{},{}",
+                        entry.duration.as_micros(),
+                        status_name(entry.status)
+                    ),
+                    None => ",".to_owned(),
+                })
+                .collect();
+            writeln!(out, "{},{},{}", year, day, cells.join(",")).unwrap();
+        }
+    }
+    out
+}
+
+/// Read the entries back out of a CSV report. Rows that can't be read are
+/// skipped.
+#[must_use]
+pub fn read_csv(csv: &str) -> Vec<ReportEntry> {
+    let mut entries = vec![];
+    for row in csv.lines().skip(1) {
+        let cells: Vec<&str> = row.split(',').collect();
+        let (year, day) = match (cells.get(0), cells.get(1)) {
+            (Some(year), Some(day)) => match (year.parse(), day.parse()) {
+                (Ok(year), Ok(day)) => (year, day),
+                _ => continue,
+            },
+            _ => continue,
+        };
+        for part in 1..=2 {
+            let idx = usize::from(part) * 2;
+            let micros = cells.get(idx).and_then(|micros| micros.parse().ok());
+            let status = cells.get(idx + 1).and_then(|name| status_from_name(name));
+            if let (Some(micros), Some(status)) = (micros, status) {
+                entries.push(ReportEntry {
+                    id: PuzzleId::new(year, day, part),
+                    status,
+                    duration: Duration::from_micros(micros),
+                });
+            }
+        }
+    }
+    entries
+}
+
+fn slowest(entries: &[ReportEntry]) -> Vec<&ReportEntry> {
+    let mut sorted: Vec<_> = entries.iter().collect();
+    sorted.sort_by_key(|entry| cmp::Reverse(entry.duration));
+    sorted.truncate(SLOWEST_COUNT);
+    sorted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_csv() {
+        let entries = vec![
+            ReportEntry {
+                id: PuzzleId::new(2017, 24, 1),
+                status: PuzzleResultStatus::Match,
+                duration: Duration::from_micros(120),
+            },
+            ReportEntry {
+                id: PuzzleId::new(2017, 24, 2),
+                status: PuzzleResultStatus::Cancelled,
+                duration: Duration::from_micros(4000),
+            },
+            ReportEntry {
+                id: PuzzleId::new(2017, 25, 1),
+                status: PuzzleResultStatus::Fail,
+                duration: Duration::from_micros(35),
+            },
+        ];
+        let read = read_csv(&csv(&entries));
+        assert_eq!(read.len(), 3);
+        for (entry, read) in entries.iter().zip(&read) {
+            assert_eq!(entry.id, read.id);
+            assert_eq!(entry.status, read.status);
+            assert_eq!(entry.duration, read.duration);
+        }
+
+        assert!(read_csv("year,day\nnot,a number\n").is_empty());
+    }
+
+    #[test]
+    fn test_entry_status() {
+        let id = PuzzleId::new(2017, 24, 1);
+        let entry = ReportEntry::new(
+            id,
+            vec![
+                (PuzzleResultStatus::Match, Duration::from_micros(10)),
+                (PuzzleResultStatus::Fail, Duration::from_micros(5)),
+                (PuzzleResultStatus::Match, Duration::from_micros(1)),
+            ],
+        );
+        assert_eq!(entry.status, PuzzleResultStatus::Fail);
+        assert_eq!(entry.duration, Duration::from_micros(16));
+
+        let entry = ReportEntry::new(id, vec![]);
+        assert_eq!(entry.status, PuzzleResultStatus::Todo);
+        assert_eq!(entry.duration, Duration::default());
+    }
+}
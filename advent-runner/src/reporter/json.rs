@@ -0,0 +1,132 @@
+use super::Reporter;
+use crate::{report::status_name, runner::CaseReport};
+use advent_lib::cases::PuzzleResult;
+use serde_json::{json, Value};
+use std::io;
+
+/// Writes a JSON array with an object for each puzzle, holding the results of
+/// its cases.
+pub struct JsonReporter<W> {
+    out: W,
+    puzzles: Vec<Value>,
+}
+
+impl<W: io::Write> JsonReporter<W> {
+    pub const fn new(out: W) -> Self {
+        Self {
+            out,
+            puzzles: vec![],
+        }
+    }
+}
+
+impl<W: io::Write> Reporter for JsonReporter<W> {
+    fn puzzle(&mut self, cases: &[CaseReport]) -> io::Result<()> {
+        if let Some(first) = cases.first() {
+            let cases: Vec<Value> = cases
+                .iter()
+                .map(|report| {
+                    let mut value = result_json(&report.case, &report.result);
+                    if let Some(memory) = report.memory {
+                        value["peak_bytes"] = json!(memory.peak_bytes);
+                        value["allocations"] = json!(memory.allocations);
+                    }
+                    value
+                })
+                .collect();
+            self.puzzles.push(json!({
+                "name": first.puzzle,
+                "implementation": first.implementation,
+                "cases": cases,
+            }));
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        serde_json::to_writer_pretty(&mut self.out, &self.puzzles)?;
+        writeln!(self.out)
+    }
+}
+
+/// A case's result as a JSON object.
+pub(crate) fn result_json(case: &str, result: &PuzzleResult) -> Value {
+    json!({
+        "name": case,
+        "status": status_name(result.status),
+        "answer": result.answer,
+        "description": result.description,
+        "duration_us": result.duration.as_micros() as u64,
+        "prepare_us": result.prepare.map(|prepare| prepare.as_micros() as u64),
+        "location": result.location.as_ref().map(|location| json!({
+            "line": location.line,
+            "column": location.column,
+            "text": location.text,
+        })),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{memory::MemoryStats, runner::Runner, testing::StubPuzzle};
+
+    #[test]
+    fn test_json_reporter() {
+        let mut reports = Runner::new(vec![
+            Box::new(StubPuzzle::new("2015-D01-P1").case("Example", "3")),
+            Box::new(
+                StubPuzzle::new("2015-D01-P2")
+                    .implementation("fast")
+                    .wrong_case("Solution", "1783"),
+            ),
+        ])
+        .run();
+        reports[0].memory = Some(MemoryStats {
+            peak_bytes: 1024,
+            allocations: 3,
+        });
+
+        let mut out = vec![];
+        let mut reporter = JsonReporter::new(&mut out);
+        reporter.puzzle(&reports[..1]).unwrap();
+        reporter.puzzle(&reports[1..]).unwrap();
+        reporter.puzzle(&[]).unwrap();
+        reporter.finish().unwrap();
+
+        let output: Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(
+            output,
+            json!([
+                {
+                    "name": "2015-D01-P1",
+                    "implementation": null,
+                    "cases": [{
+                        "name": "Example",
+                        "status": "pass",
+                        "answer": "3",
+                        "description": "3 is correct",
+                        "duration_us": 10,
+                        "prepare_us": null,
+                        "location": null,
+                        "peak_bytes": 1024,
+                        "allocations": 3,
+                    }],
+                },
+                {
+                    "name": "2015-D01-P2",
+                    "implementation": "fast",
+                    "cases": [{
+                        "name": "Solution",
+                        "status": "fail",
+                        "answer": "1783",
+                        "description": "1783 is wrong",
+                        "duration_us": 10,
+                        "prepare_us": null,
+                        "location": null,
+                    }],
+                },
+            ])
+        );
+    }
+}
@@ -0,0 +1,192 @@
+use super::Reporter;
+use crate::runner::CaseReport;
+use advent_lib::cases::PuzzleResultStatus;
+use std::{fmt::Write as _, io, time::Duration};
+
+/// Writes a JUnit XML report, with a test suite for each puzzle and a test
+/// case for each of its cases, for CI systems to show.
+pub struct JunitReporter<W> {
+    out: W,
+    suites: String,
+    totals: Counts,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Counts {
+    tests: usize,
+    failures: usize,
+    errors: usize,
+    skipped: usize,
+    time: Duration,
+}
+
+impl Counts {
+    fn attributes(&self) -> String {
+        format!(
+            "tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{:.6}\"",
+            self.tests,
+            self.failures,
+            self.errors,
+            self.skipped,
+            self.time.as_secs_f64()
+        )
+    }
+}
+
+impl<W: io::Write> JunitReporter<W> {
+    pub fn new(out: W) -> Self {
+        Self {
+            out,
+            suites: String::new(),
+            totals: Counts::default(),
+        }
+    }
+}
+
+impl<W: io::Write> Reporter for JunitReporter<W> {
+    fn puzzle(&mut self, cases: &[CaseReport]) -> io::Result<()> {
+        let first = match cases.first() {
+            Some(first) => first,
+            None => return Ok(()),
+        };
+
+        let mut counts = Counts::default();
+        let mut testcases = String::new();
+        for report in cases {
+            let result = &report.result;
+            counts.tests += 1;
+            counts.time += result.duration;
+            write!(
+                testcases,
+                "    <testcase classname=\"{}\" name=\"{}\" time=\"{:.6}\"",
+                escape_xml(&report.puzzle),
+                escape_xml(&report.case),
+                result.duration.as_secs_f64()
+            )
+            .unwrap();
+            let (element, message) = match result.status {
+                PuzzleResultStatus::Match => ("", ""),
+                PuzzleResultStatus::Fail => {
+                    counts.failures += 1;
+                    ("failure", result.description.as_str())
+                }
+                PuzzleResultStatus::Error | PuzzleResultStatus::Cancelled => {
+                    counts.errors += 1;
+                    ("error", result.description.as_str())
+                }
+                PuzzleResultStatus::Todo | PuzzleResultStatus::Unknown => {
+                    counts.skipped += 1;
+                    ("skipped", result.description.as_str())
+                }
+            };
+            if element.is_empty() {
+                testcases.push_str("/>\n");
+            } else {
+                writeln!(
+                    testcases,
+                    ">\n      <{} message=\"{}\"/>\n    </testcase>",
+                    element,
+                    escape_xml(message)
+                )
+                .unwrap();
+            }
+        }
+
+        writeln!(
+            self.suites,
+            "  <testsuite name=\"{}\" {}>\n{}  </testsuite>",
+            escape_xml(&first.puzzle),
+            counts.attributes(),
+            testcases
+        )
+        .unwrap();
+
+        self.totals.tests += counts.tests;
+        self.totals.failures += counts.failures;
+        self.totals.errors += counts.errors;
+        self.totals.skipped += counts.skipped;
+        self.totals.time += counts.time;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        writeln!(self.out, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+        writeln!(
+            self.out,
+            "<testsuites name=\"advent\" {}>",
+            self.totals.attributes()
+        )?;
+        write!(self.out, "{}", self.suites)?;
+        writeln!(self.out, "</testsuites>")?;
+        self.out.flush()
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\n', "&#10;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        runner::{group_by_puzzle, Runner},
+        testing::StubPuzzle,
+    };
+
+    #[test]
+    fn test_junit_reporter() {
+        let reports = Runner::new(vec![
+            Box::new(
+                StubPuzzle::new("2015-D01-P1")
+                    .case("Example", "3")
+                    .wrong_case("Solution", "<1>"),
+            ),
+            Box::new(StubPuzzle::new("2015-D02-P1").broken("No \"input\"")),
+        ])
+        .run();
+
+        let mut out = vec![];
+        let mut reporter = JunitReporter::new(&mut out);
+        for puzzle in group_by_puzzle(&reports) {
+            reporter.puzzle(puzzle).unwrap();
+        }
+        reporter.finish().unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            concat!(
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+                "<testsuites name=\"advent\" tests=\"3\" failures=\"1\" errors=\"1\" \
+                 skipped=\"0\" time=\"0.000020\">\n",
+                "  <testsuite name=\"2015-D01-P1\" tests=\"2\" failures=\"1\" errors=\"0\" \
+                 skipped=\"0\" time=\"0.000020\">\n",
+                "    <testcase classname=\"2015-D01-P1\" name=\"Example\" time=\"0.000010\"/>\n",
+                "    <testcase classname=\"2015-D01-P1\" name=\"Solution\" time=\"0.000010\">\n",
+                "      <failure message=\"&lt;1&gt; is wrong\"/>\n",
+                "    </testcase>\n",
+                "  </testsuite>\n",
+                "  <testsuite name=\"2015-D02-P1\" tests=\"1\" failures=\"0\" errors=\"1\" \
+                 skipped=\"0\" time=\"0.000000\">\n",
+                "    <testcase classname=\"2015-D02-P1\" name=\"Cases\" time=\"0.000000\">\n",
+                "      <error message=\"Could not determine cases: No &quot;input&quot;\"/>\n",
+                "    </testcase>\n",
+                "  </testsuite>\n",
+                "</testsuites>\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(
+            escape_xml("<a href=\"x\">Tom & Jerry</a>\nend"),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&lt;/a&gt;&#10;end"
+        );
+        assert_eq!(escape_xml("plain"), "plain");
+    }
+}
@@ -0,0 +1,72 @@
+use crate::runner::CaseReport;
+use std::{io, str::FromStr};
+
+mod json;
+mod junit;
+mod terminal;
+
+pub(crate) use json::result_json;
+pub use json::JsonReporter;
+pub use junit::JunitReporter;
+pub use terminal::{
+    format_memory,
+    format_sum_duration,
+    status_label,
+    write_cross_check,
+    TerminalReporter,
+};
+
+/// Something that shows the results of a run, one puzzle at a time.
+pub trait Reporter {
+    /// Report on the cases of one puzzle, once they have all run.
+    ///
+    /// # Errors
+    ///
+    /// If the report can't be written.
+    fn puzzle(&mut self, cases: &[CaseReport]) -> io::Result<()>;
+
+    /// Finish the report, after the last puzzle. Reporters that can't write
+    /// anything until they have seen every puzzle write it all here.
+    ///
+    /// # Errors
+    ///
+    /// If the report can't be written.
+    fn finish(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// The kinds of reporter that `advent run` can use.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    Terminal,
+    Json,
+    Junit,
+}
+
+impl OutputFormat {
+    /// A reporter of this kind that writes to `out`.
+    pub fn reporter<'a, W: io::Write + 'a>(self, out: W, verbose: bool) -> Box<dyn Reporter + 'a> {
+        match self {
+            Self::Terminal => Box::new(TerminalReporter::new(out, verbose)),
+            Self::Json => Box::new(JsonReporter::new(out)),
+            Self::Junit => Box::new(JunitReporter::new(out)),
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "terminal" => Ok(Self::Terminal),
+            "json" => Ok(Self::Json),
+            "junit" => Ok(Self::Junit),
+            _ => Err(format!(
+                "Unknown output format {}, expected terminal, json, or junit",
+                s
+            )),
+        }
+    }
+}
@@ -0,0 +1,251 @@
+use super::Reporter;
+use crate::{
+    memory::MemoryStats,
+    runner::{CaseReport, CrossCheck},
+};
+use advent_lib::{cases::PuzzleResultStatus, diff::DiffLine, error::ErrorLocation};
+use colored::{ColoredString, Colorize};
+use num_format::{Locale, ToFormattedString};
+use std::{fmt, io, time::Duration};
+
+/// Shows results in color for people to read, with a line for each puzzle, or
+/// in verbose mode a line for each case.
+pub struct TerminalReporter<W> {
+    out: W,
+    verbose: bool,
+}
+
+impl<W: io::Write> TerminalReporter<W> {
+    pub const fn new(out: W, verbose: bool) -> Self {
+        Self { out, verbose }
+    }
+
+    fn verbose_puzzle(&mut self, cases: &[CaseReport]) -> io::Result<()> {
+        writeln!(self.out, "{:<12}", cases[0].puzzle)?;
+        for CaseReport {
+            case,
+            result,
+            memory,
+            ..
+        } in cases
+        {
+            write!(self.out, "    {} ", status_label(result.status))?;
+            write!(self.out, "{:<10} ", case)?;
+            match result.status {
+                PuzzleResultStatus::Match => match &result.answer {
+                    // Drawings are shown below the case instead
+                    Some(answer) if answer.contains('\n') => {
+                        write!(self.out, " -> {:<12}", "(drawing)")?
+                    }
+                    Some(answer) => write!(self.out, " -> {:<12}", answer)?,
+                    None => write!(self.out, " -> {}", result.description)?,
+                },
+                PuzzleResultStatus::Unknown
+                | PuzzleResultStatus::Fail
+                | PuzzleResultStatus::Error
+                | PuzzleResultStatus::Todo
+                | PuzzleResultStatus::Cancelled => write!(self.out, " -> {}", result.description)?,
+            }
+            write!(self.out, "{}", format_sum_duration(&[result.duration]))?;
+            if let Some(prepare) = result.prepare {
+                write!(self.out, "+{}prepare ", format_sum_duration(&[prepare]))?;
+            }
+            if let Some(memory) = memory {
+                write!(self.out, "{}", format_memory(*memory))?;
+            }
+            writeln!(self.out)?;
+            if let Some(mismatch) = &result.mismatch {
+                write_diff(&mut self.out, mismatch.diff())?;
+            } else if let Some(location) = &result.location {
+                write_location(&mut self.out, location)?;
+            } else if let Some(answer) = result.answer.as_ref().filter(|a| a.contains('\n')) {
+                for line in answer.lines() {
+                    writeln!(self.out, "        {}", line)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn compact_puzzle(&mut self, cases: &[CaseReport]) -> io::Result<()> {
+        write!(self.out, "{:<12}", cases[0].puzzle)?;
+        for CaseReport { result, .. } in cases {
+            let symbol = match result.status {
+                PuzzleResultStatus::Match => "✔".green(),
+                PuzzleResultStatus::Unknown => "?".yellow(),
+                PuzzleResultStatus::Fail => "✗".red(),
+                PuzzleResultStatus::Error => "!".black().on_red(),
+                PuzzleResultStatus::Todo => "-".cyan(),
+                PuzzleResultStatus::Cancelled => "~".magenta(),
+            };
+            write!(self.out, "{}", symbol)?;
+        }
+
+        let spacer = (cases.len()..10).map(|_| " ").collect::<String>();
+        let durations: Vec<Duration> = cases.iter().map(|c| c.result.duration).collect();
+        write!(self.out, "{}{}", spacer, format_sum_duration(&durations))?;
+        if let Some(memory) = MemoryStats::combine(cases.iter().filter_map(|c| c.memory)) {
+            write!(self.out, "{}", format_memory(memory))?;
+        }
+        writeln!(self.out)?;
+
+        for CaseReport { case, result, .. } in cases {
+            if result.status != PuzzleResultStatus::Match {
+                writeln!(
+                    self.out,
+                    "   {} {:<10} -> {}",
+                    status_label(result.status),
+                    case,
+                    result.description
+                )?;
+                if let Some(mismatch) = &result.mismatch {
+                    write_diff(&mut self.out, mismatch.diff())?;
+                }
+                if let Some(location) = &result.location {
+                    write_location(&mut self.out, location)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<W: io::Write> Reporter for TerminalReporter<W> {
+    fn puzzle(&mut self, cases: &[CaseReport]) -> io::Result<()> {
+        if cases.is_empty() {
+            Ok(())
+        } else if self.verbose {
+            self.verbose_puzzle(cases)
+        } else {
+            self.compact_puzzle(cases)
+        }
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+}
+
+/// Show a wrong answer as a diff against the expected answer, with lines that
+/// were expected but missing in red, and unexpected lines in green.
+fn write_diff<W: io::Write>(out: &mut W, diff: Vec<DiffLine>) -> io::Result<()> {
+    for line in diff {
+        match line {
+            DiffLine::Same(line) => writeln!(out, "        {}", line)?,
+            DiffLine::Expected(line) => writeln!(out, "      {} {}", "-".red(), line.red())?,
+            DiffLine::Actual(line) => writeln!(out, "      {} {}", "+".green(), line.green())?,
+        }
+    }
+    Ok(())
+}
+
+/// Show the line of the input that an error is about, marking the column if
+/// it is known.
+fn write_location<W: io::Write>(out: &mut W, location: &ErrorLocation) -> io::Result<()> {
+    let number = location.line.to_string();
+    writeln!(out, "      {} | {}", number.blue(), location.text)?;
+    if let Some(column) = location.column {
+        let gutter = " ".repeat(number.len());
+        let padding = " ".repeat(column.saturating_sub(1));
+        writeln!(out, "      {} | {}{}", gutter, padding, "^".red())?;
+    }
+    Ok(())
+}
+
+/// Show whether the implementations of each puzzle agree on each case.
+///
+/// # Errors
+///
+/// If the output can't be written.
+pub fn write_cross_check<W: io::Write>(mut out: W, checks: &[CrossCheck]) -> io::Result<()> {
+    if checks.is_empty() {
+        writeln!(out, "No puzzles have more than one implementation")?;
+    }
+
+    let mut last_puzzle = None;
+    for check in checks {
+        if last_puzzle != Some(&check.puzzle) {
+            writeln!(out, "{:<12}", check.puzzle)?;
+            last_puzzle = Some(&check.puzzle);
+        }
+
+        let agree = check.agrees();
+        write!(
+            out,
+            "    {} {:<10}",
+            if agree { "SAME".green() } else { "DIFF".red() },
+            check.case
+        )?;
+        for (implementation, result) in &check.results {
+            write!(
+                out,
+                " {} {:<12} {}",
+                status_label(result.status),
+                implementation,
+                format_sum_duration(&[result.duration])
+            )?;
+        }
+        writeln!(out)?;
+
+        if !agree {
+            for (implementation, result) in &check.results {
+                writeln!(
+                    out,
+                    "        {:<16} -> {}",
+                    implementation, result.description
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[must_use]
+pub fn status_label(status: PuzzleResultStatus) -> ColoredString {
+    match status {
+        PuzzleResultStatus::Match => "PASS".green(),
+        PuzzleResultStatus::Unknown => "UNKO".yellow(),
+        PuzzleResultStatus::Fail => "FAIL".red(),
+        PuzzleResultStatus::Error => "ERRO".black().on_red(),
+        PuzzleResultStatus::Todo => "TODO".cyan(),
+        PuzzleResultStatus::Cancelled => "CNCL".magenta(),
+    }
+}
+
+#[must_use]
+pub fn format_memory(memory: MemoryStats) -> impl fmt::Display {
+    let bytes = memory.peak_bytes as f64;
+    let peak = match memory.peak_bytes {
+        b if b < 1 << 10 => format!("{} B", b),
+        b if b < 1 << 20 => format!("{:.1} KiB", bytes / f64::from(1 << 10)),
+        b if b < 1 << 30 => format!("{:.1} MiB", bytes / f64::from(1 << 20)),
+        _ => format!("{:.1} GiB", bytes / f64::from(1 << 30)),
+    };
+    let s = format!(
+        "{:>10} peak {:>11} allocs ",
+        peak,
+        memory.allocations.to_formatted_string(&Locale::en)
+    );
+    match memory.peak_bytes {
+        b if b < 1 << 20 => s.bright_black(),
+        b if b < 10 << 20 => s.blue(),
+        b if b < 100 << 20 => s.yellow(),
+        b if b < 1 << 30 => s.red(),
+        _ => s.black().on_red(),
+    }
+}
+
+#[must_use]
+pub fn format_sum_duration(ds: &[Duration]) -> impl fmt::Display {
+    let sum: u128 = ds.iter().map(Duration::as_micros).sum();
+    let s = sum.to_formatted_string(&Locale::en);
+    let s = format!("{:>10} μs ", s);
+    match sum {
+        0 => s.bright_black(),
+        d if d < 1_000 => s.bright_black(),
+        d if d < 10_000 => s.blue(),
+        d if d < 100_000 => s.yellow(),
+        d if d < 1_000_000 => s.red(),
+        _ => s.black().on_red(),
+    }
+}
@@ -0,0 +1,666 @@
+use crate::{
+    memory::{self, MemoryStats},
+    progress,
+    reporter::Reporter,
+};
+use advent_lib::{
+    cases::{Puzzle, PuzzleCase, PuzzleResult, PuzzleResultStatus},
+    context::RunContext,
+    error::ErrorLocation,
+};
+use std::{
+    collections::HashSet,
+    io,
+    panic::{self, AssertUnwindSafe},
+    time::Duration,
+};
+
+/// Which puzzles and cases to use. Every word of the filter has to appear in
+/// the puzzle's name or the case's name, ignoring case. An empty filter
+/// matches everything.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Filter {
+    words: Vec<String>,
+}
+
+impl Filter {
+    /// A filter from one or more strings of space separated words.
+    pub fn new<I, S>(filters: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut filter = Self::default();
+        for f in filters {
+            filter.add(f.as_ref());
+        }
+        filter
+    }
+
+    fn add(&mut self, filter: &str) {
+        self.words.extend(
+            filter
+                .split(' ')
+                .filter(|word| !word.is_empty())
+                .map(str::to_lowercase),
+        );
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Whether the case of the puzzle matches the filter.
+    #[must_use]
+    pub fn matches(&self, puzzle_name: &str, case_name: &str) -> bool {
+        let haystack = format!("{} {}", puzzle_name, case_name).to_lowercase();
+        self.words.iter().all(|word| haystack.contains(word))
+    }
+
+    /// Whether the puzzle's name matches the filter by itself.
+    #[must_use]
+    pub fn matches_puzzle(&self, puzzle_name: &str) -> bool {
+        let haystack = puzzle_name.to_lowercase();
+        self.words.iter().all(|word| haystack.contains(word))
+    }
+
+    /// Whether the filter as a whole, rather than word by word, appears in
+    /// the puzzle and case names.
+    #[must_use]
+    pub fn matches_whole(&self, puzzle_name: &str, case_name: &str) -> bool {
+        format!("{} {}", puzzle_name, case_name)
+            .to_lowercase()
+            .contains(&self.words.join(" "))
+    }
+}
+
+/// The outcome of running one case.
+#[derive(Clone, Debug)]
+pub struct CaseReport {
+    pub puzzle: String,
+    /// The name of the puzzle's implementation, for puzzles with more than one.
+    pub implementation: Option<String>,
+    pub case: String,
+    pub result: PuzzleResult,
+    /// How much memory the case used, if it was measured.
+    pub memory: Option<MemoryStats>,
+}
+
+/// The answers of each implementation of a puzzle for one of its cases.
+#[derive(Debug)]
+pub struct CrossCheck {
+    pub puzzle: String,
+    pub case: String,
+    /// The name of each implementation and its result.
+    pub results: Vec<(String, PuzzleResult)>,
+}
+
+impl CrossCheck {
+    /// Whether every implementation gave the same answer.
+    #[must_use]
+    pub fn agrees(&self) -> bool {
+        self.results
+            .windows(2)
+            .all(|pair| pair[0].1.answer == pair[1].1.answer)
+    }
+}
+
+/// Runs the cases of a set of puzzles.
+pub struct Runner {
+    puzzles: Vec<Box<dyn Puzzle>>,
+    filter: Filter,
+    timeout: Option<Duration>,
+    seed: Option<u64>,
+    memory: bool,
+    progress: bool,
+}
+
+impl Runner {
+    /// # Panics
+    ///
+    /// If two puzzles have the same name and implementation.
+    #[must_use]
+    pub fn new(puzzles: Vec<Box<dyn Puzzle>>) -> Self {
+        assert_unique(&puzzles);
+        Self {
+            puzzles,
+            filter: Filter::default(),
+            timeout: None,
+            seed: None,
+            memory: false,
+            progress: false,
+        }
+    }
+
+    /// Only run cases that match `filter`, in addition to any earlier filters.
+    #[must_use]
+    pub fn filter<S: AsRef<str>>(mut self, filter: S) -> Self {
+        self.filter.add(filter.as_ref());
+        self
+    }
+
+    /// Stop any case that runs for longer than `timeout`.
+    #[must_use]
+    pub const fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Seed property cases that don't have a seed of their own with `seed`,
+    /// instead of their default.
+    #[must_use]
+    pub const fn with_seed(mut self, seed: Option<u64>) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Measure the memory used by each case. This only works in programs that
+    /// use [`CountingAllocator`](crate::memory::CountingAllocator) as their
+    /// global allocator.
+    #[must_use]
+    pub const fn with_memory(mut self, memory: bool) -> Self {
+        self.memory = memory;
+        self
+    }
+
+    /// Draw a progress bar on stderr for cases that take a while, if stderr is
+    /// a terminal.
+    #[must_use]
+    pub const fn with_progress(mut self, progress: bool) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// The puzzles to run, skipping alternate implementations.
+    fn first_implementations(&self) -> impl Iterator<Item = &dyn Puzzle> {
+        let mut seen = HashSet::new();
+        self.puzzles
+            .iter()
+            .filter(move |puzzle| !is_alternate(&mut seen, puzzle.as_ref()))
+            .map(AsRef::as_ref)
+    }
+
+    /// The cases that would be run, with the name of their puzzle. Puzzles
+    /// whose cases can't be determined are skipped, unless the filter matches
+    /// the puzzle by itself, as when running them.
+    ///
+    /// # Errors
+    ///
+    /// If the cases of a puzzle that matches the filter can't be determined.
+    pub fn cases(&self) -> Result<Vec<(String, Box<dyn PuzzleCase>)>, Box<dyn std::error::Error>> {
+        let mut cases = vec![];
+        for puzzle in self.first_implementations() {
+            let puzzle_cases = match puzzle.cases() {
+                Ok(puzzle_cases) => puzzle_cases,
+                Err(err) if self.filter.matches_puzzle(&puzzle.name()) => {
+                    return Err(
+                        format!("Could not determine cases of {}: {}", puzzle.name(), err).into(),
+                    )
+                }
+                Err(_) => continue,
+            };
+            for case in puzzle_cases {
+                if self.filter.matches(&puzzle.name(), &case.name()) {
+                    cases.push((puzzle.name(), case));
+                }
+            }
+        }
+        Ok(cases)
+    }
+
+    fn run_case(&self, puzzle: &dyn Puzzle, case: &dyn PuzzleCase) -> CaseReport {
+        let run = || {
+            let mut ctx = RunContext::new();
+            if let Some(timeout) = self.timeout {
+                ctx = ctx.with_timeout(timeout);
+            }
+            if let Some(seed) = self.seed {
+                ctx = ctx.with_seed(seed);
+            }
+            if self.progress {
+                let label = format!("{} {}", puzzle.name(), case.name());
+                progress::run_case(case, label, ctx)
+            } else {
+                case.run_with_context(&ctx)
+            }
+        };
+        let (result, memory) = if self.memory {
+            let (result, memory) = memory::measure(run);
+            (result, Some(memory))
+        } else {
+            (run(), None)
+        };
+        CaseReport {
+            puzzle: puzzle.name(),
+            implementation: puzzle.implementation(),
+            case: case.name(),
+            result,
+            memory,
+        }
+    }
+
+    /// Run the selected cases.
+    #[must_use]
+    pub fn run(&self) -> Vec<CaseReport> {
+        let mut reports = vec![];
+        for puzzle in self.first_implementations() {
+            reports.extend(self.run_puzzle(puzzle));
+        }
+        reports
+    }
+
+    /// Run the selected cases, passing the reports for each puzzle to
+    /// `reporter` as soon as the puzzle is done.
+    ///
+    /// # Errors
+    ///
+    /// If the reporter can't write its output.
+    pub fn run_with<R: Reporter + ?Sized>(&self, reporter: &mut R) -> io::Result<Vec<CaseReport>> {
+        let mut reports = vec![];
+        for puzzle in self.first_implementations() {
+            let puzzle_reports = self.run_puzzle(puzzle);
+            if !puzzle_reports.is_empty() {
+                reporter.puzzle(&puzzle_reports)?;
+                reports.extend(puzzle_reports);
+            }
+        }
+        reporter.finish()?;
+        Ok(reports)
+    }
+
+    fn run_puzzle(&self, puzzle: &dyn Puzzle) -> Vec<CaseReport> {
+        match puzzle.cases() {
+            Ok(cases) => cases
+                .iter()
+                .filter(|case| self.filter.matches(&puzzle.name(), &case.name()))
+                .map(|case| self.run_case(puzzle, case.as_ref()))
+                .collect(),
+            // Report the problem as a case of its own, so it isn't lost
+            Err(err) if self.filter.matches_puzzle(&puzzle.name()) => vec![CaseReport {
+                puzzle: puzzle.name(),
+                implementation: puzzle.implementation(),
+                case: "Cases".to_owned(),
+                result: PuzzleResult {
+                    status: PuzzleResultStatus::Error,
+                    description: format!("Could not determine cases: {}", err),
+                    duration: Duration::default(),
+                    answer: None,
+                    mismatch: None,
+                    location: ErrorLocation::of(err.as_ref()),
+                    prepare: None,
+                },
+                memory: None,
+            }],
+            Err(_) => vec![],
+        }
+    }
+
+    /// Run every implementation of the puzzles that have more than one on the
+    /// selected cases, to compare their answers. Cases are matched up by their
+    /// position, as case names aren't unique. As puzzles are unique, the only
+    /// puzzles that share a name are implementations of the same puzzle.
+    #[must_use]
+    pub fn cross_check(&self) -> Vec<CrossCheck> {
+        // Group implementations by puzzle, keeping the order they were registered in
+        let mut groups: Vec<(String, Vec<&dyn Puzzle>)> = vec![];
+        for puzzle in &self.puzzles {
+            let name = puzzle.name();
+            match groups
+                .iter_mut()
+                .find(|(group_name, _)| *group_name == name)
+            {
+                Some((_, implementations)) => implementations.push(puzzle.as_ref()),
+                None => groups.push((name, vec![puzzle.as_ref()])),
+            }
+        }
+
+        let mut checks = vec![];
+        for (name, implementations) in groups {
+            // Puzzles with a single implementation have nothing to compare against
+            if implementations.len() < 2 {
+                continue;
+            }
+            let mut puzzle_checks: Vec<CrossCheck> = vec![];
+            for (idx, puzzle) in implementations.into_iter().enumerate() {
+                let implementation = puzzle
+                    .implementation()
+                    .unwrap_or_else(|| format!("#{}", idx + 1));
+                for (position, report) in self.run_puzzle(puzzle).into_iter().enumerate() {
+                    if position == puzzle_checks.len() {
+                        puzzle_checks.push(CrossCheck {
+                            puzzle: name.clone(),
+                            case: report.case,
+                            results: vec![],
+                        });
+                    }
+                    puzzle_checks[position]
+                        .results
+                        .push((implementation.clone(), report.result));
+                }
+            }
+            checks.extend(puzzle_checks);
+        }
+        checks
+    }
+}
+
+/// Whether `puzzle` is an alternate implementation of one in `seen`, adding it
+/// to `seen` if it isn't. Only puzzles that name their implementation can be
+/// alternates.
+fn is_alternate(seen: &mut HashSet<String>, puzzle: &dyn Puzzle) -> bool {
+    !seen.insert(puzzle.name()) && puzzle.implementation().is_some()
+}
+
+/// Check that no two puzzles have both the same name and the same
+/// implementation, since there would be no way to tell them apart.
+///
+/// # Panics
+///
+/// If two puzzles do.
+pub fn assert_unique(puzzles: &[Box<dyn Puzzle>]) {
+    let mut seen = HashSet::new();
+    for puzzle in puzzles {
+        let implementation = puzzle.implementation();
+        assert!(
+            seen.insert((puzzle.name(), implementation.clone())),
+            "There is more than one {} puzzle with the implementation {:?}",
+            puzzle.name(),
+            implementation
+        );
+    }
+}
+
+/// The puzzles without alternate implementations, keeping the first
+/// implementation of each, in the same way that a `Runner` does.
+///
+/// # Panics
+///
+/// If two puzzles have the same name and implementation.
+#[must_use]
+pub fn without_alternates(puzzles: Vec<Box<dyn Puzzle>>) -> Vec<Box<dyn Puzzle>> {
+    assert_unique(&puzzles);
+    let mut seen = HashSet::new();
+    puzzles
+        .into_iter()
+        .filter(|puzzle| !is_alternate(&mut seen, puzzle.as_ref()))
+        .collect()
+}
+
+/// Split reports into runs of reports for the same puzzle.
+#[must_use]
+pub fn group_by_puzzle(reports: &[CaseReport]) -> Vec<&[CaseReport]> {
+    let mut groups = vec![];
+    let mut start = 0;
+    for idx in 1..=reports.len() {
+        if idx == reports.len() || reports[idx].puzzle != reports[start].puzzle {
+            groups.push(&reports[start..idx]);
+            start = idx;
+        }
+    }
+    groups
+}
+
+/// Run a case with `run`, reporting a panic as an `Error` result, so that one
+/// broken case doesn't take down whatever is running it.
+pub fn catch_panic<F: FnOnce() -> PuzzleResult>(run: F) -> PuzzleResult {
+    panic::catch_unwind(AssertUnwindSafe(run)).unwrap_or_else(|panic| {
+        let message = panic
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| panic.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("unknown reason");
+        PuzzleResult {
+            status: PuzzleResultStatus::Error,
+            description: format!("Panicked: {}", message),
+            duration: Duration::default(),
+            answer: None,
+            mismatch: None,
+            location: None,
+            prepare: None,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::StubPuzzle;
+
+    #[test]
+    fn test_filter() {
+        let filter = Filter::new(&["2019 p2", "Example"]);
+        assert!(filter.matches("2019-D12-P2", "Example 1"));
+        assert!(!filter.matches("2019-D12-P1", "Example 1"));
+        assert!(!filter.matches_puzzle("2019-D12-P2"));
+        assert!(Filter::default().matches("anything", "at all"));
+    }
+
+    #[test]
+    fn test_group_by_puzzle() {
+        let report = |puzzle: &str| CaseReport {
+            puzzle: puzzle.to_owned(),
+            implementation: None,
+            case: "Example".to_owned(),
+            result: PuzzleResult {
+                status: PuzzleResultStatus::Match,
+                description: String::new(),
+                duration: Duration::default(),
+                answer: None,
+                mismatch: None,
+                location: None,
+                prepare: None,
+            },
+            memory: None,
+        };
+        let reports = vec![report("a"), report("a"), report("b")];
+        let groups = group_by_puzzle(&reports);
+        assert_eq!(
+            groups.iter().map(|g| g.len()).collect::<Vec<_>>(),
+            vec![2, 1]
+        );
+        assert!(group_by_puzzle(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_catch_panic() {
+        let puzzle = StubPuzzle::new("2015-D01-P1").panicking_case("Solution");
+        let cases = puzzle.cases().unwrap();
+        let result = catch_panic(|| cases[0].run());
+        assert_eq!(result.status, PuzzleResultStatus::Error);
+        assert_eq!(result.description, "Panicked: Solution panicked");
+    }
+
+    #[test]
+    fn test_alternate_implementations() {
+        let runner = Runner::new(vec![
+            Box::new(StubPuzzle::new("2015-D01-P1").case("Example", "1")),
+            Box::new(
+                StubPuzzle::new("2015-D01-P1")
+                    .implementation("fast")
+                    .case("Example", "2"),
+            ),
+            Box::new(StubPuzzle::new("2015-D01-P2").case("Example", "3")),
+        ]);
+
+        let answers: Vec<_> = runner
+            .run()
+            .into_iter()
+            .map(|report| report.result.answer.unwrap())
+            .collect();
+        assert_eq!(answers, vec!["1", "3"]);
+
+        let checks = runner.cross_check();
+        assert_eq!(checks.len(), 1);
+        assert_eq!(checks[0].puzzle, "2015-D01-P1");
+        assert!(!checks[0].agrees());
+        let implementations: Vec<_> = checks[0].results.iter().map(|(name, _)| name).collect();
+        assert_eq!(implementations, vec!["#1", "fast"]);
+    }
+
+    #[test]
+    fn test_cases_skips_broken_puzzles() {
+        let puzzles = || -> Vec<Box<dyn Puzzle>> {
+            vec![
+                Box::new(StubPuzzle::new("2015-D01-P1").broken("No input")),
+                Box::new(StubPuzzle::new("2015-D02-P1").case("Example", "1")),
+            ]
+        };
+        let cases = Runner::new(puzzles()).filter("Example").cases().unwrap();
+        assert_eq!(cases.len(), 1);
+        assert_eq!(cases[0].0, "2015-D02-P1");
+
+        let err = Runner::new(puzzles()).filter("D01").cases().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Could not determine cases of 2015-D01-P1: No input"
+        );
+    }
+
+    fn puzzles() -> Vec<Box<dyn Puzzle>> {
+        vec![
+            Box::new(
+                StubPuzzle::new("2015-D01-P1")
+                    .case("Example", "3")
+                    .case("Solution", "232"),
+            ),
+            Box::new(StubPuzzle::new("2015-D01-P2").wrong_case("Solution", "1783")),
+            Box::new(StubPuzzle::new("2015-D02-P1").broken("No input")),
+        ]
+    }
+
+    fn names(reports: &[CaseReport]) -> Vec<String> {
+        reports
+            .iter()
+            .map(|report| format!("{} {}", report.puzzle, report.case))
+            .collect()
+    }
+
+    #[test]
+    fn test_run() {
+        let reports = Runner::new(puzzles()).run();
+        assert_eq!(
+            names(&reports),
+            vec![
+                "2015-D01-P1 Example",
+                "2015-D01-P1 Solution",
+                "2015-D01-P2 Solution",
+                "2015-D02-P1 Cases",
+            ]
+        );
+        let statuses: Vec<_> = reports.iter().map(|report| report.result.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                PuzzleResultStatus::Match,
+                PuzzleResultStatus::Match,
+                PuzzleResultStatus::Fail,
+                PuzzleResultStatus::Error,
+            ]
+        );
+        assert_eq!(
+            reports[3].result.description,
+            "Could not determine cases: No input"
+        );
+
+        let reports = Runner::new(puzzles()).filter("solution").run();
+        assert_eq!(
+            names(&reports),
+            vec!["2015-D01-P1 Solution", "2015-D01-P2 Solution"]
+        );
+    }
+
+    #[derive(Default)]
+    struct RecordingReporter {
+        puzzles: Vec<Vec<String>>,
+        finished: bool,
+    }
+
+    impl Reporter for RecordingReporter {
+        fn puzzle(&mut self, cases: &[CaseReport]) -> io::Result<()> {
+            assert!(!self.finished);
+            self.puzzles.push(names(cases));
+            Ok(())
+        }
+
+        fn finish(&mut self) -> io::Result<()> {
+            self.finished = true;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_run_with() {
+        let mut reporter = RecordingReporter::default();
+        let reports = Runner::new(puzzles())
+            .filter("P1")
+            .run_with(&mut reporter)
+            .unwrap();
+        assert_eq!(reports.len(), 3);
+        assert!(reporter.finished);
+        assert_eq!(
+            reporter.puzzles,
+            vec![
+                vec!["2015-D01-P1 Example", "2015-D01-P1 Solution"],
+                vec!["2015-D02-P1 Cases"],
+            ]
+        );
+
+        // Puzzles without any matching cases aren't reported
+        let mut reporter = RecordingReporter::default();
+        let reports = Runner::new(puzzles())
+            .filter("Example")
+            .run_with(&mut reporter)
+            .unwrap();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reporter.puzzles, vec![vec!["2015-D01-P1 Example"]]);
+    }
+
+    #[test]
+    fn test_cross_check() {
+        let runner = Runner::new(vec![
+            Box::new(
+                StubPuzzle::new("2015-D01-P1")
+                    .implementation("simple")
+                    .case("Example", "3")
+                    .case("Solution", "232"),
+            ),
+            Box::new(StubPuzzle::new("2015-D01-P2").case("Solution", "1783")),
+            Box::new(
+                StubPuzzle::new("2015-D01-P1")
+                    .implementation("fast")
+                    .case("Example", "3")
+                    .case("Solution", "233"),
+            ),
+        ])
+        .filter("2015");
+
+        let checks = runner.cross_check();
+        assert_eq!(checks.len(), 2);
+        assert_eq!(checks[0].puzzle, "2015-D01-P1");
+        assert_eq!(checks[0].case, "Example");
+        assert!(checks[0].agrees());
+        assert_eq!(checks[1].case, "Solution");
+        assert!(!checks[1].agrees());
+        let results: Vec<_> = checks[1]
+            .results
+            .iter()
+            .map(|(implementation, result)| (implementation.as_str(), result.answer.as_deref()))
+            .collect();
+        assert_eq!(
+            results,
+            vec![("simple", Some("232")), ("fast", Some("233"))]
+        );
+
+        assert!(runner.filter("Example 3").cross_check().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "more than one 2015-D01-P1 puzzle")]
+    fn test_duplicate_puzzles() {
+        let _ = Runner::new(vec![
+            Box::new(StubPuzzle::new("2015-D01-P1")),
+            Box::new(StubPuzzle::new("2015-D01-P1")),
+        ]);
+    }
+}
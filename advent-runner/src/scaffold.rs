@@ -0,0 +1,61 @@
+use reqwest::StatusCode;
+use std::{fs, path::PathBuf};
+
+/// Add a day's puzzle to its year crate, from the template in `./template`,
+/// and download its input. Files that already exist are left alone, so this
+/// can be run again to fetch an input that wasn't available the first time.
+///
+/// # Errors
+///
+/// If the template can't be read, the files can't be written, or the input
+/// can't be downloaded.
+pub fn add_day(year: u16, day: u8, advent_cookie: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let day_padded = format!("{:0>2}", day.to_string());
+
+    let puzzle_path = day_path(year, day);
+    fs::create_dir_all(&puzzle_path)?;
+
+    let mut mod_path = puzzle_path.clone();
+    mod_path.push("mod.rs");
+    if !mod_path.exists() {
+        let mod_template = String::from_utf8(fs::read("./template/mod.rs.tmpl")?)?
+            .replace("{{YEAR}}", &year.to_string())
+            .replace("{{DAY_PADDED}}", &day_padded);
+        fs::write(mod_path, mod_template)?;
+    }
+
+    let mut input_path = puzzle_path;
+    input_path.push("input");
+    if !input_path.exists() {
+        let url = format!("https://adventofcode.com/{}/day/{}/input", year, day);
+
+        let client = reqwest::Client::new();
+        let mut res = client
+            .get(&url)
+            .header(
+                reqwest::header::COOKIE,
+                format!("session={}", advent_cookie),
+            )
+            .send()?;
+
+        if res.status() == StatusCode::NOT_FOUND {
+            println!("No input for this puzzle");
+        } else {
+            let body = res.text()?;
+            if !res.status().is_success() {
+                println!("Error: {}", body);
+                res.error_for_status()?;
+            }
+            fs::write(input_path, body)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// The directory of a day's puzzle in its year crate, relative to the root of
+/// the workspace. The day's input is in the `input` file inside it.
+#[must_use]
+pub fn day_path(year: u16, day: u8) -> PathBuf {
+    PathBuf::from(format!("./year{}/src/day{:0>2}", year, day))
+}
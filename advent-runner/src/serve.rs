@@ -0,0 +1,588 @@
+use crate::{
+    report::{severity, status_name, status_symbol},
+    reporter::result_json,
+    runner::{catch_panic, without_alternates},
+};
+use advent_lib::{
+    cases::{Puzzle, PuzzleCase, PuzzleResult},
+    context::RunContext,
+};
+use serde_json::{json, Value};
+use std::{
+    fmt::Write as _,
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    thread,
+    time::Duration,
+};
+
+/// Serve a page listing `puzzles`, with buttons to run them, and a JSON API
+/// with the same data, on localhost.
+///
+/// * `GET /` - the page
+/// * `GET /api/puzzles` - every puzzle and the last result of its cases
+/// * `GET /api/puzzles/<name>` - one puzzle
+/// * `POST /api/puzzles/<name>/run` - run a puzzle's cases, and return it
+///
+/// Requests must be addressed to the dashboard by `Host`, and runs can only be
+/// started from the dashboard's own page, so that other sites open in the same
+/// browser can't use it.
+pub fn serve(
+    puzzles: Vec<Box<dyn Puzzle>>,
+    port: u16,
+    timeout: Option<Duration>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let listener = TcpListener::bind(("127.0.0.1", port))?;
+    let addr = listener.local_addr()?;
+    let dashboard = Arc::new(Dashboard::new(puzzles, timeout, addr.port()));
+    println!("Serving results on http://{}", addr);
+
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(err) => {
+                eprintln!("Could not accept connection: {}", err);
+                continue;
+            }
+        };
+        let dashboard = dashboard.clone();
+        // Running a puzzle can take a while, so don't make other requests wait
+        thread::spawn(move || {
+            if let Err(err) = handle_connection(&dashboard, stream) {
+                eprintln!("Could not handle request: {}", err);
+            }
+        });
+    }
+
+    Ok(())
+}
+
+struct PuzzleEntry {
+    name: String,
+    cases: Vec<Box<dyn PuzzleCase>>,
+    /// Why the puzzle's cases couldn't be listed, if they couldn't.
+    error: Option<String>,
+    /// The last result of each case, if it has been run.
+    results: Mutex<Vec<Option<PuzzleResult>>>,
+    /// Held while the puzzle runs, so that it doesn't run twice at once.
+    running: Mutex<()>,
+}
+
+impl PuzzleEntry {
+    fn run(&self, timeout: Option<Duration>) {
+        let _running = lock(&self.running);
+        for (idx, case) in self.cases.iter().enumerate() {
+            let mut ctx = RunContext::new();
+            if let Some(timeout) = timeout {
+                ctx = ctx.with_timeout(timeout);
+            }
+            // A case that panics shouldn't take the server down with it. Runs
+            // are started from a browser, so there's no progress bar
+            let result = catch_panic(|| case.run_with_context(&ctx));
+            lock(&self.results)[idx] = Some(result);
+        }
+    }
+
+    /// The most serious status of the cases, and their total duration, if
+    /// they have all been run.
+    fn summary(&self) -> Option<(&'static str, &'static str, Duration)> {
+        let results = lock(&self.results);
+        let results: Option<Vec<_>> = results.iter().map(Option::as_ref).collect();
+        let results = results.filter(|results| !results.is_empty())?;
+        let status = results
+            .iter()
+            .map(|result| result.status)
+            .max_by_key(|status| severity(*status))?;
+        let duration = results.iter().map(|result| result.duration).sum();
+        Some((status_name(status), status_symbol(status), duration))
+    }
+
+    fn to_json(&self) -> Value {
+        let results = lock(&self.results);
+        let cases: Vec<Value> = self
+            .cases
+            .iter()
+            .zip(results.iter())
+            .map(|(case, result)| match result {
+                Some(result) => result_json(&case.name(), result),
+                None => json!({ "name": case.name(), "status": null }),
+            })
+            .collect();
+        drop(results);
+
+        let summary = self.summary();
+        json!({
+            "name": self.name,
+            "status": summary.map(|(status, _, _)| status),
+            "duration_us": summary.map(|(_, _, duration)| duration.as_micros() as u64),
+            "error": self.error,
+            "cases": cases,
+        })
+    }
+}
+
+struct Dashboard {
+    puzzles: Vec<PuzzleEntry>,
+    timeout: Option<Duration>,
+    /// The `Host` headers that address the dashboard.
+    hosts: Vec<String>,
+}
+
+impl Dashboard {
+    fn new(puzzles: Vec<Box<dyn Puzzle>>, timeout: Option<Duration>, port: u16) -> Self {
+        // Only the first implementation of each puzzle is shown, as in `run`
+        let puzzles = without_alternates(puzzles)
+            .into_iter()
+            .map(|puzzle| {
+                let (cases, error) = match puzzle.cases() {
+                    Ok(cases) => (cases, None),
+                    Err(err) => (vec![], Some(err.to_string())),
+                };
+                PuzzleEntry {
+                    name: puzzle.name(),
+                    results: Mutex::new(cases.iter().map(|_| None).collect()),
+                    cases,
+                    error,
+                    running: Mutex::new(()),
+                }
+            })
+            .collect();
+        let hosts = vec![format!("127.0.0.1:{}", port), format!("localhost:{}", port)];
+        Self {
+            puzzles,
+            timeout,
+            hosts,
+        }
+    }
+
+    /// Why a request should be refused, if it should be. A `Host` that isn't
+    /// the dashboard's means a DNS rebinding attack, and a POST from another
+    /// `Origin` means another site is trying to run puzzles. Requests without
+    /// an `Origin` don't come from browsers, so they're allowed.
+    fn reject(&self, method: &str, host: Option<&str>, origin: Option<&str>) -> Option<Response> {
+        let ours = |host: &str| self.hosts.iter().any(|ours| ours == host);
+        let allowed = match host {
+            Some(host) if ours(host) => match origin {
+                Some(origin) if method == "POST" => {
+                    matches!(origin.strip_prefix("http://"), Some(host) if ours(host))
+                }
+                _ => true,
+            },
+            _ => false,
+        };
+        if allowed {
+            None
+        } else {
+            Some(Response::new(403, "Forbidden", "text/plain", "Forbidden"))
+        }
+    }
+
+    fn puzzle(&self, name: &str) -> Option<&PuzzleEntry> {
+        self.puzzles.iter().find(|puzzle| puzzle.name == name)
+    }
+
+    fn route(&self, method: &str, path: &str) -> Response {
+        let segments: Vec<&str> = path
+            .trim_matches('/')
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .collect();
+        match (method, segments.as_slice()) {
+            ("GET", []) => Response::html(self.page()),
+            ("GET", ["api", "puzzles"]) => {
+                let puzzles: Vec<Value> = self.puzzles.iter().map(PuzzleEntry::to_json).collect();
+                Response::json(&Value::Array(puzzles))
+            }
+            ("GET", ["api", "puzzles", name]) => match self.puzzle(name) {
+                Some(puzzle) => Response::json(&puzzle.to_json()),
+                None => Response::not_found(),
+            },
+            ("POST", ["api", "puzzles", name, "run"]) => match self.puzzle(name) {
+                Some(puzzle) => {
+                    puzzle.run(self.timeout);
+                    Response::json(&puzzle.to_json())
+                }
+                None => Response::not_found(),
+            },
+            // The page's buttons are plain forms, so send the browser back to
+            // the page afterwards
+            ("POST", ["puzzles", name, "run"]) => match self.puzzle(name) {
+                Some(puzzle) => {
+                    puzzle.run(self.timeout);
+                    Response::see_other(&format!("/#{}", puzzle.name))
+                }
+                None => Response::not_found(),
+            },
+            ("GET", _) | ("POST", _) => Response::not_found(),
+            _ => Response::new(
+                405,
+                "Method Not Allowed",
+                "text/plain",
+                "Method not allowed",
+            ),
+        }
+    }
+
+    fn page(&self) -> String {
+        let mut page = String::from(concat!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n",
+            "<title>Advent of Code</title>\n<style>\n",
+            "body { font-family: monospace; }\n",
+            "td, th { padding: 0.2em 0.8em; text-align: left; }\n",
+            ".pass { color: green; } .fail, .error { color: red; }\n",
+            ".unknown, .todo, .cancelled { color: darkgoldenrod; }\n",
+            "</style>\n</head>\n<body>\n<table>\n",
+            "<tr><th>Puzzle</th><th>Status</th><th>Time (μs)</th><th>Cases</th><th></th></tr>\n",
+        ));
+
+        for puzzle in &self.puzzles {
+            let (status, symbol, duration) = match puzzle.summary() {
+                Some((status, symbol, duration)) => {
+                    (status, symbol, duration.as_micros().to_string())
+                }
+                None => ("", "", String::new()),
+            };
+            let results = lock(&puzzle.results);
+            let mut cases = String::new();
+            for (case, result) in puzzle.cases.iter().zip(results.iter()) {
+                let (class, detail) = match result {
+                    Some(result) => (status_name(result.status), result.description.as_str()),
+                    None => ("", "not run"),
+                };
+                write!(
+                    cases,
+                    "<span class=\"{}\" title=\"{}\">{}</span> ",
+                    class,
+                    escape_html(detail),
+                    escape_html(&case.name())
+                )
+                .unwrap();
+            }
+            if let Some(err) = &puzzle.error {
+                cases.push_str(&escape_html(err));
+            }
+            drop(results);
+
+            writeln!(
+                page,
+                concat!(
+                    "<tr id=\"{name}\"><td>{name}</td><td class=\"{status}\">{symbol} \
+                     {status}</td>",
+                    "<td>{duration}</td><td>{cases}</td><td>",
+                    "<form method=\"post\" action=\"/puzzles/{name}/run\">",
+                    "<button>run</button></form></td></tr>"
+                ),
+                name = escape_html(&puzzle.name),
+                status = status,
+                symbol = symbol,
+                duration = duration,
+                cases = cases,
+            )
+            .unwrap();
+        }
+
+        page.push_str("</table>\n</body>\n</html>\n");
+        page
+    }
+}
+
+/// Lock `mutex`, even if a thread panicked while holding it. Panics in cases
+/// are caught, so the data behind the lock is still consistent.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+struct Response {
+    status: u16,
+    reason: &'static str,
+    content_type: &'static str,
+    body: String,
+    location: Option<String>,
+}
+
+impl Response {
+    fn new<S: Into<String>>(
+        status: u16,
+        reason: &'static str,
+        content_type: &'static str,
+        body: S,
+    ) -> Self {
+        Self {
+            status,
+            reason,
+            content_type,
+            body: body.into(),
+            location: None,
+        }
+    }
+
+    fn html(body: String) -> Self {
+        Self::new(200, "OK", "text/html; charset=utf-8", body)
+    }
+
+    fn json(value: &Value) -> Self {
+        Self::new(200, "OK", "application/json", value.to_string())
+    }
+
+    fn not_found() -> Self {
+        Self::new(404, "Not Found", "text/plain", "Not found")
+    }
+
+    fn see_other(location: &str) -> Self {
+        let mut response = Self::new(303, "See Other", "text/plain", "");
+        response.location = Some(location.to_owned());
+        response
+    }
+
+    fn write_to<W: Write>(&self, mut out: W) -> std::io::Result<()> {
+        write!(
+            out,
+            "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n",
+            self.status,
+            self.reason,
+            self.content_type,
+            self.body.len()
+        )?;
+        if let Some(location) = &self.location {
+            write!(out, "Location: {}\r\n", location)?;
+        }
+        write!(out, "\r\n{}", self.body)?;
+        out.flush()
+    }
+}
+
+/// Read one request from the connection and respond to it. Only the request
+/// line and the `Host` and `Origin` headers are used; bodies are read and
+/// ignored.
+fn handle_connection(dashboard: &Dashboard, stream: TcpStream) -> std::io::Result<()> {
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    let mut content_length = 0;
+    let mut host = None;
+    let mut origin = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        let mut header_parts = header.splitn(2, ':');
+        if let (Some(name), Some(value)) = (header_parts.next(), header_parts.next()) {
+            let value = value.trim();
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.parse().unwrap_or(0);
+            } else if name.eq_ignore_ascii_case("host") {
+                host = Some(value.to_owned());
+            } else if name.eq_ignore_ascii_case("origin") {
+                origin = Some(value.to_owned());
+            }
+        }
+    }
+    reader
+        .by_ref()
+        .take(content_length)
+        .read_to_end(&mut vec![])?;
+
+    let mut parts = request_line.split_whitespace();
+    let response = match (parts.next(), parts.next()) {
+        (Some(method), Some(target)) => {
+            // Ignore any query string
+            let path = target.split('?').next().unwrap_or(target);
+            dashboard
+                .reject(method, host.as_deref(), origin.as_deref())
+                .unwrap_or_else(|| dashboard.route(method, path))
+        }
+        _ => Response::new(400, "Bad Request", "text/plain", "Bad request"),
+    };
+    response.write_to(&stream)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::StubPuzzle;
+
+    fn dashboard() -> Dashboard {
+        Dashboard::new(
+            vec![
+                Box::new(
+                    StubPuzzle::new("2015-D01-P1")
+                        .case("Example", "3")
+                        .case("Solution", "232"),
+                ),
+                Box::new(StubPuzzle::new("2015-D01-P2").panicking_case("Solution")),
+                Box::new(StubPuzzle::new("2015-D02-P1").broken("No input")),
+            ],
+            None,
+            8000,
+        )
+    }
+
+    fn json(response: &Response) -> Value {
+        assert_eq!(response.status, 200, "{}", response.body);
+        assert_eq!(response.content_type, "application/json");
+        serde_json::from_str(&response.body).unwrap()
+    }
+
+    #[test]
+    fn test_list_puzzles() {
+        let dashboard = dashboard();
+        let puzzles = json(&dashboard.route("GET", "/api/puzzles"));
+        assert_eq!(
+            puzzles,
+            json!([
+                {
+                    "name": "2015-D01-P1",
+                    "status": null,
+                    "duration_us": null,
+                    "error": null,
+                    "cases": [
+                        { "name": "Example", "status": null },
+                        { "name": "Solution", "status": null },
+                    ],
+                },
+                {
+                    "name": "2015-D01-P2",
+                    "status": null,
+                    "duration_us": null,
+                    "error": null,
+                    "cases": [{ "name": "Solution", "status": null }],
+                },
+                {
+                    "name": "2015-D02-P1",
+                    "status": null,
+                    "duration_us": null,
+                    "error": "No input",
+                    "cases": [],
+                },
+            ])
+        );
+
+        let puzzle = json(&dashboard.route("GET", "/api/puzzles/2015-D01-P1/"));
+        assert_eq!(puzzle["name"], "2015-D01-P1");
+    }
+
+    #[test]
+    fn test_run_puzzle() {
+        let dashboard = dashboard();
+        let puzzle = json(&dashboard.route("POST", "/api/puzzles/2015-D01-P1/run"));
+        assert_eq!(puzzle["status"], "pass");
+        assert_eq!(puzzle["duration_us"], 20);
+        assert_eq!(puzzle["cases"][1]["answer"], "232");
+
+        // The results are kept for later requests
+        let puzzles = json(&dashboard.route("GET", "/api/puzzles"));
+        assert_eq!(puzzles[0], puzzle);
+        assert_eq!(puzzles[1]["status"], Value::Null);
+
+        let response = dashboard.route("POST", "/puzzles/2015-D01-P1/run");
+        assert_eq!(response.status, 303);
+        assert_eq!(response.location.as_deref(), Some("/#2015-D01-P1"));
+    }
+
+    #[test]
+    fn test_run_panicking_puzzle() {
+        let dashboard = dashboard();
+        for _ in 0..2 {
+            let puzzle = json(&dashboard.route("POST", "/api/puzzles/2015-D01-P2/run"));
+            assert_eq!(puzzle["status"], "error");
+            assert_eq!(
+                puzzle["cases"][0]["description"],
+                "Panicked: Solution panicked"
+            );
+        }
+        assert_eq!(dashboard.route("GET", "/").status, 200);
+    }
+
+    #[test]
+    fn test_unknown_routes() {
+        let dashboard = dashboard();
+        assert_eq!(
+            dashboard.route("GET", "/api/puzzles/2015-D03-P1").status,
+            404
+        );
+        assert_eq!(
+            dashboard
+                .route("POST", "/api/puzzles/2015-D03-P1/run")
+                .status,
+            404
+        );
+        assert_eq!(dashboard.route("GET", "/api/nothing").status, 404);
+        assert_eq!(dashboard.route("DELETE", "/api/puzzles").status, 405);
+    }
+
+    #[test]
+    fn test_reject_other_sites() {
+        let dashboard = dashboard();
+        assert!(dashboard
+            .reject("GET", Some("localhost:8000"), None)
+            .is_none());
+        assert!(dashboard
+            .reject(
+                "POST",
+                Some("127.0.0.1:8000"),
+                Some("http://127.0.0.1:8000")
+            )
+            .is_none());
+        assert!(dashboard
+            .reject("POST", Some("localhost:8000"), None)
+            .is_none());
+
+        // DNS rebinding
+        for host in &[None, Some("evil.example:8000"), Some("localhost:8001")] {
+            let response = dashboard.reject("GET", *host, None).unwrap();
+            assert_eq!(response.status, 403);
+        }
+        // Another site's page posting to the dashboard
+        for origin in &["http://evil.example", "null", "https://localhost:8000"] {
+            let response = dashboard
+                .reject("POST", Some("localhost:8000"), Some(origin))
+                .unwrap();
+            assert_eq!(response.status, 403);
+        }
+        assert!(dashboard
+            .reject("GET", Some("localhost:8000"), Some("http://evil.example"))
+            .is_none());
+    }
+
+    #[test]
+    fn test_handle_connection() {
+        let dashboard = dashboard();
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let client = thread::spawn(move || {
+            let mut stream = TcpStream::connect(addr).unwrap();
+            write!(
+                stream,
+                concat!(
+                    "POST /api/puzzles/2015-D01-P1/run?x=1 HTTP/1.1\r\n",
+                    "Host: localhost:8000\r\nOrigin: http://localhost:8000\r\n",
+                    "Content-Length: 4\r\n\r\nbody"
+                )
+            )
+            .unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        });
+        let (stream, _) = listener.accept().unwrap();
+        handle_connection(&dashboard, stream).unwrap();
+
+        let response = client.join().unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        assert!(head.starts_with("HTTP/1.1 200 OK\r\n"), "{}", head);
+        assert!(head.contains("Content-Type: application/json\r\n"));
+        let puzzle: Value = serde_json::from_str(body).unwrap();
+        assert_eq!(puzzle["status"], "pass");
+    }
+}
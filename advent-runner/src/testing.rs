@@ -0,0 +1,122 @@
+//! Puzzles with made up cases, for testing the runner without running real
+//! puzzles.
+
+use advent_lib::cases::{Puzzle, PuzzleCase, PuzzleResult, PuzzleResultStatus};
+use std::time::Duration;
+
+/// A puzzle whose cases give fixed answers.
+#[derive(Debug)]
+pub struct StubPuzzle {
+    name: String,
+    implementation: Option<String>,
+    cases: Vec<StubCase>,
+    /// Why the cases can't be listed, for puzzles that are broken.
+    error: Option<String>,
+}
+
+impl StubPuzzle {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            implementation: None,
+            cases: vec![],
+            error: None,
+        }
+    }
+
+    /// Make listing the puzzle's cases fail.
+    pub fn broken(mut self, error: &str) -> Self {
+        self.error = Some(error.to_owned());
+        self
+    }
+
+    pub fn implementation(mut self, implementation: &str) -> Self {
+        self.implementation = Some(implementation.to_owned());
+        self
+    }
+
+    /// Add a case that passes with `answer`.
+    pub fn case(mut self, name: &str, answer: &str) -> Self {
+        self.cases.push(StubCase {
+            name: name.to_owned(),
+            answer: answer.to_owned(),
+            status: PuzzleResultStatus::Match,
+            panics: false,
+        });
+        self
+    }
+
+    /// Add a case that fails with `answer`.
+    pub fn wrong_case(mut self, name: &str, answer: &str) -> Self {
+        self.cases.push(StubCase {
+            name: name.to_owned(),
+            answer: answer.to_owned(),
+            status: PuzzleResultStatus::Fail,
+            panics: false,
+        });
+        self
+    }
+
+    /// Add a case that panics when it is run.
+    pub fn panicking_case(mut self, name: &str) -> Self {
+        self.cases.push(StubCase {
+            name: name.to_owned(),
+            answer: String::new(),
+            status: PuzzleResultStatus::Error,
+            panics: true,
+        });
+        self
+    }
+}
+
+impl Puzzle for StubPuzzle {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn implementation(&self) -> Option<String> {
+        self.implementation.clone()
+    }
+
+    fn cases(&self) -> Result<Vec<Box<dyn PuzzleCase>>, Box<dyn std::error::Error>> {
+        if let Some(error) = &self.error {
+            return Err(error.clone().into());
+        }
+        Ok(self
+            .cases
+            .iter()
+            .map(|case| Box::new(case.clone()) as Box<dyn PuzzleCase>)
+            .collect())
+    }
+}
+
+#[derive(Clone, Debug)]
+struct StubCase {
+    name: String,
+    answer: String,
+    status: PuzzleResultStatus,
+    panics: bool,
+}
+
+impl PuzzleCase for StubCase {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn run(&self) -> PuzzleResult {
+        assert!(!self.panics, "{} panicked", self.name);
+        let description = match self.status {
+            PuzzleResultStatus::Match => format!("{} is correct", self.answer),
+            _ => format!("{} is wrong", self.answer),
+        };
+        PuzzleResult {
+            status: self.status,
+            description,
+            duration: Duration::from_micros(10),
+            answer: Some(self.answer.clone()),
+            mismatch: None,
+            location: None,
+            prepare: None,
+        }
+    }
+}
@@ -0,0 +1,446 @@
+use crate::{
+    report::status_symbol,
+    runner::{catch_panic, without_alternates},
+    Filter,
+};
+use advent_lib::{
+    cases::{Puzzle, PuzzleCase, PuzzleResult},
+    context::RunContext,
+    diff::DiffLine,
+};
+use cursive::{
+    traits::{Boxable, Identifiable},
+    views::{Dialog, EditView, LinearLayout, Panel, ScrollView, SelectView, TextContent, TextView},
+    Cursive,
+};
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    panic,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+const TREE: &str = "tree";
+const HELP: &str = "enter: run  r: re-run  /: filter  v: verbose  c: cancel  q: quit";
+/// How many past durations to show for a case, unless in verbose mode.
+const SHORT_HISTORY: usize = 5;
+/// The name of the thread that runs cases, so that its panics aren't printed.
+const RUNNER_THREAD: &str = "tui-runner";
+
+/// A puzzle in the tree, with its cases as children.
+struct PuzzleNode {
+    name: String,
+    cases: Vec<Arc<dyn PuzzleCase>>,
+    /// Why the puzzle's cases couldn't be listed, if they couldn't.
+    error: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Selection {
+    Puzzle(usize),
+    Case(usize, usize),
+}
+
+#[derive(Default)]
+struct CaseHistory {
+    durations: Vec<Duration>,
+    last: Option<PuzzleResult>,
+}
+
+struct State {
+    puzzles: Vec<PuzzleNode>,
+    history: HashMap<(usize, usize), CaseHistory>,
+    details: TextContent,
+    status: TextContent,
+    filter: String,
+    verbose: bool,
+    selected: Option<Selection>,
+    last_run: Option<Selection>,
+    running: Option<RunContext>,
+}
+
+impl State {
+    /// The indexes of the cases of a puzzle that match the filter.
+    fn visible_cases(&self, puzzle_idx: usize) -> Vec<usize> {
+        let filter = Filter::new(&[&self.filter]);
+        let puzzle = &self.puzzles[puzzle_idx];
+        (0..puzzle.cases.len())
+            .filter(|&case_idx| filter.matches(&puzzle.name, &puzzle.cases[case_idx].name()))
+            .collect()
+    }
+
+    fn case_symbol(&self, puzzle_idx: usize, case_idx: usize) -> &'static str {
+        self.history
+            .get(&(puzzle_idx, case_idx))
+            .and_then(|history| history.last.as_ref())
+            .map_or(" ", |result| status_symbol(result.status))
+    }
+
+    /// The rows of the tree: each visible puzzle, followed by its cases.
+    fn tree_items(&self) -> Vec<(String, Selection)> {
+        let mut items = vec![];
+        for (puzzle_idx, puzzle) in self.puzzles.iter().enumerate() {
+            let cases = self.visible_cases(puzzle_idx);
+            if cases.is_empty() && !(self.filter.is_empty() && puzzle.error.is_some()) {
+                continue;
+            }
+            let symbols: String = cases
+                .iter()
+                .map(|&case_idx| self.case_symbol(puzzle_idx, case_idx))
+                .collect();
+            items.push((
+                format!("{:<12} {}", puzzle.name, symbols),
+                Selection::Puzzle(puzzle_idx),
+            ));
+            for case_idx in cases {
+                items.push((
+                    format!(
+                        "  {} {}",
+                        self.case_symbol(puzzle_idx, case_idx),
+                        puzzle.cases[case_idx].name()
+                    ),
+                    Selection::Case(puzzle_idx, case_idx),
+                ));
+            }
+        }
+        items
+    }
+
+    fn describe(&self, selection: Selection) -> String {
+        let mut out = String::new();
+        match selection {
+            Selection::Puzzle(puzzle_idx) => {
+                let puzzle = &self.puzzles[puzzle_idx];
+                writeln!(out, "{}\n", puzzle.name).unwrap();
+                if let Some(err) = &puzzle.error {
+                    writeln!(out, "Could not determine cases: {}", err).unwrap();
+                }
+                for case_idx in self.visible_cases(puzzle_idx) {
+                    let history = self.history.get(&(puzzle_idx, case_idx));
+                    write!(
+                        out,
+                        "{} {:<12}",
+                        self.case_symbol(puzzle_idx, case_idx),
+                        puzzle.cases[case_idx].name()
+                    )
+                    .unwrap();
+                    if let Some(duration) = history.and_then(|h| h.durations.last()) {
+                        write!(out, " {:>10} μs", duration.as_micros()).unwrap();
+                    }
+                    writeln!(out).unwrap();
+                }
+            }
+
+            Selection::Case(puzzle_idx, case_idx) => {
+                let puzzle = &self.puzzles[puzzle_idx];
+                writeln!(out, "{} {}\n", puzzle.name, puzzle.cases[case_idx].name()).unwrap();
+                let history = match self.history.get(&(puzzle_idx, case_idx)) {
+                    Some(history) => history,
+                    None => {
+                        writeln!(out, "Not run yet").unwrap();
+                        return out;
+                    }
+                };
+                if let Some(result) = &history.last {
+                    write_result(&mut out, result, self.verbose);
+                }
+
+                let skip = if self.verbose {
+                    0
+                } else {
+                    history.durations.len().saturating_sub(SHORT_HISTORY)
+                };
+                writeln!(out, "\nHistory (μs):").unwrap();
+                for duration in history.durations.iter().skip(skip).rev() {
+                    writeln!(out, "{:>12}", duration.as_micros()).unwrap();
+                }
+            }
+        }
+        out
+    }
+
+    /// Start running `selection`, returning the cases to run and the context
+    /// to run them in. Only one run happens at a time.
+    fn start(
+        &mut self,
+        selection: Selection,
+    ) -> Option<(Vec<((usize, usize), Arc<dyn PuzzleCase>)>, RunContext)> {
+        if self.running.is_some() {
+            return None;
+        }
+        let keys = match selection {
+            Selection::Puzzle(puzzle_idx) => self
+                .visible_cases(puzzle_idx)
+                .into_iter()
+                .map(|case_idx| (puzzle_idx, case_idx))
+                .collect(),
+            Selection::Case(puzzle_idx, case_idx) => vec![(puzzle_idx, case_idx)],
+        };
+        let jobs = keys
+            .into_iter()
+            .map(|(puzzle_idx, case_idx)| {
+                let case = self.puzzles[puzzle_idx].cases[case_idx].clone();
+                ((puzzle_idx, case_idx), case)
+            })
+            .collect();
+        let ctx = RunContext::new();
+        self.running = Some(ctx.clone());
+        self.last_run = Some(selection);
+        Some((jobs, ctx))
+    }
+}
+
+fn write_result(out: &mut String, result: &PuzzleResult, verbose: bool) {
+    writeln!(out, "Status: {:?}", result.status).unwrap();
+    match &result.answer {
+        Some(answer) if answer.contains('\n') => writeln!(out, "Answer:\n{}", answer).unwrap(),
+        Some(answer) => writeln!(out, "Answer: {}", answer).unwrap(),
+        None => (),
+    }
+    if verbose || result.answer.is_none() {
+        writeln!(out, "{}", result.description).unwrap();
+    }
+    if let Some(mismatch) = &result.mismatch {
+        writeln!(out).unwrap();
+        for line in mismatch.diff() {
+            match line {
+                DiffLine::Same(line) => writeln!(out, "  {}", line),
+                DiffLine::Expected(line) => writeln!(out, "- {}", line),
+                DiffLine::Actual(line) => writeln!(out, "+ {}", line),
+            }
+            .unwrap();
+        }
+    }
+    if let Some(location) = &result.location {
+        writeln!(out, "\n{} | {}", location.line, location.text).unwrap();
+        if let Some(column) = location.column {
+            let padding = " ".repeat(location.line.to_string().len() + 2 + column);
+            writeln!(out, "{}^", padding).unwrap();
+        }
+    }
+}
+
+/// Browse the puzzles and their cases in an interactive tree, running them on
+/// demand.
+pub fn browse(puzzles: Vec<Box<dyn Puzzle>>) {
+    // Only the first implementation of each puzzle is shown, as in `run`
+    let puzzles = without_alternates(puzzles)
+        .into_iter()
+        .map(|puzzle| match puzzle.cases() {
+            Ok(cases) => PuzzleNode {
+                name: puzzle.name(),
+                cases: cases.into_iter().map(Arc::from).collect(),
+                error: None,
+            },
+            Err(err) => PuzzleNode {
+                name: puzzle.name(),
+                cases: vec![],
+                error: Some(err.to_string()),
+            },
+        })
+        .collect();
+
+    let state = State {
+        puzzles,
+        history: HashMap::new(),
+        details: TextContent::new(""),
+        status: TextContent::new(HELP),
+        filter: String::new(),
+        verbose: false,
+        selected: None,
+        last_run: None,
+        running: None,
+    };
+
+    let mut siv = Cursive::default();
+    siv.add_fullscreen_layer(
+        LinearLayout::vertical()
+            .child(
+                LinearLayout::horizontal()
+                    .child(
+                        Panel::new(ScrollView::new(
+                            SelectView::<Selection>::new()
+                                .on_select(|s, selection: &Selection| {
+                                    s.with_user_data(|state: &mut State| {
+                                        state.selected = Some(*selection);
+                                    });
+                                    show_details(s);
+                                })
+                                .on_submit(|s, selection: &Selection| run_selection(s, *selection))
+                                .with_id(TREE),
+                        ))
+                        .title("Puzzles")
+                        .fixed_width(36)
+                        .full_height(),
+                    )
+                    .child(
+                        Panel::new(ScrollView::new(TextView::new_with_content(
+                            state.details.clone(),
+                        )))
+                        .title("Result")
+                        .full_width()
+                        .full_height(),
+                    ),
+            )
+            .child(TextView::new_with_content(state.status.clone())),
+    );
+    siv.set_user_data(state);
+    refresh_tree(&mut siv);
+
+    siv.add_global_callback('q', Cursive::quit);
+    siv.add_global_callback('r', |s| {
+        let last_run = s.with_user_data(|state: &mut State| state.last_run);
+        if let Some(Some(selection)) = last_run {
+            run_selection(s, selection);
+        }
+    });
+    siv.add_global_callback('v', |s| {
+        s.with_user_data(|state: &mut State| state.verbose = !state.verbose);
+        show_details(s);
+    });
+    siv.add_global_callback('c', |s| {
+        s.with_user_data(|state: &mut State| {
+            if let Some(ctx) = &state.running {
+                ctx.cancel();
+            }
+        });
+    });
+    siv.add_global_callback('/', |s| {
+        let filter = s
+            .with_user_data(|state: &mut State| state.filter.clone())
+            .unwrap_or_default();
+        s.add_layer(
+            Dialog::around(
+                EditView::new()
+                    .content(filter)
+                    .on_submit(|s, filter| {
+                        s.pop_layer();
+                        s.with_user_data(|state: &mut State| state.filter = filter.to_owned());
+                        refresh_tree(s);
+                    })
+                    .fixed_width(30),
+            )
+            .title("Filter")
+            .dismiss_button("Cancel"),
+        );
+    });
+
+    // A panicking case is shown as an error result, and printing the panic
+    // as well would draw over the screen
+    let default_hook = Arc::new(panic::take_hook());
+    let hook = Arc::clone(&default_hook);
+    panic::set_hook(Box::new(move |info| {
+        if thread::current().name() != Some(RUNNER_THREAD) {
+            (*hook)(info);
+        }
+    }));
+    siv.run();
+    panic::set_hook(Box::new(move |info| (*default_hook)(info)));
+}
+
+/// Rebuild the rows of the tree, keeping the same row selected if possible.
+fn refresh_tree(siv: &mut Cursive) {
+    let items = siv
+        .with_user_data(|state: &mut State| state.tree_items())
+        .unwrap_or_default();
+    let selected = siv
+        .with_user_data(|state: &mut State| state.selected)
+        .flatten();
+    let callback = siv.call_on_id(TREE, |view: &mut SelectView<Selection>| {
+        view.clear();
+        if items.is_empty() {
+            return None;
+        }
+        let mut selected_row = 0;
+        for (row, (label, selection)) in items.into_iter().enumerate() {
+            if Some(selection) == selected {
+                selected_row = row;
+            }
+            view.add_item(label, selection);
+        }
+        Some(view.set_selection(selected_row))
+    });
+    if let Some(callback) = callback.flatten() {
+        callback(siv);
+    }
+}
+
+fn show_details(siv: &mut Cursive) {
+    siv.with_user_data(|state: &mut State| {
+        let text = state
+            .selected
+            .map(|selection| state.describe(selection))
+            .unwrap_or_default();
+        state.details.set_content(text);
+    });
+}
+
+fn set_status(siv: &mut Cursive, message: &str) {
+    siv.with_user_data(|state: &mut State| {
+        state.status.set_content(format!("{}    {}", HELP, message));
+    });
+}
+
+/// Run a case, or all of the visible cases of a puzzle, on another thread so
+/// that the interface stays responsive.
+fn run_selection(siv: &mut Cursive, selection: Selection) {
+    let (jobs, ctx) = match siv.with_user_data(|state: &mut State| state.start(selection)) {
+        Some(Some(started)) => started,
+        _ => {
+            set_status(siv, "Already running, press c to cancel");
+            return;
+        }
+    };
+
+    let sink = siv.cb_sink().clone();
+    let spawned = thread::Builder::new()
+        .name(RUNNER_THREAD.to_owned())
+        .spawn(move || {
+            for (key, case) in jobs {
+                let label = case.name();
+                let progress_sink = sink.clone();
+                let drawn = AtomicUsize::new(usize::MAX);
+                let case_ctx = ctx.clone().with_progress(move |done, total| {
+                    let percent = (done * 100).checked_div(total).unwrap_or(0).min(100);
+                    // Only redraw when the percentage has changed
+                    if drawn.swap(percent, Ordering::Relaxed) != percent {
+                        let message = format!("Running {} {}%", label, percent);
+                        progress_sink
+                            .send(Box::new(move |s: &mut Cursive| set_status(s, &message)))
+                            .ok();
+                    }
+                });
+
+                let message = format!("Running {}", case.name());
+                sink.send(Box::new(move |s: &mut Cursive| set_status(s, &message)))
+                    .ok();
+                let result = catch_panic(|| case.run_with_context(&case_ctx));
+                sink.send(Box::new(move |s: &mut Cursive| finish_case(s, key, result)))
+                    .ok();
+            }
+            sink.send(Box::new(|s: &mut Cursive| {
+                s.with_user_data(|state: &mut State| state.running = None);
+                set_status(s, "");
+            }))
+            .ok();
+        });
+    if let Err(err) = spawned {
+        siv.with_user_data(|state: &mut State| state.running = None);
+        set_status(siv, &format!("Could not start running: {}", err));
+    }
+}
+
+fn finish_case(siv: &mut Cursive, key: (usize, usize), result: PuzzleResult) {
+    siv.with_user_data(|state: &mut State| {
+        let history = state.history.entry(key).or_default();
+        history.durations.push(result.duration);
+        history.last = Some(result);
+    });
+    refresh_tree(siv);
+    show_details(siv);
+}
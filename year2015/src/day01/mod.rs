@@ -1,4 +1,4 @@
-use advent_lib::cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner};
+use advent_lib::cases::{DefaultError, GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner};
 use std::iter::Iterator;
 
 pub fn get_puzzles() -> Vec<Box<dyn Puzzle>> {
@@ -11,6 +11,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = &'static str;
     type Output = i32;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2015-D01-P1".to_owned()
@@ -49,6 +50,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = &'static str;
     type Output = usize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2015-D01-P2".to_owned()
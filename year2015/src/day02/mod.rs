@@ -1,5 +1,5 @@
 use advent_lib::{
-    cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    cases::{DefaultError, GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
     helpers::StringAdventExt,
 };
 
@@ -24,6 +24,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = &'static str;
     type Output = u32;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2015-D02-P1".to_owned()
@@ -51,6 +52,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = &'static str;
     type Output = u32;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2015-D02-P2".to_owned()
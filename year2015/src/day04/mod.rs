@@ -1,5 +1,5 @@
 #![allow(clippy::maybe_infinite_iter)]
-use advent_lib::cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner};
+use advent_lib::cases::{DefaultError, GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner};
 use rayon::prelude::*;
 use std::iter::Iterator;
 
@@ -47,6 +47,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = &'static str;
     type Output = u32;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2015-D04-P1".to_owned()
@@ -71,6 +72,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = &'static str;
     type Output = u32;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2015-D04-P2".to_owned()
@@ -1,5 +1,5 @@
 use advent_lib::{
-    cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    cases::{DefaultError, GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
     helpers::StringAdventExt,
 };
 use rayon::prelude::*;
@@ -15,6 +15,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = Vec<String>;
     type Output = usize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2015-D05-P1".to_owned()
@@ -78,6 +79,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = Vec<String>;
     type Output = usize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2015-D05-P2".to_owned()
@@ -1,5 +1,5 @@
 use advent_lib::{
-    cases::{GenericPuzzleCase, PuzzleCase, PuzzleRunner},
+    cases::{DefaultError, GenericPuzzleCase, PuzzleCase, PuzzleRunner},
     extremes,
 };
 
@@ -9,6 +9,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = &'static str;
     type Output = u32;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2017-D02-P1".to_owned()
@@ -1,4 +1,4 @@
-use advent_lib::cases::{GenericPuzzleCase, PuzzleCase, PuzzleRunner};
+use advent_lib::cases::{DefaultError, GenericPuzzleCase, PuzzleCase, PuzzleRunner};
 use std::collections::HashMap;
 use std::ops::{Add, AddAssign};
 
@@ -8,9 +8,10 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = u32;
     type Output = u32;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
-        "2017-D03-P1".to_owned()
+        "2017-D03-P2".to_owned()
     }
 
     fn cases(&self) -> Result<Vec<Box<dyn PuzzleCase>>, Box<dyn std::error::Error>> {
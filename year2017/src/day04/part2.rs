@@ -1,4 +1,4 @@
-use advent_lib::cases::{GenericPuzzleCase, PuzzleCase, PuzzleRunner};
+use advent_lib::cases::{DefaultError, GenericPuzzleCase, PuzzleCase, PuzzleRunner};
 use std::collections::{HashMap, HashSet};
 
 #[derive(Debug)]
@@ -7,6 +7,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = &'static str;
     type Output = usize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2017-D04-P2".to_owned()
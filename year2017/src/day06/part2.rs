@@ -1,4 +1,4 @@
-use advent_lib::cases::{GenericPuzzleCase, PuzzleCase, PuzzleRunner};
+use advent_lib::cases::{DefaultError, GenericPuzzleCase, PuzzleCase, PuzzleRunner};
 use std::collections::HashMap;
 
 #[derive(Debug)]
@@ -7,6 +7,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = Vec<usize>;
     type Output = i32;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2017-D06-P2".to_owned()
@@ -1,4 +1,4 @@
-use advent_lib::cases::{GenericPuzzleCase, PuzzleCase, PuzzleRunner};
+use advent_lib::cases::{DefaultError, GenericPuzzleCase, PuzzleCase, PuzzleRunner};
 use std::collections::HashMap;
 use std::str::FromStr;
 
@@ -8,6 +8,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = Vec<NodeDesc>;
     type Output = String;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2017-D07-P1".to_owned()
@@ -1,4 +1,4 @@
-use advent_lib::cases::{GenericPuzzleCase, PuzzleCase, PuzzleRunner};
+use advent_lib::cases::{DefaultError, GenericPuzzleCase, PuzzleCase, PuzzleRunner};
 use indoc::indoc;
 use std::collections::HashMap;
 use std::rc::{Rc, Weak};
@@ -10,6 +10,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = Vec<NodeDesc>;
     type Output = u32;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2017-D07-P2".to_owned()
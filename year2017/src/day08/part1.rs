@@ -1,5 +1,5 @@
 use crate::day08::{Instruction, Operation};
-use advent_lib::cases::{GenericPuzzleCase, PuzzleCase, PuzzleRunner};
+use advent_lib::cases::{DefaultError, GenericPuzzleCase, PuzzleCase, PuzzleRunner};
 use indoc::indoc;
 use std::collections::HashMap;
 
@@ -9,6 +9,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = Vec<Instruction>;
     type Output = isize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2017-D08-P1".to_owned()
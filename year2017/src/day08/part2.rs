@@ -1,5 +1,5 @@
 use crate::day08::{Instruction, Operation};
-use advent_lib::cases::{GenericPuzzleCase, PuzzleCase, PuzzleRunner};
+use advent_lib::cases::{DefaultError, GenericPuzzleCase, PuzzleCase, PuzzleRunner};
 use std::{cmp, collections::HashMap};
 
 #[derive(Debug)]
@@ -8,6 +8,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = Vec<Instruction>;
     type Output = isize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2017-D08-P2".to_owned()
@@ -1,4 +1,4 @@
-use advent_lib::cases::{GenericPuzzleCase, PuzzleCase, PuzzleRunner};
+use advent_lib::cases::{DefaultError, GenericPuzzleCase, PuzzleCase, PuzzleRunner};
 
 #[derive(Debug)]
 pub struct Part1;
@@ -6,6 +6,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = (usize, Vec<usize>);
     type Output = usize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2017-D10-P1".to_owned()
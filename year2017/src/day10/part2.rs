@@ -1,5 +1,5 @@
 use crate::day10::KnotHash;
-use advent_lib::cases::{GenericPuzzleCase, PuzzleCase, PuzzleRunner};
+use advent_lib::cases::{DefaultError, GenericPuzzleCase, PuzzleCase, PuzzleRunner};
 
 #[derive(Debug)]
 pub struct Part2;
@@ -7,6 +7,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = &'static str;
     type Output = String;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2017-D10-P2".to_owned()
@@ -1,5 +1,5 @@
 use crate::day10::KnotHash;
-use advent_lib::cases::{GenericPuzzleCase, PuzzleCase, PuzzleRunner};
+use advent_lib::cases::{DefaultError, GenericPuzzleCase, PuzzleCase, PuzzleRunner};
 use std::{collections::HashSet, fmt};
 
 #[derive(Debug)]
@@ -8,6 +8,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = &'static str;
     type Output = usize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2017-D14-P2".to_owned()
@@ -1,4 +1,7 @@
-use advent_lib::cases::{GenericPuzzleCase, PuzzleCase, PuzzleRunner};
+use advent_lib::{
+    cases::{DefaultError, GenericPuzzleCase, PuzzleCase, PuzzleRunner},
+    context::RunContext,
+};
 use std::iter::Iterator;
 
 #[derive(Debug)]
@@ -7,6 +10,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = (&'static str, usize);
     type Output = usize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2017-D15-P2".to_owned()
@@ -34,7 +38,10 @@ impl PuzzleRunner for Part2 {
             .collect())
     }
 
-    fn run_puzzle((input, iterations): Self::Input) -> Self::Output {
+    fn try_run_puzzle_with_context(
+        (input, iterations): Self::Input,
+        ctx: &RunContext,
+    ) -> Result<Self::Output, Self::Error> {
         let initial_values: Vec<u64> = input
             .lines()
             .filter_map(|l| {
@@ -49,11 +56,17 @@ impl PuzzleRunner for Part2 {
         let generator_a = Generator::with_multiple_of(initial_values[0], 16807, 4);
         let generator_b = Generator::with_multiple_of(initial_values[1], 48271, 8);
 
-        generator_a
-            .zip(generator_b)
-            .take(iterations)
-            .filter(|&(a, b)| (a & 0xFFFF) == (b & 0xFFFF))
-            .count()
+        let mut matches = 0;
+        for (idx, (a, b)) in generator_a.zip(generator_b).take(iterations).enumerate() {
+            if idx % 0x10_0000 == 0 {
+                ctx.progress(idx, iterations);
+                ctx.check()?;
+            }
+            if (a & 0xFFFF) == (b & 0xFFFF) {
+                matches += 1;
+            }
+        }
+        Ok(matches)
     }
 }
 
@@ -1,4 +1,4 @@
-use advent_lib::cases::{GenericPuzzleCase, PuzzleCase, PuzzleRunner};
+use advent_lib::cases::{DefaultError, GenericPuzzleCase, PuzzleCase, PuzzleRunner};
 use lazy_static::lazy_static;
 use regex::Regex;
 use std::str::FromStr;
@@ -9,6 +9,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = (usize, &'static str);
     type Output = String;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2017-D16-P2".to_owned()
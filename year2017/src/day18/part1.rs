@@ -1,4 +1,4 @@
-use advent_lib::cases::{GenericPuzzleCase, PuzzleCase, PuzzleRunner};
+use advent_lib::cases::{DefaultError, GenericPuzzleCase, PuzzleCase, PuzzleRunner};
 use indoc::indoc;
 use std::collections::HashMap;
 use std::str::FromStr;
@@ -9,6 +9,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = &'static str;
     type Output = i64;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2017-D18-P1".to_owned()
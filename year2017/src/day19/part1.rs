@@ -1,4 +1,4 @@
-use advent_lib::cases::{GenericPuzzleCase, PuzzleCase, PuzzleRunner};
+use advent_lib::cases::{DefaultError, GenericPuzzleCase, PuzzleCase, PuzzleRunner};
 use std::ops::{Add, AddAssign};
 
 #[derive(Debug)]
@@ -7,6 +7,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = &'static str;
     type Output = String;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2017-D19-P1".to_owned()
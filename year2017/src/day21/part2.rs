@@ -1,4 +1,4 @@
-use advent_lib::cases::{GenericPuzzleCase, PuzzleCase, PuzzleRunner};
+use advent_lib::cases::{DefaultError, GenericPuzzleCase, PuzzleCase, PuzzleRunner};
 use indoc::indoc;
 use std::collections::HashMap;
 use std::fmt;
@@ -10,6 +10,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = (&'static str, usize);
     type Output = usize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2017-D21-P2".to_owned()
@@ -1,4 +1,4 @@
-use advent_lib::cases::{GenericPuzzleCase, PuzzleCase, PuzzleRunner};
+use advent_lib::cases::{DefaultError, GenericPuzzleCase, PuzzleCase, PuzzleRunner};
 
 #[derive(Debug)]
 pub struct Part2;
@@ -6,6 +6,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = (u32, u32);
     type Output = u32;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2017-D23-P2".to_owned()
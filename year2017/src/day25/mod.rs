@@ -1,5 +1,5 @@
 use advent_lib::cases::Puzzle;
-use advent_lib::cases::{GenericPuzzleCase, PuzzleCase, PuzzleRunner};
+use advent_lib::cases::{DefaultError, GenericPuzzleCase, PuzzleCase, PuzzleRunner};
 use std::collections::HashMap;
 use std::fmt;
 
@@ -13,9 +13,10 @@ pub struct Day25;
 impl PuzzleRunner for Day25 {
     type Input = usize;
     type Output = usize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
-        "2017-D25".to_owned()
+        "2017-D25-P1".to_owned()
     }
 
     fn cases(&self) -> Result<Vec<Box<dyn PuzzleCase>>, Box<dyn std::error::Error>> {
@@ -1,5 +1,5 @@
 use advent_lib::{
-    cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    cases::{DefaultError, GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
     helpers::StringAdventExt,
 };
 use itertools::Itertools;
@@ -15,6 +15,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = &'static str;
     type Output = usize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2018-D02-P1".to_owned()
@@ -54,6 +55,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = &'static str;
     type Output = String;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2018-D02-P2".to_owned()
@@ -1,28 +1,12 @@
 use advent_lib::{
-    cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    cases::{DefaultError, GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    error::AdventError,
     helpers::StringAdventExt,
+    parse_fmt,
 };
 use indoc::indoc;
-use lalrpop_util::lalrpop_mod;
 use std::{collections::HashMap, iter::Iterator, str::FromStr};
 
-#[cfg(windows)]
-lalrpop_mod!(
-    #[allow(clippy::all)]
-    #[allow(clippy::nursery)]
-    #[allow(clippy::pedantic)]
-    parser,
-    "\\day03\\parser.rs"
-);
-#[cfg(unix)]
-lalrpop_mod!(
-    #[allow(clippy::all)]
-    #[allow(clippy::nursery)]
-    #[allow(clippy::pedantic)]
-    parser,
-    "/day03/parser.rs"
-);
-
 pub fn get_puzzles() -> Vec<Box<dyn Puzzle>> {
     vec![Box::new(Part1), Box::new(Part2)]
 }
@@ -33,6 +17,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = &'static str;
     type Output = usize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2018-D03-P1".to_owned()
@@ -76,6 +61,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = &'static str;
     type Output = u32;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2018-D03-P2".to_owned()
@@ -137,12 +123,11 @@ pub struct Claim {
 }
 
 impl FromStr for Claim {
-    // TODO better error handling
-    type Err = String;
+    type Err = AdventError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        parser::ClaimParser::new()
-            .parse(s)
-            .map_err(|err| err.to_string())
+        let (id, x, y, w, h) =
+            parse_fmt!("#{id} @ {x},{y}: {w}x{h}", s => (u32, usize, usize, usize, usize))?;
+        Ok(Self { id, x, y, w, h })
     }
 }
@@ -1,6 +1,7 @@
 use advent_lib::{
-    cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
-    helpers::{Grid, Point, StringAdventExt},
+    cases::{DefaultError, GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    helpers::StringAdventExt,
+    twodee::{Bounds, Grid, Point, VecGrid},
 };
 use indoc::indoc;
 use std::{
@@ -13,12 +14,23 @@ pub fn get_puzzles() -> Vec<Box<dyn Puzzle>> {
     vec![Box::new(Part1), Box::new(Part2)]
 }
 
+fn parse_points(input: &str) -> Vec<Point<usize>> {
+    input
+        .trimmed_lines()
+        .map(|line| {
+            let (x, y) = line.split_once(", ").unwrap();
+            Point::new(x.parse().unwrap(), y.parse().unwrap())
+        })
+        .collect()
+}
+
 #[derive(Debug)]
 pub struct Part1;
 
 impl PuzzleRunner for Part1 {
     type Input = &'static str;
     type Output = usize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2018-D06-P1".to_owned()
@@ -66,19 +78,20 @@ impl PuzzleRunner for Part1 {
     }
 
     fn run_puzzle(input: Self::Input) -> Self::Output {
-        let points: Vec<Point> = input.trimmed_lines().map(|l| l.parse().unwrap()).collect();
+        let points = parse_points(input);
 
         let (max_x, max_y) = points.iter().fold((0, 0), |(max_x, max_y), next| {
             (cmp::max(max_x, next.x), cmp::max(max_y, next.y))
         });
 
         // Voronoi on all points, ignoring ties
-        let mut grid: Grid<Option<usize>> = Grid::new(max_x + 1, max_y + 1);
-        assert_eq!(grid[(0, 0)], None);
-        for point in grid.iter_coordinates() {
+        let mut grid: VecGrid<usize, Option<usize>> =
+            VecGrid::new(Bounds::new(0, 0, max_x + 1, max_y + 1));
+        assert_eq!(grid[Point::new(0, 0)], None);
+        for point in grid.iter_coords() {
             let mut distances: Vec<_> = points
                 .iter()
-                .map(|p| p.manhattan_distance(&point))
+                .map(|p| p.manhattan_distance(point))
                 .enumerate()
                 .collect::<Vec<_>>();
             distances.sort_by_key(|(_idx, distance)| *distance);
@@ -94,19 +107,20 @@ impl PuzzleRunner for Part1 {
 
         // find ids on the edges, which are infinite
         let mut infinite_groups: HashSet<usize> = HashSet::new();
-        for x in 0..grid.width() {
-            if let Some(id) = grid[(x, 0)] {
+        let (width, height) = (grid.bounds().width(), grid.bounds().height());
+        for x in 0..width {
+            if let Some(id) = grid[Point::new(x, 0)] {
                 infinite_groups.insert(id);
             }
-            if let Some(id) = grid[(x, grid.height() - 1)] {
+            if let Some(id) = grid[Point::new(x, height - 1)] {
                 infinite_groups.insert(id);
             }
         }
-        for y in 0..grid.height() {
-            if let Some(id) = grid[(0, y)] {
+        for y in 0..height {
+            if let Some(id) = grid[Point::new(0, y)] {
                 infinite_groups.insert(id);
             }
-            if let Some(id) = grid[(grid.width() - 1, y)] {
+            if let Some(id) = grid[Point::new(width - 1, y)] {
                 infinite_groups.insert(id);
             }
         }
@@ -135,6 +149,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = (&'static str, usize);
     type Output = usize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2018-D06-P2".to_owned()
@@ -152,7 +167,7 @@ impl PuzzleRunner for Part2 {
     }
 
     fn run_puzzle((input, max): Self::Input) -> Self::Output {
-        let points: Vec<Point> = input.trimmed_lines().map(|l| l.parse().unwrap()).collect();
+        let points = parse_points(input);
 
         let (max_x, max_y) = points.iter().fold((0, 0), |(max_x, max_y), next| {
             (cmp::max(max_x, next.x), cmp::max(max_y, next.y))
@@ -160,11 +175,11 @@ impl PuzzleRunner for Part2 {
 
         // For every grid coordinate, find the sum of the manhattan distances to all
         // given points.
-        let mut grid: Grid<usize> = Grid::new(max_x + 1, max_y + 1);
-        for coordinate in grid.iter_coordinates() {
+        let mut grid: VecGrid<usize, usize> = VecGrid::new(Bounds::new(0, 0, max_x + 1, max_y + 1));
+        for coordinate in grid.iter_coords() {
             grid[coordinate] = points
                 .iter()
-                .map(|p| p.manhattan_distance(&coordinate))
+                .map(|p| p.manhattan_distance(coordinate))
                 .sum();
         }
         grid.iter_values().filter(|v| **v < max).count()
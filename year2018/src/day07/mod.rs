@@ -1,10 +1,10 @@
 use advent_lib::{
-    cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    cases::{DefaultError, GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    error::AdventError,
     helpers::StringAdventExt,
+    parse_fmt,
 };
-use lazy_static::lazy_static;
 use petgraph::stable_graph::StableGraph;
-use regex::Regex;
 use std::{
     collections::{BTreeSet, HashMap},
     iter::Iterator,
@@ -20,6 +20,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = &'static str;
     type Output = String;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2018-D07-P1".to_owned()
@@ -87,19 +88,14 @@ struct EdgeDescription {
 }
 
 impl std::str::FromStr for EdgeDescription {
-    type Err = ();
+    type Err = AdventError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        lazy_static! {
-            static ref INPUT_RE: Regex =
-                Regex::new(r#"Step ([A-Z]) must be finished before step ([A-Z]) can begin."#)
-                    .unwrap();
-        }
-        let matches = INPUT_RE.captures(s).ok_or(())?;
-        Ok(Self {
-            name: matches[1].chars().next().unwrap(),
-            blocks: matches[2].chars().next().unwrap(),
-        })
+        let (name, blocks) = parse_fmt!(
+            "Step {name} must be finished before step {blocks} can begin.",
+            s => (char, char)
+        )?;
+        Ok(Self { name, blocks })
     }
 }
 
@@ -109,6 +105,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = (&'static str, usize, u32);
     type Output = u32;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2018-D07-P2".to_owned()
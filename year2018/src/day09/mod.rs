@@ -1,5 +1,8 @@
-use advent_lib::cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner};
-use regex::Regex;
+use advent_lib::{
+    cases::{DefaultError, GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    error::AdventError,
+    parse_fmt,
+};
 use std::{collections::VecDeque, iter::Iterator};
 
 pub fn get_puzzles() -> Vec<Box<dyn Puzzle>> {
@@ -12,6 +15,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = &'static str;
     type Output = u32;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2018-D09-P1".to_owned()
@@ -96,18 +100,16 @@ impl GameDescription {
 }
 
 impl std::str::FromStr for GameDescription {
-    type Err = Box<dyn std::error::Error>;
+    type Err = AdventError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let number_re = Regex::new(r#"(\d+)"#).unwrap();
-        let numbers: Vec<u32> = number_re
-            .captures_iter(s)
-            .map(|captures| captures.get(0).unwrap().as_str().parse().unwrap())
-            .collect();
-        assert!(numbers.len() == 2);
+        let (num_players, last_marble) = parse_fmt!(
+            "{players} players; last marble is worth {points} points",
+            s.trim() => (u32, u32)
+        )?;
         Ok(Self {
-            num_players: numbers[0],
-            last_marble: numbers[1],
+            num_players,
+            last_marble,
         })
     }
 }
@@ -118,6 +120,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = &'static str;
     type Output = u32;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2018-D09-P2".to_owned()
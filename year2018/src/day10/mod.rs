@@ -1,27 +1,11 @@
 use advent_lib::{
-    cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    cases::{DefaultError, GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    grid_letters::{GridLetters, Recognizer, ALPHABET_2018_D10},
     helpers::StringAdventExt,
+    twodee::{Bounds, Point},
 };
 use itertools::Itertools;
-use lalrpop_util::lalrpop_mod;
-use std::{cmp, iter::Iterator, str::FromStr};
-
-#[cfg(windows)]
-lalrpop_mod!(
-    #[allow(clippy::all)]
-    #[allow(clippy::nursery)]
-    #[allow(clippy::pedantic)]
-    parser,
-    "\\day10\\parser.rs"
-);
-#[cfg(unix)]
-lalrpop_mod!(
-    #[allow(clippy::all)]
-    #[allow(clippy::nursery)]
-    #[allow(clippy::pedantic)]
-    parser,
-    "/day10/parser.rs"
-);
+use std::{iter::Iterator, str::FromStr};
 
 pub fn get_puzzles() -> Vec<Box<dyn Puzzle>> {
     vec![Box::new(Part1), Box::new(Part2)]
@@ -30,21 +14,40 @@ pub fn get_puzzles() -> Vec<Box<dyn Puzzle>> {
 #[derive(Debug)]
 pub struct Part1;
 
-// TODO(challenge) How can this return a meaningful answer instead of printing
-// the output?
-
 impl PuzzleRunner for Part1 {
     type Input = &'static str;
-    type Output = ();
+    type Output = GridLetters;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2018-D10-P1".to_owned()
     }
 
     fn cases(&self) -> Result<Vec<Box<dyn PuzzleCase>>, Box<dyn std::error::Error>> {
+        // The example uses a taller font than the real input, so it is compared
+        // as a drawing.
+        let example = vec![
+            "#   #  ###",
+            "#   #   #",
+            "#   #   #",
+            "#####   #",
+            "#   #   #",
+            "#   #   #",
+            "#   #   #",
+            "#   #  ###",
+        ]
+        .join("\n");
         Ok(GenericPuzzleCase::<Self, _, _>::build_set()
-            .case("Example", include_str!("example"), ())
-            .case("Solution", include_str!("input"), ())
+            .case(
+                "Example",
+                include_str!("example"),
+                GridLetters::from(example),
+            )
+            .case(
+                "Solution",
+                include_str!("input"),
+                GridLetters::from("PANLPAPR"),
+            )
             .collect())
     }
 
@@ -61,8 +64,7 @@ impl PuzzleRunner for Part1 {
                 min_score = new_score;
             } else {
                 system.step_reverse();
-                println!("{}", system);
-                return;
+                return Recognizer::new(ALPHABET_2018_D10).read(&system.to_string());
             }
         }
 
@@ -73,13 +75,6 @@ impl PuzzleRunner for Part1 {
     }
 }
 
-struct Bounds {
-    min_x: i32,
-    max_x: i32,
-    min_y: i32,
-    max_y: i32,
-}
-
 pub struct ParticleSystem {
     particles: Vec<Particle>,
 }
@@ -100,50 +95,28 @@ impl ParticleSystem {
     }
 
     /// calculate bounding box of all particles
-    fn bounds(&self) -> Bounds {
-        let (min_x, max_x, min_y, max_y) = self.particles.iter().fold(
-            (
-                i32::max_value(),
-                i32::min_value(),
-                i32::max_value(),
-                i32::min_value(),
-            ),
-            |(min_x, max_x, min_y, max_y), p| {
-                (
-                    cmp::min(min_x, p.position.0),
-                    cmp::max(max_x, p.position.0),
-                    cmp::min(min_y, p.position.1),
-                    cmp::max(max_y, p.position.1),
-                )
-            },
-        );
-        Bounds {
-            min_x,
-            max_x,
-            min_y,
-            max_y,
-        }
+    fn bounds(&self) -> Bounds<i32> {
+        self.particles
+            .iter()
+            .map(|p| Point::new(p.position.0, p.position.1))
+            .collect()
     }
 
     // Calculate a score for this arrangement of particles. Low score are more
     // likely to be the solution
     fn score(&self) -> u64 {
-        // Use the area of the bounding box as the score
-        let Bounds {
-            min_x,
-            max_x,
-            min_y,
-            max_y,
-        } = self.bounds();
-        ((max_x - min_x).abs() as u64) * ((max_y - min_y).abs() as u64)
+        // Use the area of the bounding box as the score. The area is
+        // calculated in a u64, because it overflows an i32 at the start
+        let bounds = self.bounds();
+        (bounds.width() as u64) * (bounds.height() as u64)
     }
 }
 
 impl std::fmt::Display for ParticleSystem {
     fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
         let bounds = self.bounds();
-        let width = (bounds.max_x - bounds.min_x + 1) as usize;
-        let height = (bounds.max_y - bounds.min_y + 1) as usize;
+        let width = bounds.width() as usize;
+        let height = bounds.height() as usize;
 
         let mut canvas: Vec<Vec<char>> = Vec::new();
         canvas.resize_with(height, || {
@@ -156,8 +129,8 @@ impl std::fmt::Display for ParticleSystem {
             position: (px, py), ..
         } in &self.particles
         {
-            let x = (px - bounds.min_x) as usize;
-            let y = (py - bounds.min_y) as usize;
+            let x = (px - bounds.top_left().x) as usize;
+            let y = (py - bounds.top_left().y) as usize;
             canvas[y][x] = '#';
         }
 
@@ -184,13 +157,18 @@ impl Particle {
 }
 
 impl FromStr for Particle {
-    // TODO better error handling
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        parser::ParticleParser::new()
-            .parse(s)
-            .map_err(|e| format!("Error parsing {}: {}", s, e))
+        // Lines look like `position=< 7,  0> velocity=<-1,  0>`
+        let numbers: Vec<i32> = s
+            .try_ints()
+            .collect::<Result<_, _>>()
+            .map_err(|err| format!("Bad number in {}: {}", s, err))?;
+        match numbers[..] {
+            [x, y, dx, dy] => Ok(Self::new((x, y), (dx, dy))),
+            _ => Err(format!("Expected a position and a velocity in {}", s)),
+        }
     }
 }
 
@@ -200,6 +178,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = &'static str;
     type Output = u32;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2018-D10-P2".to_owned()
@@ -1,12 +1,15 @@
 use advent_lib::{
-    cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
-    helpers::{Grid, Point},
+    cases::{DefaultError, GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    context::RunContext,
+    error::AdventError,
+    twodee::{Bounds, Grid, Point, VecGrid},
 };
 use itertools::Itertools;
 use rayon::prelude::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 pub fn get_puzzles() -> Vec<Box<dyn Puzzle>> {
-    vec![Box::new(Part1), Box::new(Part2)]
+    vec![Box::new(Part1), Box::new(Part2SummedArea), Box::new(Part2)]
 }
 
 #[derive(Debug)]
@@ -15,6 +18,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = i32;
     type Output = String;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2018-D11-P1".to_owned()
@@ -34,22 +38,19 @@ impl PuzzleRunner for Part1 {
     fn run_puzzle(input: Self::Input) -> Self::Output {
         let size = 300;
 
-        let mut grid: Grid<i32> = Grid::new(size, size);
-        for idx in grid.iter_coordinates() {
-            grid[idx] = power_level(idx, input);
-        }
+        let grid = power_grid(size, input);
 
         let max_point = (0..size - 2)
             .cartesian_product(0..size - 2)
-            .map(Into::into)
-            .map(|p: Point| {
+            .map(|(x, y)| Point::new(x, y))
+            .map(|p| {
                 let power_sum = (p.x..p.x + 3)
                     .cartesian_product(p.y..p.y + 3)
-                    .map(|(xd, yd)| grid[(xd, yd)])
+                    .map(|(xd, yd)| grid[Point::new(xd, yd)])
                     .sum();
                 (power_sum, p)
             })
-            .max_by_key(|(power, _coord): &(i32, Point)| *power)
+            .max_by_key(|(power, _coord): &(i32, Point<usize>)| *power)
             .unwrap()
             .1;
 
@@ -57,7 +58,15 @@ impl PuzzleRunner for Part1 {
     }
 }
 
-const fn power_level(cell: Point, grid_serial: i32) -> i32 {
+fn power_grid(size: usize, grid_serial: i32) -> VecGrid<usize, i32> {
+    let mut grid = VecGrid::new(Bounds::new(0, 0, size, size));
+    for idx in grid.iter_coords() {
+        grid[idx] = power_level(idx, grid_serial);
+    }
+    grid
+}
+
+const fn power_level(cell: Point<usize>, grid_serial: i32) -> i32 {
     // Find the fuel cell's rack ID, which is its X coordinate plus 10.
     let rack_id: i32 = cell.x as i32 + 10;
     // Begin with a power level of the rack ID times the Y coordinate.
@@ -75,49 +84,70 @@ const fn power_level(cell: Point, grid_serial: i32) -> i32 {
 
 #[cfg(test)]
 mod tests {
-    use super::power_level;
+    use super::{power_level, Part2SummedArea};
+    use advent_lib::{cases::PuzzleRunner, context::RunContext, twodee::Point};
 
     #[test]
     fn test_power_level() {
-        assert_eq!(power_level((3, 5).into(), 8), 4);
-        assert_eq!(power_level((122, 79).into(), 57), -5);
-        assert_eq!(power_level((217, 196).into(), 39), 0);
-        assert_eq!(power_level((101, 153).into(), 71), 4);
+        assert_eq!(power_level(Point::new(3, 5), 8), 4);
+        assert_eq!(power_level(Point::new(122, 79), 57), -5);
+        assert_eq!(power_level(Point::new(217, 196), 39), 0);
+        assert_eq!(power_level(Point::new(101, 153), 71), 4);
     }
+
+    #[test]
+    fn test_summed_area() {
+        assert_eq!(
+            Part2SummedArea::try_run_puzzle_with_context(18, &RunContext::new()).unwrap(),
+            "90,269,16".to_owned()
+        );
+    }
+}
+
+fn part2_cases<T>() -> Vec<Box<dyn PuzzleCase>>
+where
+    T: PuzzleRunner<Input = i32, Output = String> + 'static,
+    T::Error: Into<Box<dyn std::error::Error>>,
+{
+    GenericPuzzleCase::<T, _, _>::build_set()
+        .case("Example", 18, "90,269,16".to_owned())
+        .case("Example", 42, "232,251,12".to_owned())
+        .case(
+            "Solution",
+            include_str!("input").trim().parse::<i32>().unwrap(),
+            "227,199,19".to_owned(),
+        )
+        .collect()
 }
 
+/// Checks squares up to 20 cells wide, adding up every cell in each square.
 #[derive(Debug)]
 pub struct Part2;
 
 impl PuzzleRunner for Part2 {
     type Input = i32;
     type Output = String;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2018-D11-P2".to_owned()
     }
 
+    fn implementation(&self) -> Option<String> {
+        Some("naive".to_owned())
+    }
+
     fn cases(&self) -> Result<Vec<Box<dyn PuzzleCase>>, Box<dyn std::error::Error>> {
-        Ok(GenericPuzzleCase::<Self, _, _>::build_set()
-            .case("Example", 18, "90,269,16".to_owned())
-            .case("Example", 42, "232,251,12".to_owned())
-            .case(
-                "Solution",
-                include_str!("input").trim().parse::<i32>().unwrap(),
-                "227,199,19".to_owned(),
-            )
-            .collect())
+        Ok(part2_cases::<Self>())
     }
 
-    fn run_puzzle(input: Self::Input) -> Self::Output {
-        // TODO(performance) This really isn't fast enough. There are more
-        // efficient algorithms for maximum subset of a grid, use them.
+    fn try_run_puzzle_with_context(
+        input: Self::Input,
+        ctx: &RunContext,
+    ) -> Result<Self::Output, Self::Error> {
         let grid_size = 300;
 
-        let mut grid: Grid<i32> = Grid::new(grid_size, grid_size);
-        for idx in grid.iter_coordinates() {
-            grid[idx] = power_level(idx, input);
-        }
+        let grid = power_grid(grid_size, input);
 
         let candidates: Vec<(usize, usize, usize)> = (1..=20)
             .flat_map(|square_size| {
@@ -129,19 +159,86 @@ impl PuzzleRunner for Part2 {
             })
             .collect();
 
+        let checked = AtomicUsize::new(0);
         let max_ident = candidates
             .par_iter()
-            .map(|(x, y, size)| {
+            .filter_map(|(x, y, size)| {
+                let done = checked.fetch_add(1, Ordering::Relaxed);
+                if done % 0x1_0000 == 0 {
+                    ctx.progress(done, candidates.len());
+                }
+                if ctx.is_cancelled() {
+                    return None;
+                }
                 let power_sum: i32 = (*x..x + size)
                     .cartesian_product(*y..y + size)
-                    .map(|(xd, yd)| grid[(xd, yd)])
+                    .map(|(xd, yd)| grid[Point::new(xd, yd)])
                     .sum();
-                (power_sum, (x, y, size))
+                Some((power_sum, (x, y, size)))
             })
-            .max_by_key(|(power, _ident)| *power)
-            .unwrap()
-            .1;
+            .max_by_key(|(power, _ident)| *power);
+
+        ctx.check()?;
+        let (_, max_ident) = max_ident.ok_or(AdventError::NoSolution)?;
+        Ok(format!("{},{},{}", max_ident.0, max_ident.1, max_ident.2))
+    }
+}
+
+/// Checks squares of every size, using a summed-area table to find the power
+/// of each square in constant time.
+#[derive(Debug)]
+pub struct Part2SummedArea;
+
+impl PuzzleRunner for Part2SummedArea {
+    type Input = i32;
+    type Output = String;
+    type Error = DefaultError;
+
+    fn name(&self) -> String {
+        "2018-D11-P2".to_owned()
+    }
+
+    fn implementation(&self) -> Option<String> {
+        Some("summed-area".to_owned())
+    }
+
+    fn cases(&self) -> Result<Vec<Box<dyn PuzzleCase>>, Box<dyn std::error::Error>> {
+        Ok(part2_cases::<Self>())
+    }
+
+    fn try_run_puzzle_with_context(
+        input: Self::Input,
+        ctx: &RunContext,
+    ) -> Result<Self::Output, Self::Error> {
+        let grid_size = 300;
+
+        // `sums[y][x]` is the total power of the cells from (1, 1) to (x, y),
+        // inclusive. Row and column 0 are left empty to avoid special cases.
+        let mut sums = vec![vec![0_i32; grid_size + 1]; grid_size + 1];
+        for y in 1..=grid_size {
+            for x in 1..=grid_size {
+                sums[y][x] = power_level(Point::new(x, y), input) + sums[y - 1][x] + sums[y][x - 1]
+                    - sums[y - 1][x - 1];
+            }
+        }
+
+        let mut best: Option<(i32, (usize, usize, usize))> = None;
+        for size in 1..=grid_size {
+            ctx.progress(size, grid_size);
+            ctx.check()?;
+            for y in 1..=grid_size + 1 - size {
+                for x in 1..=grid_size + 1 - size {
+                    let (x1, y1) = (x + size - 1, y + size - 1);
+                    let power =
+                        sums[y1][x1] - sums[y - 1][x1] - sums[y1][x - 1] + sums[y - 1][x - 1];
+                    if best.map_or(true, |(max_power, _)| power > max_power) {
+                        best = Some((power, (x, y, size)));
+                    }
+                }
+            }
+        }
 
-        format!("{},{},{}", max_ident.0, max_ident.1, max_ident.2)
+        let (_, (x, y, size)) = best.ok_or(AdventError::NoSolution)?;
+        Ok(format!("{},{},{}", x, y, size))
     }
 }
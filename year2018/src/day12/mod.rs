@@ -1,4 +1,7 @@
-use advent_lib::cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner};
+use advent_lib::{
+    cases::{DefaultError, GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    error::AdventError,
+};
 use std::{
     collections::{HashMap, HashSet},
     convert::TryFrom,
@@ -14,6 +17,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = PotsState;
     type Output = i64;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2018-D12-P1".to_owned()
@@ -21,7 +25,7 @@ impl PuzzleRunner for Part1 {
 
     fn cases(&self) -> Result<Vec<Box<dyn PuzzleCase>>, Box<dyn std::error::Error>> {
         Ok(GenericPuzzleCase::<Self, _, _>::build_set()
-            .add_transform(|s| s.parse().unwrap())
+            .add_try_transform(|s| Ok(s.parse()?))
             .transformed_case("Example", include_str!("example"), 325)?
             .transformed_case("Solution", include_str!("input"), 1_184)?
             .collect())
@@ -128,47 +132,50 @@ impl PotsState {
     }
 }
 
-#[derive(Debug)]
-pub struct PotsError(String);
-
-impl From<std::option::NoneError> for PotsError {
-    fn from(_: std::option::NoneError) -> Self {
-        Self("Tried to unwrap None".to_owned())
-    }
-}
-
 impl std::str::FromStr for PotsState {
-    type Err = PotsError;
+    type Err = AdventError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        fn char_to_bool(c: char) -> bool {
+        fn char_to_bool(c: char) -> Result<bool, AdventError> {
             match c {
-                '.' => false,
-                '#' => true,
-                _ => panic!(format!("Unexpected bool-char: {}", c)),
+                '.' => Ok(false),
+                '#' => Ok(true),
+                _ => Err(AdventError::parse(c, "Unexpected bool-char")),
             }
         }
         let mut lines = s.lines();
 
         // initial line looks like:
         //    initial state: #..#.#..##......###...###
-        let initial_line = lines.next().unwrap();
+        let initial_line = lines
+            .next()
+            .ok_or_else(|| AdventError::invalid_input("Missing initial state"))?;
         let initial = initial_line
             .split_whitespace()
             .nth(2)
-            .unwrap()
+            .ok_or_else(|| AdventError::parse(initial_line, "Missing initial pots"))?
             .chars()
             .map(char_to_bool)
-            .collect();
+            .collect::<Result<_, _>>()?;
 
-        lines.next().unwrap(); // skip a blank line
+        lines.next(); // skip a blank line
         let mut rules = HashMap::new();
         for rule_line in lines {
             // rule lines look like:
             //   ##... => .
             let mut parts = rule_line.split(" => ");
-            let rule_key: Vec<bool> = parts.next()?.chars().map(char_to_bool).collect();
-            let rule_result = char_to_bool(parts.next()?.chars().next()?);
+            let rule_key: Vec<bool> = parts
+                .next()
+                .ok_or_else(|| AdventError::parse(rule_line, "Missing rule key"))?
+                .chars()
+                .map(char_to_bool)
+                .collect::<Result<_, _>>()?;
+            let rule_result = char_to_bool(
+                parts
+                    .next()
+                    .and_then(|result| result.chars().next())
+                    .ok_or_else(|| AdventError::parse(rule_line, "Missing rule result"))?,
+            )?;
             rules.insert(rule_key, rule_result);
         }
 
@@ -187,6 +194,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = PotsState;
     type Output = i64;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2018-D12-P2".to_owned()
@@ -194,7 +202,7 @@ impl PuzzleRunner for Part2 {
 
     fn cases(&self) -> Result<Vec<Box<dyn PuzzleCase>>, Box<dyn std::error::Error>> {
         Ok(GenericPuzzleCase::<Self, _, _>::build_set()
-            .add_transform(|s| s.parse().unwrap())
+            .add_try_transform(|s| Ok(s.parse()?))
             .transformed_case("Solution", include_str!("input"), 250_000_000_219)?
             .collect())
     }
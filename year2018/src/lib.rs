@@ -10,7 +10,6 @@
 
     clippy::use_self, // doesn't work well with generics
 )]
-#![feature(try_trait)]
 
 use advent_lib::cases::Puzzle;
 
@@ -48,3 +47,6 @@ pub fn get_puzzles() -> Vec<Box<dyn Puzzle>> {
     .flatten()
     .collect()
 }
+
+#[cfg(feature = "plugin")]
+advent_lib::export_plugin!(get_puzzles);
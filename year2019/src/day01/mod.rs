@@ -1,5 +1,5 @@
 use advent_lib::{
-    cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    cases::{DefaultError, GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
     helpers::StringAdventExt,
 };
 use std::iter::Iterator;
@@ -14,6 +14,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = Vec<i32>;
     type Output = i32;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2019-D01-P1".to_owned()
@@ -41,6 +42,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = Vec<i32>;
     type Output = i32;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2019-D01-P2".to_owned()
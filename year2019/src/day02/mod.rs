@@ -1,6 +1,7 @@
 use crate::intcode::IntcodeComputer;
 use advent_lib::{
-    cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    cases::{DefaultError, GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    error::AdventError,
     helpers::StringAdventExt,
 };
 use std::iter::Iterator;
@@ -15,6 +16,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = Vec<isize>;
     type Output = isize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2019-D02-P1".to_owned()
@@ -42,6 +44,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = Vec<isize>;
     type Output = isize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2019-D02-P2".to_owned()
@@ -54,7 +57,7 @@ impl PuzzleRunner for Part2 {
             .collect())
     }
 
-    fn run_puzzle(program: Self::Input) -> Self::Output {
+    fn try_run_puzzle(program: Self::Input) -> Result<Self::Output, Self::Error> {
         for a in 0..100 {
             for b in 0..100 {
                 let mut modified_program = program.clone();
@@ -64,11 +67,11 @@ impl PuzzleRunner for Part2 {
                 computer.run_to_end();
 
                 if computer.read_mem(0) == 19_690_720 {
-                    return 100 * a + b;
+                    return Ok(100 * a + b);
                 }
             }
         }
-        panic!("No answer found");
+        Err(AdventError::NoSolution.into())
     }
 }
 
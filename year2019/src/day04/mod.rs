@@ -1,4 +1,4 @@
-use advent_lib::cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner};
+use advent_lib::cases::{DefaultError, GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner};
 use itertools::Itertools;
 use std::{collections::HashSet, iter::Iterator};
 
@@ -12,6 +12,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = (u32, u32);
     type Output = usize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2019-D04-P1".to_owned()
@@ -47,6 +48,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = (u32, u32);
     type Output = usize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2019-D04-P2".to_owned()
@@ -1,6 +1,6 @@
 use crate::intcode::IntcodeComputer;
 use advent_lib::{
-    cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    cases::{DefaultError, GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
     helpers::StringAdventExt,
 };
 use std::iter::Iterator;
@@ -15,6 +15,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = Vec<isize>;
     type Output = isize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2019-D05-P1".to_owned()
@@ -40,6 +41,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = Vec<isize>;
     type Output = isize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2019-D05-P2".to_owned()
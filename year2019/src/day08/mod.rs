@@ -1,4 +1,7 @@
-use advent_lib::cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner};
+use advent_lib::{
+    cases::{DefaultError, GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    grid_letters::{GridLetters, Recognizer, ALPHABET_2019_D08},
+};
 use std::{collections::HashMap, iter::Iterator};
 
 pub fn get_puzzles() -> Vec<Box<dyn Puzzle>> {
@@ -11,6 +14,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = Vec<char>;
     type Output = usize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2019-D08-P1".to_owned()
@@ -47,7 +51,8 @@ pub struct Part2;
 
 impl PuzzleRunner for Part2 {
     type Input = Vec<char>;
-    type Output = ();
+    type Output = GridLetters;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2019-D08-P2".to_owned()
@@ -56,7 +61,11 @@ impl PuzzleRunner for Part2 {
     fn cases(&self) -> Result<Vec<Box<dyn PuzzleCase>>, Box<dyn std::error::Error>> {
         Ok(GenericPuzzleCase::<Self, _, _>::build_set()
             .add_transform(parse_input)
-            .transformed_case("Solution", include_str!("input"), ())?
+            .transformed_case(
+                "Solution",
+                include_str!("input"),
+                GridLetters::from("LGYHB"),
+            )?
             .collect())
     }
 
@@ -64,6 +73,7 @@ impl PuzzleRunner for Part2 {
         let width = 25;
         let height = 6;
         let layers: Vec<&[char]> = input.chunks(width * height).collect();
+        let mut image = String::with_capacity((width * 2 + 1) * height);
         for y in 0..height {
             for x in 0..width {
                 let pixel_offset = x + y * width;
@@ -73,13 +83,14 @@ impl PuzzleRunner for Part2 {
                     .find(|p| *p != '2')
                     .unwrap_or('0');
                 match pixel {
-                    '0' => print!("  "),
-                    '1' => print!("██"),
+                    '0' => image.push_str("  "),
+                    '1' => image.push_str("██"),
                     _ => panic!("unexpected, non-transparent pixel {}", pixel),
                 }
             }
-            println!();
+            image.push('\n');
         }
+        Recognizer::new(ALPHABET_2019_D08).read(&image)
     }
 }
 
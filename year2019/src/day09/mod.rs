@@ -1,5 +1,5 @@
 use crate::intcode::IntcodeComputer;
-use advent_lib::cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner};
+use advent_lib::cases::{DefaultError, GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner};
 use std::iter::Iterator;
 
 pub fn get_puzzles() -> Vec<Box<dyn Puzzle>> {
@@ -12,6 +12,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = Vec<isize>;
     type Output = isize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2019-D09-P1".to_owned()
@@ -37,6 +38,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = Vec<isize>;
     type Output = isize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2019-D09-P2".to_owned()
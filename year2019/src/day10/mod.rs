@@ -1,8 +1,9 @@
 use advent_lib::{
-    cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
-    twodee::Point,
+    cases::{DefaultError, GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    error::AdventError,
+    twodee::{Grid, Point, VecGrid},
 };
-use std::iter::Iterator;
+use std::{convert::TryFrom, fmt, iter::Iterator};
 
 pub fn get_puzzles() -> Vec<Box<dyn Puzzle>> {
     vec![Box::new(Part1), Box::new(Part2)]
@@ -14,6 +15,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = Map;
     type Output = usize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2019-D10-P1".to_owned()
@@ -21,7 +23,7 @@ impl PuzzleRunner for Part1 {
 
     fn cases(&self) -> Result<Vec<Box<dyn PuzzleCase>>, Box<dyn std::error::Error>> {
         Ok(GenericPuzzleCase::<Self, _, _>::build_set()
-            .add_transform(parse_input)
+            .add_try_transform(parse_input)
             .transformed_case("Debug", "###\n#..\n...", 3)?
             .transformed_case("Example 0", include_str!("example0"), 8)?
             .transformed_case("Example 1", include_str!("example1"), 33)?
@@ -43,6 +45,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = Map;
     type Output = usize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2019-D10-P2".to_owned()
@@ -50,7 +53,7 @@ impl PuzzleRunner for Part2 {
 
     fn cases(&self) -> Result<Vec<Box<dyn PuzzleCase>>, Box<dyn std::error::Error>> {
         Ok(GenericPuzzleCase::<Self, _, _>::build_set()
-            .add_transform(parse_input)
+            .add_try_transform(parse_input)
             .transformed_case("Example 4", include_str!("example4"), 802)?
             .transformed_case("Solution", include_str!("input"), 608)?
             .collect())
@@ -93,31 +96,58 @@ const fn gcd(mut a: u32, mut b: u32) -> u32 {
     a
 }
 
-#[derive(Clone, Debug)]
-pub struct Map {
-    space: Vec<Vec<bool>>,
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Space {
+    Empty,
+    Asteroid,
 }
 
-impl Map {
-    fn get(&self, Point { x, y }: Point<usize>) -> bool {
-        self.space[y][x]
+impl Default for Space {
+    fn default() -> Self {
+        Self::Empty
     }
+}
+
+impl TryFrom<char> for Space {
+    type Error = String;
 
-    fn set(&mut self, Point { x, y }: Point<usize>, v: bool) {
-        self.space[y][x] = v;
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '.' => Ok(Self::Empty),
+            '#' => Ok(Self::Asteroid),
+            _ => Err(format!("bad character: {}", c)),
+        }
     }
+}
 
-    fn width(&self) -> usize {
-        self.space.len()
+impl fmt::Display for Space {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "."),
+            Self::Asteroid => write!(f, "#"),
+        }
     }
+}
 
-    fn height(&self) -> usize {
-        self.space[0].len()
+#[derive(Clone, Debug)]
+pub struct Map {
+    space: VecGrid<isize, Space>,
+}
+
+impl Map {
+    fn get(&self, p: Point<usize>) -> bool {
+        self.space.get(p.into()) == Some(&Space::Asteroid)
+    }
+
+    fn set(&mut self, p: Point<usize>, v: bool) {
+        self.space
+            .set(p.into(), if v { Space::Asteroid } else { Space::Empty });
     }
 
     fn asteroid_locations(&self) -> Vec<Point<usize>> {
-        (0..self.height())
-            .flat_map(|y| (0..self.width()).map(move |x| Point::new(x, y)))
+        self.space
+            .iter_coords()
+            .map(Into::into)
             .filter(|pos| self.get(*pos))
             .collect()
     }
@@ -155,42 +185,32 @@ impl Map {
 }
 
 impl std::str::FromStr for Map {
-    type Err = String;
+    type Err = AdventError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let space = s
-            .lines()
-            .map(|line| {
-                line.chars()
-                    .map(|c| match c {
-                        '#' => true,
-                        '.' => false,
-                        _ => panic!("bad character: {}", c),
-                    })
-                    .collect()
-            })
-            .collect();
-        Ok(Map { space })
+        Ok(Self {
+            space: VecGrid::parse(s)?,
+        })
     }
 }
 
-impl std::fmt::Display for Map {
-    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
-        for row in &self.space {
-            writeln!(
-                fmt,
-                "{}",
-                row.iter()
-                    .map(|a| if *a { '#' } else { '.' })
-                    .collect::<String>()
-            )?;
+impl fmt::Display for Map {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let bounds = self.space.bounds();
+        let (top_left, bottom_right) = (bounds.top_left(), bounds.bottom_right());
+        for y in top_left.y..bottom_right.y {
+            let row: String = (top_left.x..bottom_right.x)
+                .filter_map(|x| self.space.get(Point::new(x, y)))
+                .map(ToString::to_string)
+                .collect();
+            writeln!(f, "{}", row)?;
         }
         Ok(())
     }
 }
 
-fn parse_input(input: &str) -> Map {
-    input.parse().unwrap()
+fn parse_input(input: &str) -> Result<Map, Box<dyn std::error::Error>> {
+    Ok(input.parse()?)
 }
 
 #[cfg(test)]
@@ -1,6 +1,6 @@
 use crate::intcode::{IntcodeComputer, PauseReason};
 use advent_lib::{
-    cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    cases::{DefaultError, GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
     grid_letters::{Recognizer, ALPHABET_2019_D11},
     twodee::{Dir4, Point, Turn},
 };
@@ -20,6 +20,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = Vec<isize>;
     type Output = usize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2019-D11-P1".to_owned()
@@ -65,6 +66,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = Vec<isize>;
     type Output = String;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2019-D11-P2".to_owned()
@@ -1,8 +1,9 @@
 #![allow(clippy::match_ref_pats)]
 
 use advent_lib::{
-    cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    cases::{DefaultError, GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
     helpers::StringAdventExt,
+    property::{shrink_int, shrink_vec, PropertyCase, Rng},
 };
 use std::{
     cmp::{Ord, Ordering},
@@ -20,6 +21,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = Vec<Point3>;
     type Output = i32;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2019-D12-P1".to_owned()
@@ -69,6 +71,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = Vec<Point3>;
     type Output = usize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2019-D12-P2".to_owned()
@@ -78,6 +81,17 @@ impl PuzzleRunner for Part2 {
         Ok(GenericPuzzleCase::<Self, _, _>::build_set()
             .add_transform(parse_input)
             .transformed_case("Solution", include_str!("input"), 303_070_460_651_184)?
+            .property_case(
+                PropertyCase::<Self>::new("Random", generate_positions, |positions| {
+                    period_by_simulation(positions, 100_000)
+                })
+                .with_shrink(|positions| {
+                    shrink_vec(positions, shrink_point)
+                        .into_iter()
+                        .filter(|smaller| !smaller.is_empty())
+                        .collect()
+                }),
+            )
             .collect())
     }
 
@@ -133,6 +147,55 @@ impl PuzzleRunner for Part2 {
     }
 }
 
+/// Simulate all three axes together until the universe returns to its
+/// starting state, giving up after `max_steps`. Only practical for small
+/// inputs.
+fn period_by_simulation(planet_positions: &[Point3], max_steps: usize) -> Option<usize> {
+    let planets: Vec<Planet> = planet_positions
+        .iter()
+        .map(|&pos| Planet {
+            pos,
+            vel: Point3 { x: 0, y: 0, z: 0 },
+        })
+        .collect();
+
+    let mut universe = Universe {
+        step_count: 0,
+        planets: planets.clone(),
+    };
+
+    while universe.step_count < max_steps {
+        universe.step();
+        if universe.planets == planets {
+            return Some(universe.step_count);
+        }
+    }
+    None
+}
+
+fn generate_positions(rng: &mut Rng) -> Vec<Point3> {
+    (0..rng.range(2..=4))
+        .map(|_| Point3 {
+            x: rng.range(-3..=3) as i32,
+            y: rng.range(-3..=3) as i32,
+            z: rng.range(-3..=3) as i32,
+        })
+        .collect()
+}
+
+fn shrink_point(point: &Point3) -> Vec<Point3> {
+    let xs = shrink_int(point.x)
+        .into_iter()
+        .map(|x| Point3 { x, ..*point });
+    let ys = shrink_int(point.y)
+        .into_iter()
+        .map(|y| Point3 { y, ..*point });
+    let zs = shrink_int(point.z)
+        .into_iter()
+        .map(|z| Point3 { z, ..*point });
+    xs.chain(ys).chain(zs).collect()
+}
+
 // TODO unify this between here and 2017-d13 and 2019-d10
 const fn gcd(mut a: usize, mut b: usize) -> usize {
     // euclid's algorithm
@@ -151,7 +214,7 @@ fn parse_input(input: &str) -> Vec<Point3> {
         .collect()
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Point3 {
     x: i32,
     y: i32,
@@ -161,13 +224,10 @@ pub struct Point3 {
 impl std::str::FromStr for Point3 {
     type Err = String;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts: Vec<_> = s
-            .replace('<', "")
-            .replace('>', "")
-            .replace(' ', "")
-            .split(',')
-            .map(|c| c.split('=').last().unwrap().parse().unwrap())
-            .collect();
+        let parts: Vec<i32> = s
+            .try_ints()
+            .collect::<Result<_, _>>()
+            .map_err(|err| format!("Bad number in {}: {}", s, err))?;
         if parts.len() == 3 {
             Ok(Self {
                 x: parts[0],
@@ -180,7 +240,7 @@ impl std::str::FromStr for Point3 {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 struct Planet {
     pos: Point3,
     vel: Point3,
@@ -1,5 +1,5 @@
 use crate::intcode::{IntcodeComputer, PauseReason};
-use advent_lib::cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner};
+use advent_lib::cases::{DefaultError, GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner};
 use std::{cmp::Ordering, collections::VecDeque, iter::Iterator};
 
 pub fn get_puzzles() -> Vec<Box<dyn Puzzle>> {
@@ -12,6 +12,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = Vec<isize>;
     type Output = usize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2019-D13-P1".to_owned()
@@ -43,6 +44,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = Vec<isize>;
     type Output = isize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2019-D13-P2".to_owned()
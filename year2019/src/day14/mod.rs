@@ -1,5 +1,12 @@
 use advent_lib::{
-    cases::{/* ExpectedValue, */ GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    cases::{
+        // ExpectedValue,
+        DefaultError,
+        GenericPuzzleCase,
+        Puzzle,
+        PuzzleCase,
+        PuzzleRunner,
+    },
     helpers::StringAdventExt,
 };
 use std::{cmp::Ordering, collections::HashMap, iter::Iterator};
@@ -14,6 +21,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = &'static str;
     type Output = usize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2019-D14-P1".to_owned()
@@ -43,6 +51,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = &'static str;
     type Output = usize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2019-D14-P2".to_owned()
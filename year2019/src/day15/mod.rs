@@ -1,6 +1,7 @@
 use crate::intcode::{IntcodeComputer, PauseReason};
 use advent_lib::{
-    cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    cases::{DayCase, DayRunner, DefaultError, Puzzle},
+    error::AdventError,
     twodee::{Dir4, Grid, HashGrid, Point},
 };
 use itertools::Itertools;
@@ -11,7 +12,7 @@ use std::{
 };
 
 pub fn get_puzzles() -> Vec<Box<dyn Puzzle>> {
-    vec![Box::new(Part1), Box::new(Part2)]
+    Day15.into_puzzles()
 }
 
 // TODO this uses A* with a lot of repetition. It would probably be better
@@ -19,96 +20,77 @@ pub fn get_puzzles() -> Vec<Box<dyn Puzzle>> {
 // dijkstra's, or something fancier?
 
 #[derive(Debug)]
-pub struct Part1;
+pub struct Day15;
 
-impl PuzzleRunner for Part1 {
+impl DayRunner for Day15 {
     type Input = Vec<isize>;
-    type Output = usize;
+    type Shared = HashGrid<Area>;
+    type Output1 = usize;
+    type Output2 = usize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
-        "2019-D15-P1".to_owned()
+        "2019-D15".to_owned()
     }
 
-    fn cases(&self) -> Result<Vec<Box<dyn PuzzleCase>>, Box<dyn std::error::Error>> {
-        Ok(GenericPuzzleCase::<Self, _, _>::build_set()
+    fn cases(&self) -> Result<Vec<DayCase<Self>>, Box<dyn std::error::Error>> {
+        Ok(DayCase::<Self>::build_set()
             .add_transform(parse_input)
-            .transformed_case("Solution", include_str!("input"), 272)?
+            .transformed_case("Solution", include_str!("input"), 272, 398)?
             .collect())
     }
 
-    fn run_puzzle(program: Self::Input) -> Self::Output {
-        let grid = explore_grid(program).expect("Problem exploring grid");
-        let walkable_tiles = {
-            let mut rv = HashSet::new();
-            rv.insert(Area::Floor);
-            rv.insert(Area::OxygenSystem);
-            rv.insert(Area::Robot);
-            rv.insert(Area::Goal);
-            rv
-        };
-        let (o2_position, _) = grid
-            .cells
-            .iter()
-            .find(|(_, area)| **area == Area::OxygenSystem)
-            .expect("No answer found");
-
-        grid.astar(Point::zero(), *o2_position, &walkable_tiles)
-            .expect("no path found")
-            .len()
-            - 1
+    fn prepare(program: &Self::Input) -> Result<Self::Shared, Self::Error> {
+        Ok(explore_grid(program.clone())?)
     }
-}
 
-#[derive(Debug)]
-pub struct Part2;
-
-impl PuzzleRunner for Part2 {
-    type Input = Vec<isize>;
-    type Output = usize;
+    fn part1(_program: &Self::Input, grid: &Self::Shared) -> Result<Self::Output1, Self::Error> {
+        let walkable_tiles = walkable_tiles();
+        let o2_position = find_oxygen_system(grid)?;
 
-    fn name(&self) -> String {
-        "2019-D15-P2".to_owned()
+        grid.astar(Point::zero(), o2_position, &walkable_tiles)
+            .map(|path| path.len() - 1)
+            .ok_or_else(|| AdventError::NoSolution.into())
     }
 
-    fn cases(&self) -> Result<Vec<Box<dyn PuzzleCase>>, Box<dyn std::error::Error>> {
-        Ok(GenericPuzzleCase::<Self, _, _>::build_set()
-            .add_transform(parse_input)
-            .transformed_case("Solution", include_str!("input"), 398)?
-            .collect())
-    }
+    fn part2(_program: &Self::Input, grid: &Self::Shared) -> Result<Self::Output2, Self::Error> {
+        let walkable_tiles = walkable_tiles();
+        let o2_position = find_oxygen_system(grid)?;
 
-    fn run_puzzle(program: Self::Input) -> Self::Output {
-        let grid = explore_grid(program).expect("Problem exploring grid");
-        let walkable_tiles = {
-            let mut rv = HashSet::new();
-            rv.insert(Area::Floor);
-            rv.insert(Area::OxygenSystem);
-            rv.insert(Area::Robot);
-            rv.insert(Area::Goal);
-            rv
-        };
-        let (o2_position, _) = grid
+        Ok(grid
             .cells
-            .iter()
-            .find(|(_, area)| **area == Area::OxygenSystem)
-            .expect("No answer found");
-
-        grid.cells
             .iter()
             .filter(|(_p, c)| walkable_tiles.contains(c))
             .map(|(p, _)| {
-                grid.astar(*o2_position, *p, &walkable_tiles)
+                grid.astar(o2_position, *p, &walkable_tiles)
                     .expect("no path")
                     .len()
                     - 1
             })
             .max()
-            .unwrap()
+            .unwrap())
     }
 }
 
+fn walkable_tiles() -> HashSet<Area> {
+    let mut rv = HashSet::new();
+    rv.insert(Area::Floor);
+    rv.insert(Area::OxygenSystem);
+    rv.insert(Area::Robot);
+    rv.insert(Area::Goal);
+    rv
+}
+
+fn find_oxygen_system(grid: &HashGrid<Area>) -> Result<Point<isize>, AdventError> {
+    grid.cells
+        .iter()
+        .find(|(_, area)| **area == Area::OxygenSystem)
+        .map(|(p, _)| *p)
+        .ok_or(AdventError::NoSolution)
+}
+
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-enum Area {
+pub enum Area {
     Origin,
     Wall,
     Floor,
@@ -141,14 +123,7 @@ fn explore_grid(program: Vec<isize>) -> Result<HashGrid<Area>, String> {
     let mut grid = HashGrid::<Area>::default();
     grid.set(Point::zero(), Area::Origin);
 
-    let walkable_tiles = {
-        let mut rv = HashSet::new();
-        rv.insert(Area::Floor);
-        rv.insert(Area::OxygenSystem);
-        rv.insert(Area::Robot);
-        rv.insert(Area::Goal);
-        rv
-    };
+    let walkable_tiles = walkable_tiles();
     let mut robot = IntcodeComputer::build(program).done();
 
     let mut position: Point<isize> = Point::zero();
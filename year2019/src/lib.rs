@@ -54,3 +54,6 @@ pub fn get_puzzles() -> Vec<Box<dyn Puzzle>> {
     .flatten()
     .collect()
 }
+
+#[cfg(feature = "plugin")]
+advent_lib::export_plugin!(get_puzzles);
@@ -1,3 +0,0 @@
-fn main() {
-    lalrpop::process_root().unwrap();
-}
@@ -1,5 +1,6 @@
 use advent_lib::{
-    cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    cases::{DefaultError, GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    error::AdventError,
     helpers::StringAdventExt,
 };
 use std::{collections::HashSet, iter::Iterator};
@@ -14,6 +15,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = Vec<u32>;
     type Output = u32;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2020-D01-P1".to_owned()
@@ -27,7 +29,7 @@ impl PuzzleRunner for Part1 {
             .collect())
     }
 
-    fn run_puzzle(input: Self::Input) -> Self::Output {
+    fn try_run_puzzle(input: Self::Input) -> Result<Self::Output, Self::Error> {
         // assumption: there are no duplicates
         let original_len = input.len();
         let numbers: HashSet<_> = input.into_iter().collect();
@@ -40,10 +42,10 @@ impl PuzzleRunner for Part1 {
                 continue;
             }
             if numbers.contains(&y) {
-                return x * y;
+                return Ok(x * y);
             }
         }
-        panic!("No answer found");
+        Err(AdventError::NoSolution.into())
     }
 }
 
@@ -53,6 +55,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = Vec<u32>;
     type Output = u32;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2020-D01-P2".to_owned()
@@ -70,7 +73,7 @@ impl PuzzleRunner for Part2 {
             .collect())
     }
 
-    fn run_puzzle(input: Self::Input) -> Self::Output {
+    fn try_run_puzzle(input: Self::Input) -> Result<Self::Output, Self::Error> {
         // assumption: there are no duplicates
         let numbers: HashSet<_> = input.clone().into_iter().collect();
         assert_eq!(input.len(), numbers.len());
@@ -85,11 +88,11 @@ impl PuzzleRunner for Part2 {
                     continue;
                 }
                 if numbers.contains(&z) {
-                    return x * y * z;
+                    return Ok(x * y * z);
                 }
             }
         }
-        panic!("No answer found");
+        Err(AdventError::NoSolution.into())
     }
 }
 
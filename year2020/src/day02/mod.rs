@@ -1,5 +1,5 @@
 use advent_lib::{
-    cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    cases::{DefaultError, GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
     helpers::StringAdventExt,
 };
 use std::iter::Iterator;
@@ -22,6 +22,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = Vec<Input>;
     type Output = usize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2020-D02-P1".to_owned()
@@ -57,6 +58,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = Vec<Input>;
     type Output = usize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2020-D02-P2".to_owned()
@@ -1,5 +1,5 @@
 use advent_lib::{
-    cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    cases::{DefaultError, GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
     helpers::StringAdventExt,
 };
 use indoc::indoc;
@@ -31,6 +31,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = Vec<Vec<bool>>;
     type Output = u64;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2020-D03-P1".to_owned()
@@ -71,6 +72,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = Vec<Vec<bool>>;
     type Output = u64;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2020-D03-P2".to_owned()
@@ -1,18 +1,11 @@
 use advent_lib::{
-    cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
-    helpers::StringAdventExt,
+    cases::{DefaultError, GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    helpers::{FieldValue, Record, RecordError, StringAdventExt},
 };
 use indoc::indoc;
+use once_cell::sync::Lazy;
 use regex::Regex;
-use std::{
-    collections::HashMap,
-    error::Error,
-    fmt::Display,
-    iter::Iterator,
-    lazy::SyncLazy,
-    ops::RangeBounds,
-    str::FromStr,
-};
+use std::{error::Error, fmt::Display, iter::Iterator, str::FromStr};
 
 pub fn get_puzzles() -> Vec<Box<dyn Puzzle>> {
     vec![Box::new(Part1), Box::new(Part2)]
@@ -22,8 +15,9 @@ pub fn get_puzzles() -> Vec<Box<dyn Puzzle>> {
 pub struct Part1;
 
 impl PuzzleRunner for Part1 {
-    type Input = Vec<Passport>;
+    type Input = Vec<Result<Passport, RecordError>>;
     type Output = usize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2020-D04-P1".to_owned()
@@ -59,7 +53,10 @@ impl PuzzleRunner for Part1 {
     fn run_puzzle(input: Self::Input) -> Self::Output {
         input
             .iter()
-            .filter(|passport| passport.required_fields_present())
+            .filter(|passport| match passport {
+                Ok(_) => true,
+                Err(err) => !err.is_missing_fields(),
+            })
             .count()
     }
 }
@@ -68,8 +65,9 @@ impl PuzzleRunner for Part1 {
 pub struct Part2;
 
 impl PuzzleRunner for Part2 {
-    type Input = Vec<Passport>;
+    type Input = Vec<Result<Passport, RecordError>>;
     type Output = usize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2020-D04-P2".to_owned()
@@ -120,119 +118,37 @@ impl PuzzleRunner for Part2 {
     }
 
     fn try_run_puzzle(input: Self::Input) -> Result<Self::Output, Self::Error> {
-        Ok(input.iter().filter(|passport| passport.is_valid()).count())
+        Ok(input.iter().filter(|passport| passport.is_ok()).count())
     }
 }
 
-fn parse_input(input: &str) -> Result<Vec<Passport>, Box<dyn Error>> {
-    input
-        .paragraphs()
-        .map(|p| p.parse())
-        .collect::<Result<Vec<_>, PassportError>>()
-        .map_err(|err| err.into())
+fn parse_input(input: &str) -> Result<Vec<Result<Passport, RecordError>>, Box<dyn Error>> {
+    Ok(input.records().collect())
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Record)]
 pub struct Passport {
-    birth_year: PassportField<u16>,
-    issue_year: PassportField<u16>,
-    expiry_year: PassportField<u16>,
-    height: PassportField<Height>,
-    hair_color: PassportField<Color>,
-    eye_color: PassportField<EyeColor>,
-    passport_id: PassportField<String>,
-    country_id: PassportField<String>,
-}
-
-impl Passport {
-    fn from_data(data: &HashMap<String, String>) -> Result<Self, PassportError> {
-        let mut passport = Self::default();
-        for (key, value) in data {
-            passport.set_from_str(key, value)?;
-        }
-        Ok(passport)
-    }
-
-    fn set_from_str(&mut self, key: &str, value: &str) -> Result<(), PassportError> {
-        // let required: Vec<_> = vec!["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"]
-        match key {
-            "byr" => self.birth_year.ranged_set(Some(value), 1920..=2002),
-            "iyr" => self.issue_year.ranged_set(Some(value), 2010..=2020),
-            "eyr" => self.expiry_year.ranged_set(Some(value), 2020..=2030),
-            "hcl" => self.hair_color.set(Some(value)),
-            "ecl" => self.eye_color.set(Some(value)),
-
-            "hgt" => self
-                .height
-                .validated_set(Some(value), |height| match height {
-                    Height::Inches(h) => (59..=76).contains(h),
-                    Height::Centimeters(h) => (150..=193).contains(h),
-                }),
-
-            "pid" => self
-                .passport_id
-                .validated_set(Some(value), |s| s.len() == 9),
-
-            "cid" => self.country_id.validated_set(Some(value), |s| s.len() == 9),
-
-            _ => return Err(PassportError::UnknownKey(key.to_string())),
-        }
-        Ok(())
-    }
-
-    pub fn required_fields_present(&self) -> bool {
-        [
-            self.birth_year != PassportField::Missing,
-            self.issue_year != PassportField::Missing,
-            self.expiry_year != PassportField::Missing,
-            self.height != PassportField::Missing,
-            self.hair_color != PassportField::Missing,
-            self.eye_color != PassportField::Missing,
-            self.passport_id != PassportField::Missing,
-            // country id is not required
-        ]
-        .iter()
-        .all(|p| *p)
-    }
-
-    pub fn is_valid(&self) -> bool {
-        [
-            matches!(self.birth_year, PassportField::Valid(_)),
-            matches!(self.issue_year, PassportField::Valid(_)),
-            matches!(self.expiry_year, PassportField::Valid(_)),
-            matches!(self.height, PassportField::Valid(_)),
-            matches!(self.hair_color, PassportField::Valid(_)),
-            matches!(self.eye_color, PassportField::Valid(_)),
-            matches!(self.passport_id, PassportField::Valid(_)),
-            // country id is not required or validated
-        ]
-        .iter()
-        .all(|p| *p)
-    }
-}
-
-impl FromStr for Passport {
-    type Err = PassportError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let data = s.split_whitespace()
-                .map(|part| {
-                    // split each part into pairs
-                    part.split_once(':')
-                        .map(|(a, b)| (a.to_string(), b.to_string()))
-                        .ok_or(PassportError::MissingColon)
-                })
-                // to build into a hashmap
-                .collect::<Result<HashMap<String, String>, _>>()?;
-        Passport::from_data(&data)
-    }
+    #[record(key = "byr", range(1920, 2002))]
+    birth_year: u16,
+    #[record(key = "iyr", range(2010, 2020))]
+    issue_year: u16,
+    #[record(key = "eyr", range(2020, 2030))]
+    expiry_year: u16,
+    #[record(key = "hgt", check = "Height::is_valid")]
+    height: Height,
+    #[record(key = "hcl")]
+    hair_color: Color,
+    #[record(key = "ecl")]
+    eye_color: EyeColor,
+    #[record(key = "pid", len = 9)]
+    passport_id: String,
+    // country id is not required or validated
+    #[record(key = "cid")]
+    country_id: Option<String>,
 }
 
 #[derive(Clone, Debug)]
 pub enum PassportError {
-    MissingColon,
-    UnknownKey(String),
-    InvalidEyeColor(String),
     NumberSyntax(std::num::ParseIntError),
     InvalidUnit(String),
     InvalidColor(String),
@@ -241,10 +157,7 @@ pub enum PassportError {
 impl Display for PassportError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            PassportError::MissingColon => write!(f, "Missing colon"),
-            PassportError::UnknownKey(key) => write!(f, "Unknown key {}", key),
             PassportError::InvalidColor(s) => write!(f, "Invalid color {}", s),
-            PassportError::InvalidEyeColor(s) => write!(f, "Invalid eye color {}", s),
             PassportError::InvalidUnit(u) => write!(f, "Invalid unit {}", u),
             PassportError::NumberSyntax(err) => write!(f, "Could not parse number: {}", err),
         }
@@ -253,51 +166,21 @@ impl Display for PassportError {
 
 impl Error for PassportError {}
 
-#[derive(Clone, Debug, PartialEq)]
-pub enum PassportField<T> {
-    Valid(T),
-    Invalid(String),
-    Missing,
-}
-
-impl<T: FromStr> PassportField<T> {
-    fn set(&mut self, value: Option<&str>) {
-        self.validated_set(value, |_| true)
-    }
-
-    fn ranged_set<R>(&mut self, value: Option<&str>, range: R)
-    where
-        R: RangeBounds<T>,
-        T: PartialOrd,
-    {
-        self.validated_set(value, |v| range.contains(v))
-    }
-
-    fn validated_set<F>(&mut self, value: Option<&str>, is_valid: F)
-    where
-        F: Fn(&T) -> bool,
-    {
-        *self = value
-            .map(|s| match s.parse() {
-                Ok(parsed) if is_valid(&parsed) => Self::Valid(parsed),
-                _ => Self::Invalid(s.to_string()),
-            })
-            .unwrap_or(Self::Missing);
-    }
-}
-
-impl<T> Default for PassportField<T> {
-    fn default() -> Self {
-        Self::Missing
-    }
-}
-
 #[derive(Clone, Debug, Copy, PartialEq)]
 pub enum Height {
     Inches(u8),
     Centimeters(u8),
 }
 
+impl Height {
+    fn is_valid(&self) -> bool {
+        match self {
+            Self::Inches(h) => (59..=76).contains(h),
+            Self::Centimeters(h) => (150..=193).contains(h),
+        }
+    }
+}
+
 impl FromStr for Height {
     type Err = PassportError;
 
@@ -312,38 +195,28 @@ impl FromStr for Height {
     }
 }
 
-#[derive(Clone, Debug, Copy, PartialEq)]
+#[derive(Clone, Debug, Copy, PartialEq, FieldValue)]
 pub enum EyeColor {
+    #[record(value = "amb")]
     Amber,
+    #[record(value = "blu")]
     Blue,
+    #[record(value = "brn")]
     Brown,
+    #[record(value = "gry")]
     Grey,
+    #[record(value = "grn")]
     Green,
+    #[record(value = "hzl")]
     Hazel,
+    #[record(value = "oth")]
     Other,
 }
 
-impl FromStr for EyeColor {
-    type Err = PassportError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(match s {
-            "amb" => Self::Amber,
-            "blu" => Self::Blue,
-            "brn" => Self::Brown,
-            "gry" => Self::Grey,
-            "grn" => Self::Green,
-            "hzl" => Self::Hazel,
-            "oth" => Self::Other,
-            _ => return Err(PassportError::InvalidEyeColor(s.to_string())),
-        })
-    }
-}
-
 #[derive(Clone, Debug, PartialEq)]
 pub struct Color(String);
 
-static COLOR_RE: SyncLazy<Regex> = SyncLazy::new(|| Regex::new("^#[0-9a-f]{6}$").unwrap());
+static COLOR_RE: Lazy<Regex> = Lazy::new(|| Regex::new("^#[0-9a-f]{6}$").unwrap());
 
 impl FromStr for Color {
     type Err = PassportError;
@@ -1,6 +1,7 @@
 use advent_lib::{
-    cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
-    helpers::{Bounds, StringAdventExt},
+    cases::{DefaultError, GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    extremes,
+    helpers::StringAdventExt,
 };
 use std::{collections::HashSet, iter::Iterator};
 
@@ -14,6 +15,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = Vec<String>;
     type Output = i32;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2020-D05-P1".to_owned()
@@ -48,6 +50,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = Vec<String>;
     type Output = i32;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2020-D05-P2".to_owned()
@@ -62,7 +65,7 @@ impl PuzzleRunner for Part2 {
 
     fn run_puzzle(input: Self::Input) -> Self::Output {
         let seen_seats: HashSet<_> = input.into_iter().map(|s| seat_id(&s)).collect();
-        let (min_seat, max_seat) = seen_seats.iter().bounds().unwrap();
+        let (min_seat, max_seat) = extremes(&seen_seats).unwrap();
         let all_seats: HashSet<_> = (*min_seat..=*max_seat).into_iter().collect();
         *all_seats.difference(&seen_seats).next().unwrap()
     }
@@ -1,5 +1,5 @@
 use advent_lib::{
-    cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    cases::{DefaultError, GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
     helpers::StringAdventExt,
 };
 use indoc::indoc;
@@ -19,6 +19,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = Vec<Rule>;
     type Output = usize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2020-D07-P1".to_owned()
@@ -108,6 +109,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = Vec<Rule>;
     type Output = usize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2020-D07-P2".to_owned()
@@ -1,6 +1,8 @@
 use advent_lib::{
-    cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    cases::{DefaultError, GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    error::AdventError,
     helpers::StringAdventExt,
+    parse_fmt,
 };
 use indoc::indoc;
 use rayon::prelude::*;
@@ -17,18 +19,10 @@ pub struct Instruction {
 }
 
 impl FromStr for Instruction {
-    type Err = String;
+    type Err = AdventError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (op, data) = s
-            .split_once(' ')
-            .ok_or(format!("Syntax error: no space in `{}`", s))?;
-        let op = op.parse()?;
-        let data = data
-            .trim_start_matches('+')
-            .parse()
-            .map_err(|err| format!("Syntax error, invalid data: `{}`", err))?;
-
+        let (op, data) = parse_fmt!("{op} {data}", s => (Operation, i32))?;
         Ok(Self { op, data })
     }
 }
@@ -103,6 +97,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = Vec<Instruction>;
     type Output = i32;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2020-D08-P1".to_owned()
@@ -152,6 +147,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = Vec<Instruction>;
     type Output = i32;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2020-D08-P2".to_owned()
@@ -242,13 +238,10 @@ impl PuzzleRunner for Part2 {
         answer_rx
             .iter()
             .next()
-            .ok_or_else(|| "No answer found".into())
+            .ok_or_else(|| AdventError::NoSolution.into())
     }
 }
 
 fn parse_input(input: &str) -> Result<Vec<Instruction>, Box<dyn Error>> {
-    Ok(input
-        .trimmed_lines()
-        .map(str::parse)
-        .collect::<Result<Vec<_>, _>>()?)
+    Ok(input.parse_lines().collect::<Result<Vec<_>, _>>()?)
 }
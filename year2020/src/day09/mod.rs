@@ -1,6 +1,8 @@
 use advent_lib::{
-    cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
-    helpers::{Bounds, StringAdventExt},
+    cases::{DefaultError, GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    error::AdventError,
+    extremes,
+    helpers::StringAdventExt,
 };
 use itertools::Itertools;
 use std::{error::Error, iter::Iterator};
@@ -15,6 +17,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = (usize, Vec<u64>);
     type Output = u64;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2020-D09-P1".to_owned()
@@ -55,7 +58,7 @@ impl PuzzleRunner for Part1 {
             log.push(n);
         }
 
-        Err("No answer".into())
+        Err(AdventError::NoSolution.into())
     }
 }
 
@@ -65,6 +68,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = (usize, Vec<u64>);
     type Output = u64;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2020-D09-P2".to_owned()
@@ -98,9 +102,7 @@ impl PuzzleRunner for Part2 {
                 match sum.cmp(&goal) {
                     std::cmp::Ordering::Less => (),
                     std::cmp::Ordering::Equal => {
-                        return input[start..=end]
-                            .iter()
-                            .bounds()
+                        return extremes(&input[start..=end])
                             .ok_or_else(|| "Matched range was empty?".into())
                             .map(|(min, max)| *min + *max)
                     }
@@ -109,15 +111,11 @@ impl PuzzleRunner for Part2 {
             }
         }
 
-        Err("No answer found".into())
+        Err(AdventError::NoSolution.into())
     }
 }
 
 fn parse_input(input: &str) -> Result<(usize, Vec<u64>), Box<dyn Error>> {
-    input
-        .trimmed_lines()
-        .map(str::parse)
-        .collect::<Result<Vec<u64>, _>>()
-        .map_err(|err| err.into())
-        .map(|vec| (25, vec))
+    let numbers = input.parse_lines().collect::<Result<Vec<u64>, _>>()?;
+    Ok((25, numbers))
 }
@@ -1,8 +1,8 @@
 use advent_lib::{
-    cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    cases::{DefaultError, GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
     helpers::StringAdventExt,
 };
-use std::{collections::HashMap, error::Error, iter::Iterator, num::ParseIntError};
+use std::{collections::HashMap, error::Error, iter::Iterator};
 
 pub fn get_puzzles() -> Vec<Box<dyn Puzzle>> {
     vec![Box::new(Part1), Box::new(Part2)]
@@ -14,6 +14,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = Vec<i32>;
     type Output = i32;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2020-D10-P1".to_owned()
@@ -63,6 +64,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = Vec<i32>;
     type Output = u64;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2020-D10-P2".to_owned()
@@ -127,9 +129,5 @@ impl PuzzleRunner for Part2 {
 }
 
 fn parse_input(input: &str) -> Result<Vec<i32>, Box<dyn Error>> {
-    input
-        .trimmed_lines()
-        .map(str::parse)
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|err: ParseIntError| err.into())
+    Ok(input.parse_lines().collect::<Result<Vec<_>, _>>()?)
 }
@@ -1,19 +1,12 @@
 use advent_lib::{
-    cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
-    helpers::StringAdventExt,
-    twodee::Bounds,
-    twodee::{Dir8, Grid, Point, VecGrid},
+    cases::{DayCase, DayRunner, DefaultError, Puzzle},
+    twodee::{Dir8, Grid, VecGrid},
 };
 use indoc::indoc;
-use std::{
-    convert::{TryFrom, TryInto},
-    error::Error,
-    fmt::Display,
-    iter::Iterator,
-};
+use std::{convert::TryFrom, error::Error, fmt::Display, iter::Iterator};
 
 pub fn get_puzzles() -> Vec<Box<dyn Puzzle>> {
-    vec![Box::new(Part1), Box::new(Part2)]
+    Day11.into_puzzles()
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -53,35 +46,25 @@ impl TryFrom<char> for Space {
 }
 
 fn parse_input(input: &str) -> Result<VecGrid<isize, Space>, Box<dyn Error>> {
-    let lines: Vec<_> = input.trimmed_lines().collect();
-    let width = lines[0].len();
-    let height = lines.len();
-    assert!(lines.iter().all(|l| l.len() == width));
-
-    let mut grid = VecGrid::new(Bounds::new(0, 0, width as isize, height as isize));
-
-    for (y, line) in lines.into_iter().enumerate() {
-        for (x, char) in line.chars().enumerate() {
-            grid.set(Point::new(x as isize, y as isize), char.try_into()?);
-        }
-    }
-
-    Ok(grid)
+    Ok(VecGrid::parse(input)?)
 }
 
 #[derive(Debug)]
-pub struct Part1;
+pub struct Day11;
 
-impl PuzzleRunner for Part1 {
+impl DayRunner for Day11 {
     type Input = VecGrid<isize, Space>;
-    type Output = usize;
+    type Shared = ();
+    type Output1 = usize;
+    type Output2 = usize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
-        "2020-D11-P1".to_owned()
+        "2020-D11".to_owned()
     }
 
-    fn cases(&self) -> Result<Vec<Box<dyn PuzzleCase>>, Box<dyn std::error::Error>> {
-        Ok(GenericPuzzleCase::<Self, _, _>::build_set()
+    fn cases(&self) -> Result<Vec<DayCase<Self>>, Box<dyn std::error::Error>> {
+        Ok(DayCase::<Self>::build_set()
             .add_try_transform(parse_input)
             .transformed_case(
                 "Example",
@@ -98,14 +81,19 @@ impl PuzzleRunner for Part1 {
                 L.LLLLL.LL
             "},
                 37,
+                26,
             )?
-            .transformed_case("Solution", include_str!("input"), 2_296)?
+            .transformed_case("Solution", include_str!("input"), 2_296, 2_089)?
             .collect())
     }
 
-    fn try_run_puzzle(input: Self::Input) -> Result<Self::Output, Self::Error> {
+    fn prepare(_input: &Self::Input) -> Result<Self::Shared, Self::Error> {
+        Ok(())
+    }
+
+    fn part1(input: &Self::Input, _shared: &Self::Shared) -> Result<Self::Output1, Self::Error> {
         let mut current = input.clone();
-        let mut next = input;
+        let mut next = input.clone();
 
         loop {
             for p in current.iter_coords() {
@@ -137,45 +125,10 @@ impl PuzzleRunner for Part1 {
             .filter(|v| **v == Space::OccupiedSeat)
             .count())
     }
-}
-
-#[derive(Debug)]
-pub struct Part2;
-
-impl PuzzleRunner for Part2 {
-    type Input = VecGrid<isize, Space>;
-    type Output = usize;
-
-    fn name(&self) -> String {
-        "2020-D11-P1".to_owned()
-    }
-
-    fn cases(&self) -> Result<Vec<Box<dyn PuzzleCase>>, Box<dyn std::error::Error>> {
-        Ok(GenericPuzzleCase::<Self, _, _>::build_set()
-            .add_try_transform(parse_input)
-            .transformed_case(
-                "Example",
-                indoc! {"
-                L.LL.LL.LL
-                LLLLLLL.LL
-                L.L.L..L..
-                LLLL.LL.LL
-                L.LL.LL.LL
-                L.LLLLL.LL
-                ..L.L.....
-                LLLLLLLLLL
-                L.LLLLLL.L
-                L.LLLLL.LL
-            "},
-                26,
-            )?
-            .transformed_case("Solution", include_str!("input"), 2_089)?
-            .collect())
-    }
 
-    fn try_run_puzzle(input: Self::Input) -> Result<Self::Output, Self::Error> {
+    fn part2(input: &Self::Input, _shared: &Self::Shared) -> Result<Self::Output2, Self::Error> {
         let mut current = input.clone();
-        let mut next = input;
+        let mut next = input.clone();
 
         loop {
             for p in current.iter_coords() {
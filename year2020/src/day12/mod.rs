@@ -1,5 +1,5 @@
 use advent_lib::{
-    cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    cases::{DefaultError, GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
     helpers::StringAdventExt,
     twodee::{Dir4, Point, Turn},
 };
@@ -10,11 +10,7 @@ pub fn get_puzzles() -> Vec<Box<dyn Puzzle>> {
 }
 
 fn parse_input(input: &str) -> Result<Vec<Instruction>, Box<dyn Error>> {
-    input
-        .trimmed_lines()
-        .map(str::parse)
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|err: String| err.into())
+    Ok(input.parse_lines().collect::<Result<Vec<_>, _>>()?)
 }
 
 #[derive(Clone, Debug)]
@@ -66,6 +62,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = Vec<Instruction>;
     type Output = i32;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2020-D12-P1".to_owned()
@@ -106,6 +103,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = Vec<Instruction>;
     type Output = i32;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2020-D12-P2".to_owned()
@@ -1,6 +1,8 @@
 use advent_lib::{
-    cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    cases::{DefaultError, GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    error::AdventError,
     helpers::StringAdventExt,
+    property::{shrink_vec, PropertyCase, Rng},
 };
 use std::{error::Error, fmt::Debug, iter::Iterator};
 
@@ -65,6 +67,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = (u64, Vec<BusSpec>);
     type Output = u64;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2020-D13-P1".to_owned()
@@ -83,7 +86,7 @@ impl PuzzleRunner for Part1 {
             .into_iter()
             .map(|spec| (spec.schedule, now + (spec.schedule - now % spec.schedule)))
             .min_by_key(|(_id, next)| *next)
-            .ok_or_else(|| "No answer found".into())
+            .ok_or_else(|| AdventError::NoSolution.into())
             .map(|(id, next)| id * (next - now))
     }
 }
@@ -94,6 +97,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = Vec<BusSpec>;
     type Output = u64;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2020-D13-P2".to_owned()
@@ -109,12 +113,23 @@ impl PuzzleRunner for Part2 {
             .transformed_case("Example 5", "0\n67,7,x,59,61", 1_261_476)?
             .transformed_case("Example 6", "0\n1789,37,47,1889", 1_202_161_486)?
             .transformed_case("Solution", include_str!("input"), 535_296_695_251_210)?
+            .property_case(
+                PropertyCase::<Self>::new("Random", generate_bus_specs, |bus_specs| {
+                    part2_naive(bus_specs)
+                })
+                .with_shrink(|bus_specs| {
+                    shrink_vec(bus_specs, |_| vec![])
+                        .into_iter()
+                        .filter(|smaller| !smaller.is_empty())
+                        .collect()
+                }),
+            )
             .collect())
     }
 
     fn try_run_puzzle(bus_specs: Self::Input) -> Result<Self::Output, Self::Error> {
         if bus_specs.is_empty() {
-            return Err("No buses".into());
+            return Err(AdventError::invalid_input("No buses").into());
         }
         Ok(part2_helper(
             // ((bus_specs[0].index)..).step_by(bus_specs[0].schedule as usize),
@@ -127,7 +142,7 @@ impl PuzzleRunner for Part2 {
 fn part2_helper<I: Iterator<Item = u64> + Debug>(
     steps: I,
     bus_specs: &[BusSpec],
-) -> Result<u64, String> {
+) -> Result<u64, AdventError> {
     // Iterate through `steps` until the first item in the list matches twice
     // (or stop early if the first match also matches the rest of the list).
     // After the second time it matches, increase the step size to the
@@ -141,7 +156,8 @@ fn part2_helper<I: Iterator<Item = u64> + Debug>(
     // progress through the list.
 
     if bus_specs.is_empty() {
-        return Err("No more buses (should this have returned previously?)".into());
+        // should this have returned previously?
+        return Err(AdventError::NoSolution);
     }
 
     let next = bus_specs[0];
@@ -175,5 +191,29 @@ fn part2_helper<I: Iterator<Item = u64> + Debug>(
         }
     }
 
-    Err("No answer found".into())
+    Err(AdventError::NoSolution)
+}
+
+/// Check every timestamp in turn. Only practical for small inputs.
+fn part2_naive(bus_specs: &[BusSpec]) -> Option<u64> {
+    let period: u64 = bus_specs.iter().map(|spec| spec.schedule).product();
+    (0..period).find(|t| {
+        bus_specs
+            .iter()
+            .all(|spec| (t + spec.index) % spec.schedule == 0)
+    })
+}
+
+/// A few buses with small, distinct, prime schedules, like the real input.
+fn generate_bus_specs(rng: &mut Rng) -> Vec<BusSpec> {
+    let mut primes = vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    let mut index = 0;
+    (0..rng.range(1..=4))
+        .map(|_| {
+            let schedule = primes.remove(rng.below(primes.len()));
+            let spec = BusSpec { index, schedule };
+            index += rng.range(1..=4) as u64;
+            spec
+        })
+        .collect()
 }
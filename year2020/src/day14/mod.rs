@@ -1,5 +1,5 @@
 use advent_lib::{
-    cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    cases::{DefaultError, GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
     helpers::StringAdventExt,
 };
 use indoc::indoc;
@@ -16,11 +16,7 @@ pub fn get_puzzles() -> Vec<Box<dyn Puzzle>> {
 }
 
 fn parse_input(input: &str) -> Result<Vec<Assignment>, Box<dyn Error>> {
-    input
-        .trimmed_lines()
-        .map(str::parse)
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|err: String| err.into())
+    Ok(input.parse_lines().collect::<Result<Vec<_>, _>>()?)
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -201,6 +197,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = Vec<Assignment>;
     type Output = u64;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2020-D14-P1".to_owned()
@@ -252,6 +249,7 @@ struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = Vec<Assignment>;
     type Output = u64;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2020-D14-P2".to_owned()
@@ -1,4 +1,8 @@
-use advent_lib::cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner};
+use advent_lib::{
+    cases::{DefaultError, GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    context::RunContext,
+    error::AdventError,
+};
 use std::{collections::HashMap, iter::Iterator};
 
 pub fn get_puzzles() -> Vec<Box<dyn Puzzle>> {
@@ -13,9 +17,11 @@ struct MemoryGame {
 }
 
 impl MemoryGame {
-    fn new(starting_numbers: Vec<u32>) -> Result<Self, String> {
+    fn new(starting_numbers: Vec<u32>) -> Result<Self, AdventError> {
         if starting_numbers.is_empty() {
-            return Err("Starting numbers can't be empty".into());
+            return Err(AdventError::invalid_input(
+                "Starting numbers can't be empty",
+            ));
         }
         let last_seen = HashMap::new();
         Ok(Self {
@@ -55,12 +61,27 @@ impl Iterator for MemoryGame {
     }
 }
 
+/// Play the game for `turns` turns, and return the last number spoken.
+fn play(starting_numbers: Vec<u32>, turns: usize, ctx: &RunContext) -> Result<u32, AdventError> {
+    let mut game = MemoryGame::new(starting_numbers)?;
+    let mut last = None;
+    for turn in 0..turns {
+        if turn % 0x10_0000 == 0 {
+            ctx.progress(turn, turns);
+            ctx.check()?;
+        }
+        last = game.next();
+    }
+    last.ok_or(AdventError::NoSolution)
+}
+
 #[derive(Debug)]
 pub struct Part1;
 
 impl PuzzleRunner for Part1 {
     type Input = Vec<u32>;
     type Output = u32;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2020-D15-P1".to_owned()
@@ -78,11 +99,11 @@ impl PuzzleRunner for Part1 {
             .collect())
     }
 
-    fn try_run_puzzle(input: Self::Input) -> Result<Self::Output, Self::Error> {
-        // 2019 because `.nth()` is 0-indexed
-        MemoryGame::new(input)?
-            .nth(2019)
-            .ok_or_else(|| "No answer".into())
+    fn try_run_puzzle_with_context(
+        input: Self::Input,
+        ctx: &RunContext,
+    ) -> Result<Self::Output, Self::Error> {
+        Ok(play(input, 2020, ctx)?)
     }
 }
 
@@ -92,6 +113,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = Vec<u32>;
     type Output = u32;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2020-D15-P2".to_owned()
@@ -110,9 +132,10 @@ impl PuzzleRunner for Part2 {
             .collect())
     }
 
-    fn try_run_puzzle(input: Self::Input) -> Result<Self::Output, Self::Error> {
-        MemoryGame::new(input)?
-            .nth(29_999_999)
-            .ok_or_else(|| "No answer".into())
+    fn try_run_puzzle_with_context(
+        input: Self::Input,
+        ctx: &RunContext,
+    ) -> Result<Self::Output, Self::Error> {
+        Ok(play(input, 30_000_000, ctx)?)
     }
 }
@@ -1,5 +1,5 @@
 use advent_lib::{
-    cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    cases::{DefaultError, GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
     helpers::StringAdventExt,
 };
 use indoc::indoc;
@@ -33,36 +33,30 @@ pub struct PuzzleInput {
 }
 
 fn parse_input(input: &str) -> Result<PuzzleInput, Box<dyn Error>> {
-    let mut iter = input.paragraphs();
+    let mut sections = input.split_sections(|line| line.ends_with(':'));
 
-    let ranges = iter
+    let ranges = sections
         .next()
         .ok_or("No validity ranges found")?
-        .trimmed_lines()
+        .lines
+        .into_iter()
         .map(str::parse)
         .collect::<Result<_, _>>()?;
 
-    let my_ticket = Ticket(
-        iter.next()
-            .ok_or("My ticket not found")?
-            .trimmed_lines()
-            .nth(1)
-            .ok_or("no my ticket data")?
-            .split(',')
-            .map(str::parse)
-            .collect::<Result<_, _>>()?,
-    );
-
-    let nearby_tickets = iter
+    let my_ticket = sections
+        .next()
+        .ok_or("My ticket not found")?
+        .lines
+        .first()
+        .ok_or("no my ticket data")?
+        .parse()?;
+
+    let nearby_tickets = sections
         .next()
         .ok_or("No nearby tickets")?
-        .trimmed_lines()
-        .skip(1)
-        .map(|line| -> Result<Ticket, ParseIntError> {
-            Ok(Ticket(
-                line.split(',').map(str::parse).collect::<Result<_, _>>()?,
-            ))
-        })
+        .lines
+        .into_iter()
+        .map(str::parse)
         .collect::<Result<_, _>>()?;
 
     Ok(PuzzleInput {
@@ -77,27 +71,29 @@ impl FromStr for ValidityRange {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let (name, range_strs) = s.split_once(": ").ok_or("no colon")?;
-        let ranges = range_strs
-            .split(" or ")
-            .map(|p| -> Result<_, Self::Err> {
-                let range_parts: Vec<u32> = p
-                    .split('-')
-                    .map(str::parse::<u32>)
-                    .collect::<Result<_, _>>()
-                    .map_err(|err| err.to_string())?;
-                let a = range_parts.get(0).ok_or("no first part")?;
-                let b = range_parts.get(1).ok_or("no second part")?;
-                Ok(*a..=*b)
-            })
-            .collect::<Result<_, _>>()?;
+        let bounds: Vec<u32> = range_strs
+            .try_unsigned_ints()
+            .collect::<Result<_, _>>()
+            .map_err(|err| format!("Bad bound in {}: {}", range_strs, err))?;
+        if bounds.is_empty() || bounds.len() % 2 != 0 {
+            return Err(format!("Expected pairs of bounds in {}", range_strs));
+        }
 
         Ok(Self {
             name: name.to_string(),
-            ranges,
+            ranges: bounds.chunks(2).map(|pair| pair[0]..=pair[1]).collect(),
         })
     }
 }
 
+impl FromStr for Ticket {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.comma_separated().collect::<Result<_, _>>()?))
+    }
+}
+
 impl ValidityRange {
     fn contains(&self, field: u32) -> bool {
         self.ranges.iter().any(|range| range.contains(&field))
@@ -120,6 +116,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = PuzzleInput;
     type Output = u32;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2020-D16-P1".to_owned()
@@ -170,6 +167,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = PuzzleInput;
     type Output = u64;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2020-D16-P2".to_owned()
@@ -254,3 +252,17 @@ impl PuzzleRunner for Part2 {
         Ok(result_prod)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validity_range_parse() {
+        let range: ValidityRange = "class: 1-3 or 5-7".parse().unwrap();
+        assert_eq!(range.name, "class");
+        assert_eq!(range.ranges, vec![1..=3, 5..=7]);
+        assert!("class: 1-99999999999".parse::<ValidityRange>().is_err());
+        assert!("class: 1-3 or 5".parse::<ValidityRange>().is_err());
+    }
+}
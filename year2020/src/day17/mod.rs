@@ -1,4 +1,5 @@
 use advent_lib::cases::Puzzle;
+use std::{convert::TryFrom, fmt};
 
 mod part1;
 mod part2;
@@ -6,3 +7,24 @@ mod part2;
 pub fn get_puzzles() -> Vec<Box<dyn Puzzle>> {
     vec![Box::new(part1::Runner), Box::new(part2::Runner)]
 }
+
+/// An active cube in the starting slice. Inactive cubes aren't stored.
+#[derive(Clone, Copy, Debug)]
+struct Active;
+
+impl TryFrom<char> for Active {
+    type Error = String;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '#' => Ok(Self),
+            _ => Err(format!("Invalid cube character {}", c)),
+        }
+    }
+}
+
+impl fmt::Display for Active {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#")
+    }
+}
@@ -1,15 +1,11 @@
+use super::Active;
 use advent_lib::{
-    cases::{GenericPuzzleCase, PuzzleCase, PuzzleRunner},
-    helpers::StringAdventExt,
-    twodee::PointAxe,
-};
-use std::{
-    collections::HashMap,
-    error::Error,
-    hash::Hash,
-    iter::{Iterator, Step},
-    ops::Neg,
+    bounds::{Bounds, Coordinates},
+    cases::{DefaultError, GenericPuzzleCase, PuzzleCase, PuzzleRunner},
+    error::AdventError,
+    twodee::{axis_range, HashGrid, PointAxe},
 };
+use std::{collections::HashMap, error::Error, hash::Hash, iter::Iterator, ops::Neg};
 
 fn parse_input<P>(input: &str) -> Result<HashGrid4<P>, Box<dyn Error>>
 where
@@ -48,19 +44,37 @@ impl<P> Point4<P> {
     }
 }
 
+impl<P: PointAxe> Coordinates for Point4<P> {
+    type Axis = P;
+
+    const DIMENSIONS: usize = 4;
+
+    fn axis(&self, dimension: usize) -> P {
+        [self.x, self.y, self.z, self.w][dimension]
+    }
+
+    fn from_axes<F: FnMut(usize) -> P>(mut f: F) -> Self {
+        let x = f(0);
+        let y = f(1);
+        let z = f(2);
+        let w = f(3);
+        Self { x, y, z, w }
+    }
+}
+
 impl<P> Point4<P>
 where
-    P: PointAxe + Neg<Output = P> + Step,
+    P: PointAxe + Neg<Output = P>,
 {
     fn neighbors80(&self) -> Vec<Point4<P>> {
         let zero = P::zero();
         let one = P::one();
 
         let mut rv = Vec::with_capacity(26);
-        for dx in -one..=one {
-            for dy in -one..=one {
-                for dz in -one..=one {
-                    for dw in -one..=one {
+        for dx in axis_range(-one, one) {
+            for dy in axis_range(-one, one) {
+                for dz in axis_range(-one, one) {
+                    for dw in axis_range(-one, one) {
                         if dx == zero && dy == zero && dz == zero && dw == zero {
                             continue;
                         }
@@ -82,45 +96,18 @@ impl<P> HashGrid4<P>
 where
     P: PointAxe + Hash + From<i32> + Ord,
 {
-    fn from_2d_slice(s: &str) -> Result<Self, String> {
-        let mut cells = HashMap::new();
-        for (x, line) in s.trimmed_lines().enumerate() {
-            for (y, c) in line.chars().enumerate() {
-                cells.insert(
-                    Point4::new(x as i32, y as i32, P::zero(), P::zero()),
-                    match c {
-                        '.' => Ok(false),
-                        '#' => Ok(true),
-                        x => Err(format!("Invalid cell {}", x)),
-                    }?,
-                );
-            }
-        }
+    fn from_2d_slice(s: &str) -> Result<Self, AdventError> {
+        let slice = HashGrid::<Active, i32>::parse_sparse(s, |c| c != '.')?;
+        let cells = slice
+            .cells
+            .keys()
+            .map(|p| (Point4::new(p.x, p.y, P::zero(), P::zero()), true))
+            .collect();
         Ok(Self { cells })
     }
 
-    fn bounds(&self) -> Option<(Point4<P>, Point4<P>)> {
-        let mut mins: Option<Point4<P>> = None;
-        let mut maxs: Option<Point4<P>> = None;
-
-        for p in self.cells.keys() {
-            mins = Some(if let Some(Point4 { x, y, z, w }) = mins {
-                Point4::new(x.min(p.x), y.min(p.y), z.min(p.z), w.min(p.w))
-            } else {
-                *p
-            });
-            maxs = Some(if let Some(Point4 { x, y, z, w }) = maxs {
-                Point4::new(x.max(p.x), y.max(p.y), z.max(p.z), w.max(p.w))
-            } else {
-                *p
-            });
-        }
-
-        match (mins, maxs) {
-            (Some(min), Some(max)) => Some((min, max)),
-            (None, None) => None,
-            _ => panic!("Bug"),
-        }
+    fn bounds(&self) -> Bounds<Point4<P>> {
+        self.cells.keys().copied().collect()
     }
 }
 
@@ -130,9 +117,10 @@ pub struct Runner;
 impl PuzzleRunner for Runner {
     type Input = HashGrid4<i32>;
     type Output = usize;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
-        "2020-D17-P1".to_owned()
+        "2020-D17-P2".to_owned()
     }
 
     fn cases(&self) -> Result<Vec<Box<dyn PuzzleCase>>, Box<dyn std::error::Error>> {
@@ -148,28 +136,20 @@ impl PuzzleRunner for Runner {
         let mut next = HashGrid4::<i32>::default();
 
         for _ in 0..6 {
-            let (min, max) = current.bounds().ok_or("Empty grid")?;
-            for x in (min.x - 1)..=(max.x + 1) {
-                for y in (min.y - 1)..=(max.y + 1) {
-                    for z in (min.z - 1)..=(max.z + 1) {
-                        for w in (min.w - 1)..=(max.w + 1) {
-                            let p = Point4::new(x, y, z, w);
-                            let neighbor_count = p
-                                .neighbors80()
-                                .iter()
-                                .filter(|n| *current.cells.get(n).unwrap_or(&false))
-                                .count();
-                            let c = *current.cells.get(&p).unwrap_or(&false);
-                            next.cells.insert(
-                                p,
-                                match (c, neighbor_count) {
-                                    (true, n) => n == 2 || n == 3,
-                                    (false, n) => n == 3,
-                                },
-                            );
-                        }
-                    }
-                }
+            for p in current.bounds().expand(1).iter_points() {
+                let neighbor_count = p
+                    .neighbors80()
+                    .iter()
+                    .filter(|n| *current.cells.get(n).unwrap_or(&false))
+                    .count();
+                let c = *current.cells.get(&p).unwrap_or(&false);
+                next.cells.insert(
+                    p,
+                    match (c, neighbor_count) {
+                        (true, n) => n == 2 || n == 3,
+                        (false, n) => n == 3,
+                    },
+                );
             }
             current = next;
             next = HashGrid4::default();
@@ -1,25 +1,9 @@
 use advent_lib::{
-    cases::{GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    cases::{DefaultError, GenericPuzzleCase, Puzzle, PuzzleCase, PuzzleRunner},
+    expr::{Assoc, BinaryOp, Expr, Precedence},
     helpers::StringAdventExt,
 };
-use lalrpop_util::lalrpop_mod;
-
-#[cfg(windows)]
-lalrpop_mod!(
-    #[allow(clippy::all)]
-    #[allow(clippy::nursery)]
-    #[allow(clippy::pedantic)]
-    parser,
-    "\\day18\\parser.rs"
-);
-#[cfg(unix)]
-lalrpop_mod!(
-    #[allow(clippy::all)]
-    #[allow(clippy::nursery)]
-    #[allow(clippy::pedantic)]
-    parser,
-    "/day18/parser.rs"
-);
+use std::error::Error;
 
 pub fn get_puzzles() -> Vec<Box<dyn Puzzle>> {
     vec![Box::new(Part1), Box::new(Part2)]
@@ -31,6 +15,7 @@ pub struct Part1;
 impl PuzzleRunner for Part1 {
     type Input = &'static str;
     type Output = u64;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2020-D18-P1".to_owned()
@@ -57,14 +42,11 @@ impl PuzzleRunner for Part1 {
     }
 
     fn try_run_puzzle(input: Self::Input) -> Result<Self::Output, Self::Error> {
-        let parser = parser::P1ExprParser::new();
-        Ok(input
-            .trimmed_lines()
-            .map(|l| parser.parse(l))
-            .collect::<Result<Vec<u64>, _>>()
-            .map_err(|err| format!("Error: {}", err))?
-            .into_iter()
-            .sum())
+        // Addition and multiplication are evaluated left to right
+        sum_lines(
+            input,
+            &Precedence::new().level(Assoc::Left, &[BinaryOp::Add, BinaryOp::Mul]),
+        )
     }
 }
 
@@ -74,6 +56,7 @@ pub struct Part2;
 impl PuzzleRunner for Part2 {
     type Input = &'static str;
     type Output = u64;
+    type Error = DefaultError;
 
     fn name(&self) -> String {
         "2020-D18-P2".to_owned()
@@ -100,13 +83,21 @@ impl PuzzleRunner for Part2 {
     }
 
     fn try_run_puzzle(input: Self::Input) -> Result<Self::Output, Self::Error> {
-        let parser = parser::P2ExprParser::new();
-        Ok(input
-            .trimmed_lines()
-            .map(|l| parser.parse(l))
-            .collect::<Result<Vec<u64>, _>>()
-            .map_err(|err| format!("Error: {}", err))?
-            .into_iter()
-            .sum())
+        // Addition is evaluated before multiplication
+        sum_lines(
+            input,
+            &Precedence::new()
+                .level(Assoc::Left, &[BinaryOp::Mul])
+                .level(Assoc::Left, &[BinaryOp::Add]),
+        )
+    }
+}
+
+fn sum_lines(input: &str, precedence: &Precedence) -> Result<u64, Box<dyn Error>> {
+    let mut sum = 0;
+    for line in input.numbered_lines() {
+        let expr: Expr<u64> = line.parse_with(|text| Expr::parse(text, precedence))?;
+        sum += expr.eval()?;
     }
+    Ok(sum)
 }
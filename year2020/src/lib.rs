@@ -10,13 +10,6 @@
 
     clippy::use_self, // doesn't work well with generics
 )]
-#![feature(
-    str_split_once,
-    once_cell,
-    step_trait,
-    map_into_keys_values,
-    min_const_generics
-)]
 
 use advent_lib::cases::Puzzle;
 
@@ -65,3 +58,6 @@ pub fn get_puzzles() -> Vec<Box<dyn Puzzle>> {
     .flatten()
     .collect()
 }
+
+#[cfg(feature = "plugin")]
+advent_lib::export_plugin!(get_puzzles);